@@ -0,0 +1,61 @@
+extern crate orbtk;
+
+use orbtk::{Animation, Button, Canvas, Color, Easing, Label, Point, Rect, Window};
+use orbtk::timer;
+use orbtk::traits::{Click, Place, Text};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 420, 260), "Animation");
+
+    let label = Label::new();
+    label.position(10, 10).size(400, 16).text("0 seconds");
+    window.add(&label);
+
+    // the label counts the seconds with an interval timer
+    let seconds = Rc::new(Cell::new(0));
+    {
+        let label = label.clone();
+        let seconds = seconds.clone();
+        let id = timer::interval(Duration::from_secs(1));
+        timer::on_timer(id, move |_id| {
+            seconds.set(seconds.get() + 1);
+            label.text(format!("{} seconds", seconds.get()));
+        });
+    }
+
+    let canvas = Canvas::new();
+    canvas.position(10, 80).size(60, 60).background(Color::rgb(0, 120, 215));
+    window.add(&canvas);
+
+    let moved = Rc::new(Cell::new(false));
+    let button = Button::new();
+    button.position(10, 40).text("Move");
+    {
+        let canvas = canvas.clone();
+        button.on_click(move |_button: &Button, _point: Point| {
+            let from = canvas.rect.get();
+            let (to, color) = if moved.get() {
+                (Rect::new(10, 80, 60, 60), Color::rgb(0, 120, 215))
+            } else {
+                (Rect::new(250, 100, 160, 140), Color::rgb(215, 60, 0))
+            };
+            moved.set(!moved.get());
+
+            Animation::new(from, to, Duration::from_millis(600))
+                .easing(Easing::EaseInOut)
+                .start_cell(&canvas, |canvas: &Canvas| &canvas.rect);
+
+            let target = canvas.clone();
+            Animation::new(canvas.background.get(), color, Duration::from_millis(600))
+                .start(move |color| {
+                    target.background(color);
+                });
+        });
+    }
+    window.add(&button);
+
+    window.exec();
+}
@@ -1,6 +1,6 @@
 extern crate orbtk;
 
-use orbtk::{Color, Window, Image, Rect, Point, Renderer};
+use orbtk::{Canvas, Color, Window, Rect, Point};
 use orbtk::traits::{Click, Place};
 
 use std::rc::Rc;
@@ -11,16 +11,47 @@ fn main() {
 
     let click_pos: Rc<RefCell<Option<Point>>>= Rc::new(RefCell::new(None));
 
-    let canvas = Image::from_color(400, 400, Color::rgb(255, 255, 255));
+    let canvas = Canvas::new();
+    canvas.background(Color::rgb(255, 255, 255))
+        .stroke_width(2)
+        .antialiasing(true)
+        .on_draw(|painter| {
+            let width = painter.width();
+
+            painter.linear_gradient(
+                Rect::new(0, 0, width, 40),
+                Point::new(0, 0),
+                Point::new(width as i32, 0),
+                Color::rgb(82, 148, 226),
+                Color::rgb(255, 255, 255),
+            );
+            painter.text(10, 12, "Click and drag to draw", Color::rgb(0, 0, 0));
+
+            painter.stroke_rect(10, 60, 100, 60, Color::rgb(60, 60, 60));
+            painter.fill_rounded_rect(130, 60, 100, 60, 8, Color::rgb(92, 184, 92));
+            painter.stroke_circle(300, 90, 30, Color::rgb(226, 82, 82));
+            painter.fill_circle(370, 90, 20, Color::rgb(226, 148, 82));
+            painter.arc(60, 200, 40, 180.0, 360.0, Color::rgb(82, 148, 226));
+            painter.fill_polygon(
+                &[Point::new(130, 230), Point::new(180, 150), Point::new(230, 230)],
+                Color::rgb(148, 82, 226),
+            );
+            painter.polyline(
+                &[Point::new(260, 230), Point::new(290, 160), Point::new(320, 230), Point::new(350, 160)],
+                Color::rgb(0, 0, 0),
+            );
+        });
     canvas.position(10, 10)
-        .on_click(move |canvas: &Image, point: Point| {
+        .size(400, 400)
+        .on_click(move |canvas: &Canvas, point: Point| {
             let click = click_pos.clone();
             {
                 let mut prev_opt = click.borrow_mut();
 
                 if let Some(prev_position) = *prev_opt {
-                    let mut image = canvas.image.borrow_mut();
-                    image.line(prev_position.x, prev_position.y, point.x, point.y, orbtk::Color::rgb(0, 0, 0));
+                    canvas.paint(|painter| {
+                        painter.line(prev_position.x, prev_position.y, point.x, point.y, Color::rgb(0, 0, 0));
+                    });
                     *prev_opt = Some(point);
                 } else {
                     *prev_opt = Some(point);
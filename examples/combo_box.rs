@@ -0,0 +1,35 @@
+extern crate orbtk;
+
+use orbtk::{ComboBox, ComboBoxFilter, Label, Rect, Window};
+use orbtk::traits::{Change, Place, Text};
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 420, 400), "ComboBox");
+
+    let label = Label::new();
+    label.position(10, 10).size(400, 16).text("Type to search a country");
+    window.add(&label);
+
+    let countries = [
+        "Argentina", "Australia", "Austria", "Belgium", "Brazil", "Canada", "Chile", "Denmark",
+        "Finland", "France", "Germany", "Greece", "Iceland", "Ireland", "Italy", "Japan",
+    ];
+
+    let combo_box = ComboBox::new();
+    combo_box.position(10, 36);
+    combo_box.editable(true).filter(ComboBoxFilter::Fuzzy).custom_text(true);
+    for country in countries.iter() {
+        combo_box.push(country);
+    }
+
+    let label_clone = label.clone();
+    combo_box.on_change(move |combo_box: &ComboBox, text: String| {
+        match combo_box.selected() {
+            -1 => label_clone.text(format!("Custom: {}", text)),
+            index => label_clone.text(format!("Selected {}: {}", index, text)),
+        };
+    });
+    window.add(&combo_box);
+
+    window.exec();
+}
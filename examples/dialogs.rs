@@ -0,0 +1,50 @@
+extern crate orbtk;
+
+use orbtk::{Button, Label, MessageBox, MessageBoxResult, Rect, Window};
+use orbtk::traits::{Click, Place, Text};
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 420, 300), "OrbTK");
+
+    let label = Label::new();
+    label.position(10, 60).size(400, 16);
+    window.add(&label);
+
+    let info_button = Button::new();
+    info_button.position(10, 10).size(120, 30).text("Info").text_offset(6, 6);
+    let dialogs = window.dialogs().clone();
+    let info_label = label.clone();
+    info_button.on_click(move |_, _| {
+        let info_label = info_label.clone();
+        dialogs.show(&MessageBox::info("Info", "The file was saved.", move |_| {
+            info_label.text("Info closed");
+        }));
+    });
+    window.add(&info_button);
+
+    let question_button = Button::new();
+    question_button.position(140, 10).size(120, 30).text("Question").text_offset(6, 6);
+    let dialogs = window.dialogs().clone();
+    let question_label = label.clone();
+    question_button.on_click(move |_, _| {
+        let question_label = question_label.clone();
+        dialogs.show(&MessageBox::question("Unsaved changes", "Save the changes before closing?", move |result| {
+            question_label.text(match result {
+                MessageBoxResult::Yes => "Saved",
+                MessageBoxResult::No => "Discarded",
+                _ => "Cancelled",
+            });
+        }));
+    });
+    window.add(&question_button);
+
+    let error_button = Button::new();
+    error_button.position(270, 10).size(120, 30).text("Error").text_offset(6, 6);
+    let dialogs = window.dialogs().clone();
+    error_button.on_click(move |_, _| {
+        dialogs.show(&MessageBox::error("Error", "The file could not be opened:\npermission denied", |_| {}));
+    });
+    window.add(&error_button);
+
+    window.exec();
+}
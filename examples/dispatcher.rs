@@ -0,0 +1,69 @@
+extern crate orbtk;
+
+use orbtk::{Button, Label, Point, ProgressBar, Rect, Window};
+use orbtk::traits::{Click, Place, Text};
+use std::thread;
+use std::time::Duration;
+
+/// The progress of the copy, sent from the worker thread.
+struct Copied {
+    bytes: i32,
+    total: i32,
+}
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 420, 110), "Dispatcher");
+
+    let progress_bar = ProgressBar::new();
+    progress_bar.position(10, 10)
+        .size(400, 24)
+        .text_format(Some("{value} of {maximum} bytes"));
+    window.add(&progress_bar);
+
+    let label = Label::new();
+    label.position(10, 44).size(400, 16).text("Ready");
+    window.add(&label);
+
+    // the handler runs on the thread of the window, so it could use the widgets
+    {
+        let progress_bar = progress_bar.clone();
+        let label = label.clone();
+        window.on_message(move |_window: &Window, copied: Copied| {
+            progress_bar.range(0, copied.total).value(copied.bytes);
+            if copied.bytes == copied.total {
+                label.text("Done");
+            }
+        });
+    }
+
+    let button = Button::new();
+    button.position(10, 70).text("Copy");
+    {
+        let label = label.clone();
+        let dispatcher = window.dispatcher();
+        button.on_click(move |_button: &Button, _point: Point| {
+            label.text("Copying...");
+
+            // a stand-in for a file copy that reports its progress
+            let dispatcher = dispatcher.clone();
+            thread::spawn(move || {
+                let total = 64 * 1024;
+                let mut bytes = 0;
+                while bytes < total {
+                    thread::sleep(Duration::from_millis(20));
+                    bytes = (bytes + 1024).min(total);
+                    if !dispatcher.send(Copied { bytes, total }) {
+                        return;
+                    }
+                }
+
+                dispatcher.invoke(|window: &Window| {
+                    window.set_title("Dispatcher - copied");
+                });
+            });
+        });
+    }
+    window.add(&button);
+
+    window.exec();
+}
@@ -1,7 +1,7 @@
 extern crate orbtk;
 
-use orbtk::{Window, TextBox, Rect, Label, Event};
-use orbtk::traits::{EventFilter, Place, Text};
+use orbtk::{CharClass, Window, TextBox, Rect, Label, Event, Validator};
+use orbtk::traits::{Change, EventFilter, Place, Text};
 
 fn main() {
     let mut window = Window::new(Rect::new(100, 100, 420, 420), "Filtered Textbox");
@@ -31,25 +31,56 @@ fn main() {
 
     let label = Label::new();
     label.text("Field below will only accept numbers \n(as defined by unicode)")
-         .position(10, 32+28+6).size(400, 32);       
+         .position(10, 32+28+6).size(400, 32);
     window.add(&label);
 
     let text_field = TextBox::new();
-    text_field.position(10, 32+32+12+28).size(400, 28).event_filter(|_widget, event, _focused, _redraw| {
-       match event {
-            Event::KeyPressed (key_event) => {
-                if let Some(character) = key_event.character {
-                    if character.is_numeric() {
-                        return Some(event)
-                    }       
-                } 
-            },
-            _ => {}
-        }
+    text_field.position(10, 32+32+12+28).size(400, 28)
+        .validator(Validator::new().allow(CharClass::Numeric));
+    text_field.text_offset(6, 6);
+    window.add(&text_field);
 
-        Some(event)
-    });
+    let label = Label::new();
+    label.text("Number between 0 and 100")
+         .position(10, 148).size(400, 16);
+    window.add(&label);
+
+    let error_label = Label::new();
+    error_label.position(10, 202).size(400, 16);
+    window.add(&error_label);
+
+    let text_field = TextBox::new();
+    text_field.position(10, 168).size(400, 28)
+        .validator(Validator::new().numeric(0.0, 100.0))
+        .on_change(move |text_box: &TextBox, text: String| {
+            if text_box.is_valid() {
+                error_label.text.set(String::new());
+            } else {
+                error_label.text.set(format!("{} is out of range", text));
+            }
+        });
+    text_field.text_offset(6, 6);
+    window.add(&text_field);
 
+    let label = Label::new();
+    label.text("Date (####-##-##)")
+         .position(10, 226).size(400, 16);
+    window.add(&label);
+
+    let text_field = TextBox::new();
+    text_field.position(10, 246).size(400, 28)
+        .validator(Validator::new().mask("####-##-##"));
+    text_field.text_offset(6, 6);
+    window.add(&text_field);
+
+    let label = Label::new();
+    label.text("At most 8 letters")
+         .position(10, 282).size(400, 16);
+    window.add(&label);
+
+    let text_field = TextBox::new();
+    text_field.position(10, 302).size(400, 28)
+        .validator(Validator::new().allow(CharClass::Alphabetic).max_length(8));
     text_field.text_offset(6, 6);
     window.add(&text_field);
 
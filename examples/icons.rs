@@ -0,0 +1,91 @@
+extern crate orbtk;
+extern crate orbimage;
+
+use orbtk::{Action, Button, Color, Entry, IconPlacement, Image, Label, List, Menu, MenuBar, Point, Rect, WindowBuilder};
+use orbtk::icons;
+use orbtk::theme::{Selector, Theme};
+use orbtk::traits::{Click, Place, Style, Text};
+
+fn main() {
+    // icons are loaded from res/icons/<name>.png, or registered by name
+    icons::add_dir("res/icons");
+    icons::register("document-new", orbimage::Image::from_color(16, 16, Color::rgb(82, 148, 226)));
+    icons::register("document-save", orbimage::Image::from_color(16, 16, Color::rgb(92, 184, 92)));
+
+    let theme = Theme::parse("
+        button.new, action.new { icon: \"document-new\"; }
+        button.save { icon: document-save; }
+    ");
+    let mut window = WindowBuilder::new(Rect::new(100, 100, 420, 320), "Icons")
+        .theme(theme)
+        .build();
+
+    let label = Label::new();
+    label.position(10, 200).size(400, 16);
+    window.add(&label);
+
+    let placements = [
+        ("Left", IconPlacement::Left),
+        ("Right", IconPlacement::Right),
+        ("Top", IconPlacement::Top),
+        ("Icon only", IconPlacement::IconOnly),
+    ];
+
+    let mut x = 10;
+    for &(text, placement) in placements.iter() {
+        let button = Button::new();
+        button.position(x, 34)
+            .text(text)
+            .icon(&Image::from_color(16, 16, Color::rgb(226, 148, 82)))
+            .icon_placement(placement);
+        let label = label.clone();
+        button.on_click(move |_button: &Button, _point: Point| {
+            label.text(format!("Clicked {}", text));
+        });
+        window.add(&button);
+        x += button.rect.get().width as i32 + 10;
+    }
+
+    // the icons of these buttons are set by the theme
+    let new_button = Button::new();
+    new_button.position(10, 90).text("New");
+    new_button.selector().set(Selector::new(Some("button")).with_class("new"));
+    window.add(&new_button);
+
+    let save_button = Button::new();
+    save_button.position(100, 90).text("Save");
+    save_button.selector().set(Selector::new(Some("button")).with_class("save"));
+    window.add(&save_button);
+
+    let list = List::new();
+    list.position(10, 130).size(400, 60);
+    for name in ["notes.txt", "todo.md"].iter() {
+        let entry = Entry::new(28);
+        entry.icon(&Image::from_color(16, 16, Color::rgb(180, 180, 180)));
+
+        let entry_label = Label::new();
+        entry_label.position(0, 6).size(200, 16).text(*name);
+        entry.add(&entry_label);
+        list.push(&entry);
+    }
+    window.add(&list);
+
+    let menu_bar = MenuBar::new();
+    menu_bar.position(0, 0).size(420, 24);
+    let file_menu = Menu::new("&File");
+    {
+        let action = Action::new("&New");
+        action.selector().set(Selector::new(Some("action")).with_class("new"));
+        file_menu.add(&action);
+    }
+    {
+        let action = Action::new("&Open");
+        action.icon(&Image::from_color(12, 12, Color::rgb(226, 148, 82)))
+            .icon_placement(IconPlacement::Right);
+        file_menu.add(&action);
+    }
+    menu_bar.add(&file_menu);
+    window.add(&menu_bar);
+
+    window.exec();
+}
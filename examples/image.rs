@@ -0,0 +1,80 @@
+extern crate orbtk;
+
+use orbtk::{Color, HorizontalAlignment, Image, Label, Rect, Resampling, Stretch, Thickness, VerticalAlignment, Window};
+use orbtk::traits::{Place, Text};
+
+use std::sync::Arc;
+
+/// A small checkerboard that shows the difference between the resampling filters.
+fn checkerboard() -> Arc<Image> {
+    let image = Image::new(8, 8);
+    {
+        let mut bitmap = image.image.borrow_mut();
+        for (i, pixel) in bitmap.data_mut().iter_mut().enumerate() {
+            *pixel = if (i % 8 + i / 8) % 2 == 0 {
+                Color::rgb(82, 148, 226)
+            } else {
+                Color::rgb(255, 255, 255)
+            };
+        }
+    }
+    image
+}
+
+/// A frame with a dark border of 4 pixels, used for nine-slice scaling.
+fn frame() -> Arc<Image> {
+    let image = Image::from_color(16, 16, Color::rgb(230, 230, 230));
+    {
+        let mut bitmap = image.image.borrow_mut();
+        for (i, pixel) in bitmap.data_mut().iter_mut().enumerate() {
+            let (x, y) = (i % 16, i / 16);
+            if x < 4 || y < 4 || x >= 12 || y >= 12 {
+                *pixel = Color::rgb(60, 60, 60);
+            }
+        }
+    }
+    image
+}
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 560, 300), "Image");
+
+    let modes = [
+        ("None", Stretch::None),
+        ("Fill", Stretch::Fill),
+        ("Uniform", Stretch::Uniform),
+        ("UniformToFill", Stretch::UniformToFill),
+    ];
+
+    for (i, &(name, stretch)) in modes.iter().enumerate() {
+        let x = 10 + i as i32 * 135;
+
+        let label = Label::new();
+        label.position(x, 10).size(125, 16).text(name);
+        window.add(&label);
+
+        let image = checkerboard();
+        image.position(x, 30).size(125, 80);
+        image.stretch(stretch)
+            .resampling(Resampling::Nearest)
+            .alignment(HorizontalAlignment::Center, VerticalAlignment::Center);
+        window.add(&image);
+
+        let image = checkerboard();
+        image.position(x, 120).size(125, 80);
+        image.stretch(stretch)
+            .alignment(HorizontalAlignment::Center, VerticalAlignment::Center);
+        window.add(&image);
+    }
+
+    let label = Label::new();
+    label.position(10, 210).size(540, 16).text("Nine-slice, the border keeps its width");
+    window.add(&label);
+
+    let image = frame();
+    image.position(10, 230).size(540, 60);
+    image.nine_slice(Some(Thickness::new(4, 4, 4, 4)));
+    window.add(&image);
+
+    window.exec();
+}
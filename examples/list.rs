@@ -1,26 +1,55 @@
 extern crate orbtk;
 
-use orbtk::{ Window, List, Rect, Entry, Button };
-use orbtk::traits::{ Place, Text, Click };
+use orbtk::{ Window, List, Rect, Entry, Button, Label, ObservableCollection, SelectionMode };
+use orbtk::traits::{ Change, Place, Text, Click };
 
 fn main() {
     let mut window = Window::new(Rect::new(100, 100, 420, 500), "OrbTK");
 
+    let items = ObservableCollection::from_vec((0..10).map(|i| format!("Item {}", i)).collect());
+
     let list = List::new();
     list.position(5, 5).size(400, 400);
+    list.selection_mode(SelectionMode::Extended);
+    list.on_change(|_, selected| { println!("selected: {:?}", selected); });
+    list.bind(&items, |item: &String| {
+        let label = Label::new();
+        label.text(item.clone()).position(10, 10).size(200, 16);
+
+        let entry = Entry::new(36);
+        let text = item.clone();
+        entry.on_click(move |_, _| { println!("{}", text); });
+        entry.add(&label);
+        entry
+    });
+
+    let add_button = Button::new();
+    add_button.text("Add").position(5, 415).size(80, 30);
+    let add_items = items.clone();
+    add_button.on_click(move |_, _| {
+        let count = add_items.len();
+        add_items.insert(0, format!("New item {}", count));
+    });
 
-    for i in 0..10 {
-        let button = Button::new();
-        button.text(format!("{}", i)).position(10, 10).size(50, 30);
+    let remove_button = Button::new();
+    remove_button.text("Remove").position(90, 415).size(80, 30);
+    let remove_items = items.clone();
+    let remove_list = list.clone();
+    remove_button.on_click(move |_, _| {
+        for i in remove_list.selected().into_iter().rev() {
+            remove_items.remove(i as usize);
+        }
+    });
 
-        let entry = Entry::new(60);
-        let _list_clone = list.clone();
-        entry.on_click(move |_, _| { println!("{}", i); });
-        entry.add(&button);
-        list.push(&entry);  
-    }
+    let clear_button = Button::new();
+    clear_button.text("Clear").position(175, 415).size(80, 30);
+    let list_clone = list.clone();
+    clear_button.on_click(move |_, _| { list_clone.clear_selection(); });
 
     window.add(&list);
+    window.add(&add_button);
+    window.add(&remove_button);
+    window.add(&clear_button);
 
     window.exec();
 }
@@ -0,0 +1,114 @@
+extern crate orbtk;
+
+use orbtk::{Action, ActionGroup, Color, Image, Label, Menu, MenuBar, Point, Rect, Separator, TextBox, Window};
+use orbtk::traits::{Click, Place, Text};
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 420, 300), "Menus");
+
+    let label = Label::new();
+    label.position(10, 40).size(400, 16).text("Press Alt+F, Alt+E or Alt+V");
+    window.add(&label);
+
+    // right click the label for its context menu, the text box has a default one
+    let label_menu = Menu::new("");
+    {
+        let action = Action::new("&Clear");
+        let label = label.clone();
+        action.on_click(move |_action: &Action, _point: Point| {
+            label.text("");
+        });
+        label_menu.add(&action);
+    }
+    window.context_menus().set(&label, &label_menu);
+
+    let text_box = TextBox::new();
+    text_box.position(10, 66).size(400, 28).text_offset(6, 6).text("Right click or press Shift+F10");
+    window.add(&text_box);
+
+    let menu_bar = MenuBar::new();
+    menu_bar.position(0, 0).size(420, 24);
+
+    let file_menu = Menu::new("&File");
+    {
+        let action = Action::new("&New");
+        action.icon(&Image::from_color(12, 12, Color::rgb(82, 148, 226)))
+            .shortcut("Ctrl+N".parse().ok());
+        let label = label.clone();
+        action.on_click(move |_action: &Action, _point: Point| {
+            label.text("New");
+        });
+        file_menu.add(&action);
+    }
+
+    {
+        let recent_menu = Menu::new("Recent");
+        for name in ["notes.txt", "todo.md", "main.rs"].iter() {
+            let action = Action::new(*name);
+            let label = label.clone();
+            action.on_click(move |_action: &Action, _point: Point| {
+                label.text(format!("Open {}", name));
+            });
+            recent_menu.add(&action);
+        }
+
+        let action = Action::new("Open &Recent");
+        action.submenu(&recent_menu);
+        file_menu.add(&action);
+    }
+
+    file_menu.add(&Separator::new());
+
+    {
+        let action = Action::new("&Quit");
+        action.shortcut("Ctrl+Q".parse().ok());
+        let label = label.clone();
+        action.on_click(move |_action: &Action, _point: Point| {
+            label.text("Quit");
+        });
+        file_menu.add(&action);
+    }
+
+    let edit_menu = Menu::new("&Edit");
+    for &(text, chord) in [("&Undo", "Ctrl+Z"), ("&Redo", "Ctrl+Shift+Z")].iter() {
+        let action = Action::new(text);
+        action.shortcut(chord.parse().ok());
+        let label = label.clone();
+        action.on_click(move |_action: &Action, _point: Point| {
+            label.text(text.replace("&", ""));
+        });
+        edit_menu.add(&action);
+    }
+
+    let view_menu = Menu::new("&View");
+    {
+        let action = Action::new("Show &Toolbar");
+        action.checkable(true).checked.set(true);
+        let label = label.clone();
+        action.on_click(move |action: &Action, _point: Point| {
+            label.text(format!("Toolbar: {}", action.is_checked()));
+        });
+        view_menu.add(&action);
+    }
+
+    view_menu.add(&Separator::new());
+
+    let zoom_group = ActionGroup::new();
+    for (i, text) in ["&Small", "&Medium", "&Large"].iter().enumerate() {
+        let action = Action::new(*text);
+        zoom_group.add(&action);
+        action.checked.set(i == 1);
+        let label = label.clone();
+        action.on_click(move |_action: &Action, _point: Point| {
+            label.text(format!("Zoom: {}", text.replace("&", "")));
+        });
+        view_menu.add(&action);
+    }
+
+    menu_bar.add(&file_menu);
+    menu_bar.add(&edit_menu);
+    menu_bar.add(&view_menu);
+    window.add(&menu_bar);
+
+    window.exec();
+}
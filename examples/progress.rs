@@ -0,0 +1,61 @@
+extern crate orbtk;
+
+use orbtk::{Button, Orientation, Point, ProgressBar, Rect, Slider, Window};
+use orbtk::traits::{Change, Click, Place, Text};
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 420, 220), "Progress");
+
+    let progress_bar = ProgressBar::new();
+    progress_bar.position(10, 10)
+        .size(360, 24)
+        .range(0, 200)
+        .value(50)
+        .text_format(Some("{value} of {maximum} ({percent}%)"));
+    window.add(&progress_bar);
+
+    let vertical_bar = ProgressBar::new();
+    vertical_bar.position(390, 10)
+        .size(20, 200)
+        .range(0, 200)
+        .value(50)
+        .orientation(Orientation::Vertical);
+    window.add(&vertical_bar);
+
+    let slider = Slider::new();
+    {
+        let progress_bar = progress_bar.clone();
+        let vertical_bar = vertical_bar.clone();
+        slider.position(10, 44)
+            .size(360, 16)
+            .range(0, 200)
+            .value(50)
+            .on_change(move |_slider: &Slider, value: i32| {
+                progress_bar.value(value);
+                vertical_bar.value(value);
+            });
+    }
+    window.add(&slider);
+
+    let busy_bar = ProgressBar::new();
+    busy_bar.position(10, 80)
+        .size(360, 24)
+        .indeterminate(true)
+        .text_format(Some("Working..."));
+    window.add(&busy_bar);
+
+    let button = Button::new();
+    button.position(10, 114).text("Stop");
+    {
+        let busy_bar = busy_bar.clone();
+        button.on_click(move |button: &Button, _point: Point| {
+            let running = !busy_bar.indeterminate.get();
+            busy_bar.indeterminate(running)
+                .text_format(if running { Some("Working...") } else { None });
+            button.text(if running { "Stop" } else { "Start" });
+        });
+    }
+    window.add(&button);
+
+    window.exec();
+}
@@ -0,0 +1,44 @@
+extern crate orbtk;
+
+use orbtk::{Button, Grid, Label, Rect, ScrollViewer, Window};
+use orbtk::traits::{Click, Place, Text};
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 420, 400), "ScrollViewer example");
+
+    let scroll_viewer = ScrollViewer::new();
+    scroll_viewer.position(10, 10)
+        .size(400, 330);
+
+    let grid = Grid::new();
+    grid.spacing(8, 8);
+
+    let mut last_label = None;
+    for row in 0..30 {
+        for col in 0..8 {
+            let label = Label::new();
+            label.size(80, 16).text(format!("Cell {}, {}", col, row));
+            grid.insert(col, row, &label);
+            last_label = Some(label);
+        }
+    }
+
+    scroll_viewer.content(&grid);
+    window.add(&scroll_viewer);
+
+    let button = Button::new();
+    {
+        let scroll_viewer = scroll_viewer.clone();
+        button.position(10, 350)
+            .size(120, 36)
+            .text("Go to end")
+            .on_click(move |_, _| {
+                if let Some(ref label) = last_label {
+                    scroll_viewer.scroll_into_view(label);
+                }
+            });
+    }
+    window.add(&button);
+
+    window.exec();
+}
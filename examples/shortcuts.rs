@@ -0,0 +1,69 @@
+extern crate orbtk;
+
+use orbtk::{Action, KeyChord, Label, Menu, Point, Rect, TextBox, Window};
+use orbtk::traits::{Click, Place, Text};
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 420, 200), "Shortcuts");
+
+    let menu = Menu::new("Menu");
+    menu.position(10, 0).size(32, 16);
+
+    let label = Label::new();
+    label.position(10, 26).size(400, 16).text("Press Ctrl+Shift+P or Ctrl+R");
+    window.add(&label);
+
+    let text_box = TextBox::new();
+    text_box.position(10, 52).size(400, 28).text_offset(6, 6);
+    window.add(&text_box);
+
+    let palette: KeyChord = "Ctrl+Shift+P".parse().unwrap();
+    let reset: KeyChord = "Ctrl+R".parse().unwrap();
+    let clear: KeyChord = "Esc".parse().unwrap();
+
+    {
+        let label = label.clone();
+        window.shortcuts().register("palette.open", palette, move |_window: &Window| {
+            label.text.set("Command palette".to_owned());
+        });
+    }
+
+    {
+        let label = label.clone();
+        window.shortcuts().register("label.reset", reset, move |_window: &Window| {
+            label.text.set("Press Ctrl+Shift+P or Ctrl+R".to_owned());
+        });
+    }
+
+    // Only active while the text box is focused
+    {
+        let text_box_clone = text_box.clone();
+        window.shortcuts().register_scoped("text-box.clear", clear, &text_box, move |_window: &Window| {
+            text_box_clone.text("");
+        });
+    }
+
+    {
+        let action = Action::new("Command Palette");
+        action.shortcut(window.shortcuts().chord("palette.open"));
+        let label = label.clone();
+        action.on_click(move |_action: &Action, _point: Point| {
+            label.text.set("Command palette".to_owned());
+        });
+        menu.add(&action);
+    }
+
+    {
+        let action = Action::new("Reset Label");
+        action.shortcut(window.shortcuts().chord("label.reset"));
+        let label = label.clone();
+        action.on_click(move |_action: &Action, _point: Point| {
+            label.text.set("Press Ctrl+Shift+P or Ctrl+R".to_owned());
+        });
+        menu.add(&action);
+    }
+
+    window.add(&menu);
+
+    window.exec();
+}
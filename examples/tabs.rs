@@ -0,0 +1,47 @@
+extern crate orbtk;
+
+use orbtk::{Button, Label, Rect, TabControl, TextBox, Window};
+use orbtk::traits::{Change, Place, Text};
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 420, 300), "TabControl example");
+
+    let status = Label::new();
+    status.position(10, 270)
+        .size(400, 16)
+        .text("Selected tab: 0");
+    window.add(&status);
+
+    let tab_control = TabControl::new();
+    tab_control.position(10, 10)
+        .size(400, 250);
+    tab_control.closable(true)
+        .on_change(move |_tab_control: &TabControl, selected: Option<usize>| {
+            match selected {
+                Some(index) => status.text(format!("Selected tab: {}", index)),
+                None => status.text("No tab left"),
+            };
+        });
+
+    let general = Label::new();
+    general.position(10, 10)
+        .size(200, 16)
+        .text("General settings");
+    tab_control.add_tab("General", &general);
+
+    let name = TextBox::new();
+    name.position(10, 10)
+        .size(200, 28)
+        .text_offset(6, 6)
+        .placeholder("Name");
+    tab_control.add_tab("Account", &name);
+
+    let button = Button::new();
+    button.position(10, 10)
+        .size(120, 36)
+        .text("Reset");
+    tab_control.add_tab("Advanced", &button);
+
+    window.add(&tab_control);
+    window.exec();
+}
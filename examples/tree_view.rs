@@ -0,0 +1,70 @@
+extern crate orbtk;
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use orbtk::{Label, Rect, TreeNode, TreeView, Window};
+use orbtk::traits::{Change, Place, Text};
+
+/// Create a node for the path, its entries are read when it is expanded.
+fn directory_node(path: PathBuf) -> Arc<TreeNode> {
+    let name = path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string_lossy().into_owned());
+    let node = TreeNode::new(name);
+
+    if path.is_dir() {
+        node.lazy(move |node: &TreeNode| {
+            if let Ok(read_dir) = fs::read_dir(&path) {
+                let mut paths: Vec<PathBuf> = read_dir.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect();
+                paths.sort();
+                for path in paths {
+                    node.add(&directory_node(path));
+                }
+            }
+        });
+    }
+
+    node
+}
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 420, 420), "TreeView example");
+
+    let status = Label::new();
+    status.position(10, 390)
+        .size(400, 16)
+        .text("Double click or press Enter to activate a node");
+    window.add(&status);
+
+    let tree_view = TreeView::new();
+    tree_view.position(10, 10)
+        .size(400, 370);
+
+    let root = directory_node(PathBuf::from("."));
+    root.expand();
+    tree_view.add(&root);
+
+    let width = TreeNode::new("width: 420");
+    width.add(&TreeNode::new("unit: px"));
+
+    let settings = TreeNode::new("settings.json");
+    settings.add(&width)
+        .add(&TreeNode::new("height: 420"));
+    tree_view.add(&settings);
+
+    {
+        let status = status.clone();
+        tree_view.on_change(move |_tree_view: &TreeView, node: Arc<TreeNode>| {
+            status.text(format!("Selected: {}", node.text.get()));
+        });
+    }
+
+    tree_view.on_activate(move |_tree_view: &TreeView, node: Arc<TreeNode>| {
+        status.text(format!("Activated: {}", node.text.get()));
+    });
+
+    window.add(&tree_view);
+    window.exec();
+}
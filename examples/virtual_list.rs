@@ -0,0 +1,54 @@
+extern crate orbtk;
+
+use std::sync::Arc;
+
+use orbtk::{DataGrid, Label, Rect, VirtualList, Window};
+use orbtk::theme::Selector;
+use orbtk::traits::{Change, Place, Text};
+
+fn main() {
+    let mut window = Window::new(Rect::new(100, 100, 840, 420), "VirtualList example");
+
+    // 100k log lines, only the visible ones get a label
+    let log = VirtualList::new();
+    log.position(10, 10)
+        .size(400, 370);
+    log.source(
+        100_000,
+        || {
+            let label = Label::new();
+            label.text_offset(4, 2);
+            // use the theme of list entries, so the selected row is highlighted
+            label.selector.set(Selector::new(Some("entry")));
+            label
+        },
+        |label: &Arc<Label>, index: usize| {
+            label.text(format!("{:06} message of the log", index));
+        },
+    );
+    window.add(&log);
+
+    let status = Label::new();
+    status.position(10, 390)
+        .size(820, 16)
+        .text("Click a header to sort, drag its border to resize");
+    window.add(&status);
+
+    let data_grid = DataGrid::new();
+    data_grid.position(430, 10)
+        .size(400, 370);
+    data_grid.column("Id", 64)
+        .column("Name", 160)
+        .column("Size", 96)
+        .source(100_000, |row, column| match column {
+            0 => row.to_string(),
+            1 => format!("file-{}.txt", (row * 7919) % 100_000),
+            _ => ((row * 104_729) % 65_536).to_string(),
+        })
+        .on_change(move |_data_grid: &DataGrid, row: usize| {
+            status.text(format!("Selected row {}", row));
+        });
+    window.add(&data_grid);
+
+    window.exec();
+}
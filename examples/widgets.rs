@@ -1,7 +1,7 @@
 extern crate orbtk;
 
-use orbtk::{Action, Button, ComboBox, Grid, Image, Label, Menu, Point, ProgressBar, Rect, Separator, TextBox, Window};
-use orbtk::traits::{Click, Enter, Place, Text};
+use orbtk::{Action, Button, CheckBox, ComboBox, Grid, Image, Label, Menu, Point, ProgressBar, RadioButton, RadioGroup, Rect, Separator, Slider, SpinBox, TextBox, Window};
+use orbtk::traits::{Change, Click, Enter, Place, Text};
 
 fn main() {
     let mut window = Window::new(Rect::new(100, 100, 420, 768), "OrbTK");
@@ -26,8 +26,10 @@ fn main() {
     let text_box = TextBox::new();
     text_box.position(x, y)
         .size(332, 28)
-        .text_offset(6, 6);
+        .text_offset(6, 6)
+        .placeholder("Type something");
     window.add(&text_box);
+    window.tooltips().set(&text_box, "Press Enter to copy the text\ninto the label above");
 
     let button = Button::new();
     button.position(x + text_box.rect.get().width as i32 + 8, y)
@@ -38,11 +40,12 @@ fn main() {
             text_box.emit_enter();
         });
     window.add(&button);
+    window.tooltips().set(&button, "Update the label");
 
     y += button.rect.get().height as i32 + 10;
 
     let progress_label = Label::new();
-    progress_label.text("Progress: 0%").position(x, y);
+    progress_label.text("Progress: 50%").position(x, y);
     window.add(&progress_label);
 
     y += progress_label.rect.get().height as i32 + 10;
@@ -50,16 +53,54 @@ fn main() {
     let progress_bar = ProgressBar::new();
     progress_bar.position(x, y)
         .size(400, 16)
-        .value(100)
-        .on_click(move |progress_bar: &ProgressBar, point: Point| {
-            let progress = point.x * 100 / progress_bar.rect.get().width as i32;
-            progress_label.text.set(format!("Progress: {}%", progress));
-            progress_bar.value.set(progress);
-        });
+        .value(50);
     window.add(&progress_bar);
 
     y += progress_bar.rect.get().height as i32 + 10;
 
+    let slider = Slider::new();
+    slider.position(x, y)
+        .size(400, 16)
+        .value(50)
+        .on_change(move |_slider: &Slider, value: i32| {
+            progress_label.text.set(format!("Progress: {}%", value));
+            progress_bar.value.set(value);
+        });
+    window.add(&slider);
+
+    y += slider.rect.get().height as i32 + 10;
+
+    let spin_box = SpinBox::new();
+    spin_box.position(x, y)
+        .range(-10, 10)
+        .step(2);
+    window.add(&spin_box);
+
+    y += spin_box.rect.get().height as i32 + 10;
+
+    let check_box = CheckBox::new();
+    check_box.position(x, y)
+        .text("Check box")
+        .tristate(true);
+    window.add(&check_box);
+
+    y += check_box.rect.get().height as i32 + 10;
+
+    let radio_group = RadioGroup::new();
+    let mut radio_x = x;
+    for i in 1..4 {
+        let radio_button = RadioButton::new();
+        radio_button.position(radio_x, y)
+            .text(format!("Option {}", i))
+            .checked(i == 1);
+        radio_group.add(&radio_button);
+        window.add(&radio_button);
+
+        radio_x += radio_button.rect.get().width as i32 + 10;
+    }
+
+    y += 16 + 10;
+
     let combo_box = ComboBox::new();
     combo_box.position(x, y);
 
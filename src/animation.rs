@@ -0,0 +1,299 @@
+//! Animations that change a value over time, e.g. the rect of a widget or a color.
+//!
+//! The window advances its running animations every frame and redraws while an animation
+//! is running. Values are interpolated between a start and an end value with an easing
+//! curve. Like timers, an animation belongs to the window whose event loop runs when it
+//! is started.
+
+use orbclient::Color;
+use std::cell::{Cell, RefCell};
+use std::mem;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use point::Point;
+use rect::Rect;
+use window::{current_window, WindowId};
+
+/// Maps the linear progress of an animation between 0 and 1 to the progress of its value.
+#[derive(Clone)]
+pub enum Easing {
+    Linear,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts fast and slows down.
+    EaseOut,
+    /// Starts and ends slowly.
+    EaseInOut,
+    Custom(Arc<dyn Fn(f64) -> f64>),
+}
+
+impl Easing {
+    pub fn apply(&self, t: f64) -> f64 {
+        match *self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => if t < 0.5 {
+                4.0 * t * t * t
+            } else {
+                1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+            },
+            Easing::Custom(ref func) => func(t),
+        }
+    }
+}
+
+/// A value that could be animated.
+pub trait Interpolate: Copy {
+    /// Get the value at `t` between `from` (0) and `to` (1).
+    fn interpolate(from: Self, to: Self, t: f64) -> Self;
+}
+
+impl Interpolate for f64 {
+    fn interpolate(from: Self, to: Self, t: f64) -> Self {
+        from + (to - from) * t
+    }
+}
+
+impl Interpolate for f32 {
+    fn interpolate(from: Self, to: Self, t: f64) -> Self {
+        f64::interpolate(from as f64, to as f64, t) as f32
+    }
+}
+
+impl Interpolate for i32 {
+    fn interpolate(from: Self, to: Self, t: f64) -> Self {
+        f64::interpolate(from as f64, to as f64, t).round() as i32
+    }
+}
+
+impl Interpolate for u32 {
+    fn interpolate(from: Self, to: Self, t: f64) -> Self {
+        f64::interpolate(from as f64, to as f64, t).round().max(0.0) as u32
+    }
+}
+
+impl Interpolate for u8 {
+    fn interpolate(from: Self, to: Self, t: f64) -> Self {
+        f64::interpolate(from as f64, to as f64, t).round().max(0.0).min(255.0) as u8
+    }
+}
+
+impl Interpolate for Point {
+    fn interpolate(from: Self, to: Self, t: f64) -> Self {
+        Point::new(i32::interpolate(from.x, to.x, t), i32::interpolate(from.y, to.y, t))
+    }
+}
+
+impl Interpolate for Rect {
+    fn interpolate(from: Self, to: Self, t: f64) -> Self {
+        Rect::new(
+            i32::interpolate(from.x, to.x, t),
+            i32::interpolate(from.y, to.y, t),
+            u32::interpolate(from.width, to.width, t),
+            u32::interpolate(from.height, to.height, t),
+        )
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(from: Self, to: Self, t: f64) -> Self {
+        Color::rgba(
+            u8::interpolate(from.r(), to.r(), t),
+            u8::interpolate(from.g(), to.g(), t),
+            u8::interpolate(from.b(), to.b(), t),
+            u8::interpolate(from.a(), to.a(), t),
+        )
+    }
+}
+
+/// Identifies a running animation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AnimationId(u64);
+
+struct Running {
+    id: AnimationId,
+    /// The window that advances the animation, `None` until a window advances it
+    window: Option<WindowId>,
+    start: Instant,
+    duration: Duration,
+    repeat: bool,
+    /// Sets the value for the linear progress, returns false if the animated value is gone.
+    step: Box<dyn Fn(f64) -> bool>,
+    complete_callback: Option<Rc<dyn Fn()>>,
+}
+
+thread_local! {
+    static ANIMATIONS: RefCell<Vec<Running>> = RefCell::new(vec![]);
+    static NEXT_ID: Cell<u64> = Cell::new(0);
+}
+
+/// Describes how a value is animated, `start` runs the animation.
+pub struct Animation<T> {
+    from: T,
+    to: T,
+    duration: Duration,
+    easing: Easing,
+    repeat: bool,
+    complete_callback: Option<Rc<dyn Fn()>>,
+}
+
+impl<T: Interpolate + 'static> Animation<T> {
+    pub fn new(from: T, to: T, duration: Duration) -> Self {
+        Animation {
+            from,
+            to,
+            duration,
+            easing: Easing::Linear,
+            repeat: false,
+            complete_callback: None,
+        }
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// A repeating animation starts again from the start value until it is stopped.
+    pub fn repeat(mut self, repeat: bool) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Called when the animation reached its end value. Not called for repeating animations
+    /// and animations that were stopped.
+    pub fn on_complete<F: Fn() + 'static>(mut self, func: F) -> Self {
+        self.complete_callback = Some(Rc::new(func));
+        self
+    }
+
+    /// Run the animation, `apply` is called with the value of every frame.
+    pub fn start<F: Fn(T) + 'static>(self, apply: F) -> AnimationId {
+        self.start_step(move |value| {
+            apply(value);
+            true
+        })
+    }
+
+    /// Run the animation on a cell of a widget or another shared value. The animation
+    /// stops if the owner of the cell is dropped.
+    pub fn start_cell<O: 'static>(self, owner: &Arc<O>, cell: fn(&O) -> &Cell<T>) -> AnimationId {
+        let owner = Arc::downgrade(owner);
+        self.start_step(move |value| match owner.upgrade() {
+            Some(owner) => {
+                cell(&owner).set(value);
+                true
+            }
+            None => false,
+        })
+    }
+
+    fn start_step<F: Fn(T) -> bool + 'static>(self, apply: F) -> AnimationId {
+        let id = NEXT_ID.with(|next_id| {
+            let id = next_id.get();
+            next_id.set(id + 1);
+            AnimationId(id)
+        });
+
+        let (from, to, easing) = (self.from, self.to, self.easing);
+        let running = Running {
+            id,
+            window: current_window(),
+            start: Instant::now(),
+            duration: self.duration,
+            repeat: self.repeat,
+            step: Box::new(move |t| apply(T::interpolate(from, to, easing.apply(t)))),
+            complete_callback: self.complete_callback,
+        };
+
+        // the first frame shows the start value
+        (running.step)(0.0);
+        ANIMATIONS.with(|animations| animations.borrow_mut().push(running));
+        id
+    }
+}
+
+/// Stop an animation, its value keeps the value of the last frame.
+pub fn stop(id: AnimationId) {
+    ANIMATIONS.with(|animations| animations.borrow_mut().retain(|running| running.id != id));
+}
+
+pub fn is_running(id: AnimationId) -> bool {
+    ANIMATIONS.with(|animations| animations.borrow().iter().any(|running| running.id == id))
+}
+
+/// Stop the animations of a window, e.g. when its event loop ended.
+pub fn stop_window(window: WindowId) {
+    ANIMATIONS.with(|animations| animations.borrow_mut().retain(|running| running.window != Some(window)));
+}
+
+/// Check if the animation is advanced by the window. An animation without a window is
+/// advanced by the first window.
+fn runs_in(running: &Running, window: WindowId) -> bool {
+    running.window.map_or(true, |running_window| running_window == window)
+}
+
+/// Returns true if an animation of the window is running.
+pub fn is_animating(window: WindowId) -> bool {
+    ANIMATIONS.with(|animations| animations.borrow().iter().any(|running| runs_in(running, window)))
+}
+
+/// Set the values of the running animations of the window for the given time and remove
+/// the animations that completed. Returns true if an animation changed its value.
+pub fn tick(window: WindowId, now: Instant) -> bool {
+    // the animations are taken out, their values and callbacks could start new animations
+    let (mut running, others): (Vec<Running>, Vec<Running>) = ANIMATIONS.with(|animations| {
+        mem::replace(&mut *animations.borrow_mut(), vec![])
+            .into_iter()
+            .partition(|running| runs_in(running, window))
+    });
+    ANIMATIONS.with(|animations| *animations.borrow_mut() = others);
+    if running.is_empty() {
+        return false;
+    }
+
+    for animation in running.iter_mut() {
+        animation.window = Some(window);
+    }
+
+    let mut completed = vec![];
+    running.retain(|animation| {
+        let elapsed = now.duration_since(animation.start);
+        let duration = animation.duration;
+        let elapsed_secs = elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 / 1e9;
+        let duration_secs = duration.as_secs() as f64 + duration.subsec_nanos() as f64 / 1e9;
+
+        let mut t = if duration_secs > 0.0 { elapsed_secs / duration_secs } else { 1.0 };
+        if animation.repeat {
+            t = t.fract();
+        }
+
+        if !(animation.step)(t.min(1.0)) {
+            return false;
+        }
+
+        if t >= 1.0 {
+            if let Some(ref complete_callback) = animation.complete_callback {
+                completed.push(complete_callback.clone());
+            }
+            return false;
+        }
+
+        true
+    });
+
+    ANIMATIONS.with(|animations| {
+        let mut animations = animations.borrow_mut();
+        running.extend(animations.drain(..));
+        *animations = running;
+    });
+
+    for complete_callback in completed {
+        complete_callback();
+    }
+
+    true
+}
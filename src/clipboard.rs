@@ -0,0 +1,58 @@
+//! The clipboard that is shared by the widgets of a thread.
+//!
+//! The window loads the text of the system clipboard before it handles key presses and
+//! mouse clicks, and stores the text that was set by a widget in the system clipboard
+//! after it handled the events.
+
+use std::cell::RefCell;
+
+struct Clipboard {
+    text: String,
+    /// Set if the text was changed by a widget and not yet stored in the system clipboard.
+    changed: bool,
+}
+
+thread_local! {
+    static CLIPBOARD: RefCell<Clipboard> = RefCell::new(Clipboard {
+        text: String::new(),
+        changed: false,
+    });
+}
+
+/// Get the text of the clipboard.
+pub fn text() -> String {
+    CLIPBOARD.with(|clipboard| clipboard.borrow().text.clone())
+}
+
+/// Set the text of the clipboard.
+pub fn set_text<S: Into<String>>(text: S) {
+    CLIPBOARD.with(|clipboard| {
+        let mut clipboard = clipboard.borrow_mut();
+        clipboard.text = text.into();
+        clipboard.changed = true;
+    });
+}
+
+/// Replace the text with the text of the system clipboard. A text that was set by a
+/// widget and not yet taken is kept.
+pub fn load(text: String) {
+    CLIPBOARD.with(|clipboard| {
+        let mut clipboard = clipboard.borrow_mut();
+        if !clipboard.changed {
+            clipboard.text = text;
+        }
+    });
+}
+
+/// Take the text that was set since the last call to store it in the system clipboard.
+pub fn take_changed() -> Option<String> {
+    CLIPBOARD.with(|clipboard| {
+        let mut clipboard = clipboard.borrow_mut();
+        if clipboard.changed {
+            clipboard.changed = false;
+            Some(clipboard.text.clone())
+        } else {
+            None
+        }
+    })
+}
@@ -0,0 +1,145 @@
+use std::cell::{Cell, Ref, RefCell};
+use std::sync::Arc;
+
+/// Describes how the items of an `ObservableCollection` changed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CollectionChange {
+    /// An item was inserted at the index
+    Insert(usize),
+    /// The item at the index was removed
+    Remove(usize),
+    /// The item at `from` was moved to `to`
+    Move { from: usize, to: usize },
+    /// All items were replaced
+    Reset,
+}
+
+/// Identifies a change callback of an `ObservableCollection`, see `subscribe`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+/// A list of items that notifies its observers about every insert, remove, move and reset.
+/// The change callbacks get the collection after the change and read its items with
+/// `borrow`. Clones share the same items.
+pub struct ObservableCollection<T: Clone + 'static> {
+    inner: Arc<RefCell<Vec<T>>>,
+    change_callbacks: Arc<RefCell<Vec<(SubscriptionId, Arc<dyn Fn(&ObservableCollection<T>, CollectionChange)>)>>>,
+    next_subscription: Arc<Cell<u64>>,
+}
+
+impl<T: Clone> ObservableCollection<T> {
+    pub fn new() -> Self {
+        ObservableCollection::from_vec(vec![])
+    }
+
+    pub fn from_vec(items: Vec<T>) -> Self {
+        ObservableCollection {
+            inner: Arc::new(RefCell::new(items)),
+            change_callbacks: Arc::new(RefCell::new(vec![])),
+            next_subscription: Arc::new(Cell::new(0)),
+        }
+    }
+
+    pub fn borrow(&self) -> Ref<'_, Vec<T>> {
+        self.inner.borrow()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<T> {
+        self.inner.borrow().get(index).cloned()
+    }
+
+    pub fn push(&self, item: T) {
+        let index = self.len();
+        self.insert(index, item);
+    }
+
+    /// Insert an item at the index. Panics if the index is greater than the length.
+    pub fn insert(&self, index: usize, item: T) {
+        self.inner.borrow_mut().insert(index, item);
+        self.raise_changed(CollectionChange::Insert(index));
+    }
+
+    pub fn remove(&self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let item = self.inner.borrow_mut().remove(index);
+        self.raise_changed(CollectionChange::Remove(index));
+        Some(item)
+    }
+
+    /// Move the item at `from` to the index `to`, the other items keep their order.
+    pub fn move_item(&self, from: usize, to: usize) {
+        {
+            let mut items = self.inner.borrow_mut();
+            if from >= items.len() || to >= items.len() || from == to {
+                return;
+            }
+
+            let item = items.remove(from);
+            items.insert(to, item);
+        }
+
+        self.raise_changed(CollectionChange::Move { from, to });
+    }
+
+    pub fn clear(&self) {
+        self.set(vec![]);
+    }
+
+    /// Replace all items.
+    pub fn set(&self, items: Vec<T>) {
+        *self.inner.borrow_mut() = items;
+        self.raise_changed(CollectionChange::Reset);
+    }
+
+    fn raise_changed(&self, change: CollectionChange) {
+        // nothing is borrowed while the callbacks run, they could change the collection and its callbacks
+        let change_callbacks: Vec<_> = self.change_callbacks
+            .borrow()
+            .iter()
+            .map(|&(_, ref callback)| callback.clone())
+            .collect();
+
+        for callback in change_callbacks {
+            callback(self, change)
+        }
+    }
+
+    pub fn on_changed<F: Fn(&ObservableCollection<T>, CollectionChange) + 'static>(&self, func: F) -> &Self {
+        self.subscribe(func);
+        self
+    }
+
+    /// Add a change callback like `on_changed`, it could be removed with `unsubscribe`.
+    pub fn subscribe<F: Fn(&ObservableCollection<T>, CollectionChange) + 'static>(&self, func: F) -> SubscriptionId {
+        let id = SubscriptionId(self.next_subscription.get());
+        self.next_subscription.set(id.0 + 1);
+        self.change_callbacks.borrow_mut().push((id, Arc::new(func)));
+        id
+    }
+
+    /// Remove a change callback that was added with `subscribe`.
+    pub fn unsubscribe(&self, id: SubscriptionId) {
+        self.change_callbacks.borrow_mut().retain(|&(callback_id, _)| callback_id != id);
+    }
+}
+
+impl<T: Clone> Clone for ObservableCollection<T> {
+    fn clone(&self) -> Self {
+        ObservableCollection {
+            inner: self.inner.clone(),
+            change_callbacks: self.change_callbacks.clone(),
+            next_subscription: self.next_subscription.clone(),
+        }
+    }
+}
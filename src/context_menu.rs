@@ -0,0 +1,138 @@
+use std::cell::{Cell, RefCell};
+use std::cmp::{max, min};
+use std::sync::Arc;
+
+use point::Point;
+use rect::Rect;
+use widgets::{Menu, Widget};
+
+struct ContextMenu {
+    widget: Arc<dyn Widget>,
+    menu: Arc<Menu>,
+}
+
+/// Opens the context menu of a widget when the right mouse button is released on it,
+/// or of the focused widget on the Menu key or Shift+F10. Widgets without a menu that
+/// was set here show their default context menu, see `Widget::context_menu`.
+/// Context menus use the same `Action` and `Separator` entries as a `Menu`.
+pub struct ContextMenuManager {
+    menus: RefCell<Vec<ContextMenu>>,
+    open: RefCell<Option<Arc<Menu>>>,
+    right_pressed: Cell<bool>,
+}
+
+impl ContextMenuManager {
+    pub fn new() -> Self {
+        ContextMenuManager {
+            menus: RefCell::new(vec![]),
+            open: RefCell::new(None),
+            right_pressed: Cell::new(false),
+        }
+    }
+
+    /// Set the context menu of a widget, it replaces the default context menu of the widget.
+    pub fn set<T: Widget>(&self, widget: &Arc<T>, menu: &Arc<Menu>) -> &Self {
+        let widget: Arc<dyn Widget> = widget.clone();
+        let mut menus = self.menus.borrow_mut();
+        menus.retain(|context_menu| !Arc::ptr_eq(&context_menu.widget, &widget));
+        menus.push(ContextMenu {
+            widget,
+            menu: menu.clone(),
+        });
+        self
+    }
+
+    /// Remove the context menu of a widget, it shows its default context menu again.
+    pub fn remove<T: Widget>(&self, widget: &Arc<T>) {
+        let widget: Arc<dyn Widget> = widget.clone();
+        self.menus.borrow_mut().retain(|context_menu| !Arc::ptr_eq(&context_menu.widget, &widget));
+    }
+
+    fn menu(&self, widget: &Arc<dyn Widget>) -> Option<Arc<Menu>> {
+        let menus = self.menus.borrow();
+        match menus.iter().find(|context_menu| Arc::ptr_eq(&context_menu.widget, widget)) {
+            Some(context_menu) => Some(context_menu.menu.clone()),
+            None => widget.context_menu(),
+        }
+    }
+
+    /// Find the context menu of the innermost widget at the given point.
+    fn find(&self, widgets: &[Arc<dyn Widget>], point: Point) -> Option<Arc<Menu>> {
+        for widget in widgets.iter().rev() {
+            if widget.rect().get().contains(point) {
+                if let Some(menu) = self.find(&widget.children().borrow(), point) {
+                    return Some(menu);
+                }
+
+                if let Some(menu) = self.menu(widget) {
+                    return Some(menu);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Open a menu at a point, it is moved to stay inside of the bounds.
+    pub fn open(&self, menu: &Arc<Menu>, point: Point, bounds: Rect) {
+        if let Some(open) = self.open_menu() {
+            open.close();
+        }
+
+        menu.open_at(point);
+        if let Some(flyout) = menu.popup() {
+            let rect = flyout.rect().get();
+
+            // open above the point if there is no space below
+            let mut y = point.y;
+            if y + rect.height as i32 > bounds.y + bounds.height as i32 {
+                y -= rect.height as i32;
+            }
+            let x = min(point.x, bounds.x + bounds.width as i32 - rect.width as i32);
+            let moved = Point::new(max(bounds.x, x), max(bounds.y, y));
+
+            if moved.x != point.x || moved.y != point.y {
+                menu.open_at(moved);
+            }
+        }
+
+        *self.open.borrow_mut() = Some(menu.clone());
+    }
+
+    /// Get the open context menu.
+    pub fn open_menu(&self) -> Option<Arc<Menu>> {
+        self.open.borrow().as_ref().filter(|menu| menu.is_open()).cloned()
+    }
+
+    /// Track the right mouse button. Returns true if it was released.
+    pub fn right_button(&self, pressed: bool) -> bool {
+        let released = self.right_pressed.get() && !pressed;
+        self.right_pressed.set(pressed);
+        released
+    }
+
+    /// Open the context menu of the widget at the given point. Returns true if a menu was opened.
+    pub fn open_at_point(&self, point: Point, widgets: &[Arc<dyn Widget>], bounds: Rect) -> bool {
+        match self.find(widgets, point) {
+            Some(menu) => {
+                self.open(&menu, point, bounds);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Open the context menu of a widget below it with its first entry highlighted, used
+    /// for the focused widget. Returns true if a menu was opened.
+    pub fn open_for(&self, widget: &Arc<dyn Widget>, bounds: Rect) -> bool {
+        match self.menu(widget) {
+            Some(menu) => {
+                let rect = widget.rect().get();
+                self.open(&menu, Point::new(rect.x, rect.y + rect.height as i32), bounds);
+                menu.highlight_first();
+                true
+            }
+            None => false,
+        }
+    }
+}
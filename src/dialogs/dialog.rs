@@ -0,0 +1,305 @@
+use orbclient::Renderer;
+use orbclient;
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+
+use cell::CloneCell;
+use draw::draw_box;
+use event::Event;
+use point::Point;
+use primitives::Rectangle;
+use rect::Rect;
+use thickness::Thickness;
+use theme::{Selector, Theme};
+use traits::{Click, Place, Style};
+use widgets::{Button, HorizontalPlacement, VerticalPlacement, Widget};
+
+const TITLE_HEIGHT: u32 = 28;
+
+/// A modal dialog that is shown inside of a window by `Window::show_dialog`. While it
+/// is open, the widgets beneath it are dimmed and get no input. Enter clicks the default
+/// button, Escape clicks the cancel button or closes the dialog if there is none.
+pub struct Dialog {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub selector: CloneCell<Selector>,
+    pub title: CloneCell<String>,
+    /// The area below the title bar that contains the widgets of the dialog
+    content: Arc<Rectangle>,
+    focused: RefCell<Option<Arc<dyn Widget>>>,
+    default_button: RefCell<Option<Arc<Button>>>,
+    cancel_button: RefCell<Option<Arc<Button>>>,
+    pub open: Cell<bool>,
+    close_callback: RefCell<Option<Arc<dyn Fn(&Dialog)>>>,
+}
+
+impl Dialog {
+    pub fn new() -> Arc<Self> {
+        let content = Rectangle::new();
+        content.selector().set(Selector::new(Some("dialog-content")));
+
+        Arc::new(Dialog {
+            rect: Cell::new(Rect::new(0, 0, 320, 160)),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector: CloneCell::new(Selector::new(Some("dialog"))),
+            title: CloneCell::new(String::new()),
+            content,
+            focused: RefCell::new(None),
+            default_button: RefCell::new(None),
+            cancel_button: RefCell::new(None),
+            open: Cell::new(false),
+            close_callback: RefCell::new(None),
+        })
+    }
+
+    pub fn title<S: Into<String>>(&self, title: S) -> &Self {
+        self.title.set(title.into());
+        self
+    }
+
+    /// Adds a widget to the dialog, its position is relative to the area below the title bar.
+    pub fn add<T: Widget>(&self, widget: &Arc<T>) -> &Self {
+        self.content.add(widget.clone());
+        self
+    }
+
+    /// Set the button that is clicked by Enter.
+    pub fn default_button(&self, button: &Arc<Button>) -> &Self {
+        *self.default_button.borrow_mut() = Some(button.clone());
+        self
+    }
+
+    /// Set the button that is clicked by Escape.
+    pub fn cancel_button(&self, button: &Arc<Button>) -> &Self {
+        *self.cancel_button.borrow_mut() = Some(button.clone());
+        self
+    }
+
+    /// Give the keyboard focus to a widget of the dialog.
+    pub fn focus<T: Widget>(&self, widget: &Arc<T>) -> &Self {
+        *self.focused.borrow_mut() = Some(widget.clone());
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open.get()
+    }
+
+    /// Close the dialog, the window removes it before the next event.
+    pub fn close(&self) {
+        if self.open.get() {
+            self.open.set(false);
+            if let Some(ref close_callback) = *self.close_callback.borrow() {
+                close_callback(self);
+            }
+        }
+    }
+
+    /// Called when the dialog gets closed.
+    pub fn on_close<F: Fn(&Dialog) + 'static>(&self, func: F) -> &Self {
+        *self.close_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+
+    fn is_focused(&self, widget: &Arc<dyn Widget>) -> bool {
+        match *self.focused.borrow() {
+            Some(ref focused) => Arc::ptr_eq(focused, widget),
+            None => false,
+        }
+    }
+}
+
+impl Place for Dialog {}
+
+impl Style for Dialog {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.selector
+    }
+}
+
+impl Widget for Dialog {
+    fn name(&self) -> &str {
+        "Dialog"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn arrange(&self) {
+        let rect = self.rect.get();
+        self.content.rect().set(Rect::new(
+            rect.x,
+            rect.y + TITLE_HEIGHT as i32,
+            rect.width,
+            rect.height.saturating_sub(TITLE_HEIGHT),
+        ));
+        self.content.arrange();
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, _focused: bool, theme: &Theme) {
+        fn draw_widget(widget: &Arc<dyn Widget>, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+            widget.update();
+            widget.draw(renderer, focused, theme);
+
+            for child in widget.children().borrow().iter() {
+                draw_widget(child, renderer, focused, theme);
+            }
+        }
+
+        let rect = self.rect.get();
+        draw_box(renderer, rect, theme, &self.selector.get());
+
+        //TODO: set this selector as the child of self.selector
+        let title_selector = Selector::new(Some("dialog-title"));
+        let title_rect = Rect::new(rect.x, rect.y, rect.width, TITLE_HEIGHT);
+        draw_box(renderer, title_rect, theme, &title_selector);
+
+        let color = theme.color("color", &title_selector);
+        let mut x = title_rect.x + 8;
+        for c in self.title.get().chars() {
+            if x + 8 > title_rect.x + title_rect.width as i32 - 8 {
+                break;
+            }
+            renderer.char(x, title_rect.y + (TITLE_HEIGHT as i32 - 16) / 2, c, color);
+            x += 8;
+        }
+
+        let content: Arc<dyn Widget> = self.content.clone();
+        content.update();
+        content.draw(renderer, false, theme);
+        for child in content.children().borrow().iter() {
+            draw_widget(child, renderer, self.is_focused(child), theme);
+        }
+    }
+
+    fn event(&self, event: Event, focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        if let Event::KeyPressed(key_event) = event {
+            let button = match key_event.scancode {
+                orbclient::K_ENTER => self.default_button.borrow().clone(),
+                orbclient::K_ESC => match *self.cancel_button.borrow() {
+                    Some(ref button) => Some(button.clone()),
+                    None => {
+                        self.close();
+                        *redraw = true;
+                        return focused;
+                    }
+                },
+                _ => None,
+            };
+
+            if let Some(button) = button {
+                button.emit_click(Point::new(0, 0));
+                *redraw = true;
+                return focused;
+            }
+        }
+
+        let children = self.content.children().borrow().clone();
+        let mut child_caught = false;
+        for child in children.iter().rev() {
+            let child_focused = self.is_focused(child);
+            if child.event(event, child_focused, redraw, &mut child_caught) && !child_focused {
+                *self.focused.borrow_mut() = Some(child.clone());
+                *redraw = true;
+            }
+
+            if child_caught {
+                break;
+            }
+        }
+
+        // the dialog is modal, the widgets beneath it never see an event
+        *caught = true;
+        focused
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+    fn visible_children(&self) -> Vec<Arc<dyn Widget>> {
+        let mut children = self.children.borrow().clone();
+        children.push(self.content.clone());
+        children
+    }
+}
+
+/// Shows modal dialogs in a window, see `Window::dialogs`. It can be cloned into
+/// callbacks, all clones show their dialogs in the same window.
+#[derive(Clone)]
+pub struct DialogHost {
+    dialogs: Arc<RefCell<Vec<Arc<Dialog>>>>,
+    /// The size of the window the dialogs are centered in
+    size: Arc<Cell<(u32, u32)>>,
+}
+
+impl DialogHost {
+    pub fn new() -> Self {
+        DialogHost {
+            dialogs: Arc::new(RefCell::new(vec![])),
+            size: Arc::new(Cell::new((0, 0))),
+        }
+    }
+
+    /// Show a dialog centered in the window. It gets all input until it is closed,
+    /// a dialog that is shown while another one is open is stacked on top of it.
+    pub fn show(&self, dialog: &Arc<Dialog>) {
+        self.center(dialog);
+        dialog.open.set(true);
+        self.dialogs.borrow_mut().push(dialog.clone());
+    }
+
+    /// Set the size of the window and center the open dialogs in it.
+    pub fn resize(&self, width: u32, height: u32) {
+        self.size.set((width, height));
+        for dialog in self.open_dialogs() {
+            self.center(&dialog);
+        }
+    }
+
+    fn center(&self, dialog: &Arc<Dialog>) {
+        let (width, height) = self.size.get();
+        let rect = dialog.rect.get();
+        dialog.position(
+            (width as i32 - rect.width as i32) / 2,
+            (height as i32 - rect.height as i32) / 2,
+        );
+    }
+
+    /// Get the open dialogs, the topmost dialog is the last one.
+    pub fn open_dialogs(&self) -> Vec<Arc<Dialog>> {
+        self.dialogs.borrow().iter().filter(|dialog| dialog.is_open()).cloned().collect()
+    }
+
+    /// Remove the closed dialogs. Returns true if a dialog was removed.
+    pub fn remove_closed(&self) -> bool {
+        let mut dialogs = self.dialogs.borrow_mut();
+        let count = dialogs.len();
+        dialogs.retain(|dialog| dialog.is_open());
+        dialogs.len() != count
+    }
+}
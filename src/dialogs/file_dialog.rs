@@ -1,3 +1,4 @@
+use orbclient::WindowFlag;
 use { InnerWindow, Window, List, Entry, Label };
 use traits::{ Place, Text, Click };
 
@@ -95,7 +96,8 @@ impl FileDialog {
         let w = 644;
         let h = 484;
 
-        let mut orb_window = Some(InnerWindow::new(-1, -1, w, h, &self.title).unwrap());
+        // the window polls its events to run its timers, see `Window::from_inner`
+        let mut orb_window = Some(InnerWindow::new_flags(-1, -1, w, h, &self.title, &[WindowFlag::Async]).unwrap());
 
         loop {
             let path = match path_opt.borrow_mut().take() {
@@ -0,0 +1,138 @@
+use std::cell::Cell;
+use std::cmp::{max, min};
+use std::sync::Arc;
+
+use dialogs::Dialog;
+use primitives::{TextAlignment, TextWrap, VerticalTextAlignment};
+use theme::Selector;
+use traits::{Click, Place, Style, Text};
+use widgets::{Button, Label};
+
+const PADDING: i32 = 16;
+const ICON_SIZE: u32 = 24;
+const BUTTON_WIDTH: u32 = 80;
+const BUTTON_HEIGHT: u32 = 28;
+/// The widest a message box gets, longer lines of the message are wrapped.
+const MAX_WIDTH: u32 = 480;
+
+/// The kind of a message box, it decides the icon and the buttons.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MessageBoxKind {
+    Info,
+    Warning,
+    Error,
+    /// Asks a question with the buttons Yes, No and Cancel
+    Question,
+}
+
+/// The button a message box was closed with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MessageBoxResult {
+    Ok,
+    Yes,
+    No,
+    /// The question was cancelled with the Cancel button or Escape
+    Cancel,
+}
+
+/// Creates dialogs that show a message. The result is passed to a callback when the
+/// dialog gets closed, show the dialog with `Window::show_dialog`.
+pub struct MessageBox;
+
+impl MessageBox {
+    pub fn info<F: Fn(MessageBoxResult) + 'static>(title: &str, message: &str, func: F) -> Arc<Dialog> {
+        MessageBox::new(MessageBoxKind::Info, title, message, func)
+    }
+
+    pub fn warning<F: Fn(MessageBoxResult) + 'static>(title: &str, message: &str, func: F) -> Arc<Dialog> {
+        MessageBox::new(MessageBoxKind::Warning, title, message, func)
+    }
+
+    pub fn error<F: Fn(MessageBoxResult) + 'static>(title: &str, message: &str, func: F) -> Arc<Dialog> {
+        MessageBox::new(MessageBoxKind::Error, title, message, func)
+    }
+
+    pub fn question<F: Fn(MessageBoxResult) + 'static>(title: &str, message: &str, func: F) -> Arc<Dialog> {
+        MessageBox::new(MessageBoxKind::Question, title, message, func)
+    }
+
+    pub fn new<F: Fn(MessageBoxResult) + 'static>(kind: MessageBoxKind, title: &str, message: &str, func: F) -> Arc<Dialog> {
+        let (pseudo_class, symbol, buttons) = match kind {
+            MessageBoxKind::Info => ("info", "i", vec![("OK", MessageBoxResult::Ok)]),
+            MessageBoxKind::Warning => ("warning", "!", vec![("OK", MessageBoxResult::Ok)]),
+            MessageBoxKind::Error => ("error", "x", vec![("OK", MessageBoxResult::Ok)]),
+            MessageBoxKind::Question => ("question", "?", vec![
+                ("Yes", MessageBoxResult::Yes),
+                ("No", MessageBoxResult::No),
+                ("Cancel", MessageBoxResult::Cancel),
+            ]),
+        };
+
+        let columns = message.lines().map(|line| line.chars().count()).max().unwrap_or(0) as u32;
+        let buttons_width = buttons.len() as u32 * (BUTTON_WIDTH + 8) - 8;
+        let width = max(
+            max(280, buttons_width + 2 * PADDING as u32),
+            min(MAX_WIDTH, ICON_SIZE + columns * 8 + 3 * PADDING as u32),
+        );
+        let text_width = width - ICON_SIZE - 3 * PADDING as u32;
+        let rows = max(1, TextWrap::Wrap.line_count(message, text_width)) as u32;
+        let message_height = max(ICON_SIZE, rows * 16);
+        let height = 28 + message_height + BUTTON_HEIGHT + 3 * PADDING as u32;
+
+        let dialog = Dialog::new();
+        dialog.title(title).size(width, height);
+
+        let icon = Label::new();
+        icon.selector().set(Selector::new(Some("message-box-icon")).with_pseudo_class(pseudo_class));
+        icon.text(symbol).position(PADDING, PADDING).size(ICON_SIZE, ICON_SIZE);
+        dialog.add(&icon);
+
+        let label = Label::new();
+        label.text_wrap(TextWrap::Wrap)
+            .text_alignment(TextAlignment::Left, VerticalTextAlignment::Center);
+        label.text(message)
+            .position(2 * PADDING + ICON_SIZE as i32, PADDING)
+            .size(text_width, message_height);
+        dialog.add(&label);
+
+        let func = Arc::new(func);
+        // the result of the clicked button, closing without a button counts as its escape result
+        let result = Arc::new(Cell::new(None));
+        let escape_result = match kind {
+            MessageBoxKind::Question => MessageBoxResult::Cancel,
+            _ => MessageBoxResult::Ok,
+        };
+
+        let mut x = width as i32 - PADDING - buttons_width as i32;
+        let y = height as i32 - 28 - PADDING - BUTTON_HEIGHT as i32;
+        for (i, &(text, button_result)) in buttons.iter().enumerate() {
+            let button = Button::new();
+            button.text(text).position(x, y).size(BUTTON_WIDTH, BUTTON_HEIGHT);
+
+            let weak_dialog = Arc::downgrade(&dialog);
+            let result = result.clone();
+            button.on_click(move |_, _| {
+                result.set(Some(button_result));
+                if let Some(dialog) = weak_dialog.upgrade() {
+                    dialog.close();
+                }
+            });
+
+            dialog.add(&button);
+            if i == 0 {
+                dialog.default_button(&button);
+            }
+            if button_result == escape_result {
+                dialog.cancel_button(&button);
+            }
+
+            x += (BUTTON_WIDTH + 8) as i32;
+        }
+
+        dialog.on_close(move |_| {
+            func(result.get().unwrap_or(escape_result));
+        });
+
+        dialog
+    }
+}
@@ -1,3 +1,7 @@
+pub use self::dialog::{Dialog, DialogHost};
 pub use self::file_dialog::FileDialog;
+pub use self::message_box::{MessageBox, MessageBoxKind, MessageBoxResult};
 
+mod dialog;
 mod file_dialog;
+mod message_box;
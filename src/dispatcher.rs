@@ -0,0 +1,121 @@
+//! Run code on the thread of a window from other threads.
+//!
+//! Widgets are not `Send`, so a background thread could not change them directly. It gets
+//! a `Dispatcher` from the window instead and queues closures or messages with it. The
+//! window runs them on its thread after it handled its events, it wakes up as soon as
+//! something is queued.
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::Duration;
+
+use window::Window;
+
+enum Message {
+    Invoke(Box<dyn FnOnce(&Window) + Send>),
+    Custom(TypeId, Box<dyn Any + Send>),
+}
+
+/// Queues closures and messages for a window from any thread. It could be cloned and sent
+/// to other threads.
+#[derive(Clone)]
+pub struct Dispatcher {
+    sender: Sender<Message>,
+}
+
+impl Dispatcher {
+    /// Queue a closure that is called with the window on its thread.
+    /// Returns false if the window is gone.
+    pub fn invoke<F: FnOnce(&Window) + Send + 'static>(&self, func: F) -> bool {
+        self.sender.send(Message::Invoke(Box::new(func))).is_ok()
+    }
+
+    /// Queue a message for the handler of its type, see `Window::on_message`. A message
+    /// without a handler is dropped. Returns false if the window is gone.
+    pub fn send<T: Any + Send>(&self, message: T) -> bool {
+        self.sender.send(Message::Custom(TypeId::of::<T>(), Box::new(message))).is_ok()
+    }
+}
+
+/// The queue of a window that receives the closures and messages of its dispatchers.
+pub struct DispatchQueue {
+    sender: Sender<Message>,
+    receiver: Receiver<Message>,
+    /// A message that was received while waiting and not yet run.
+    pending: RefCell<VecDeque<Message>>,
+    handlers: RefCell<HashMap<TypeId, Rc<dyn Fn(&Window, Box<dyn Any + Send>)>>>,
+}
+
+impl DispatchQueue {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        DispatchQueue {
+            sender,
+            receiver,
+            pending: RefCell::new(VecDeque::new()),
+            handlers: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Get a new dispatcher that queues closures and messages for this queue.
+    pub fn dispatcher(&self) -> Dispatcher {
+        Dispatcher {
+            sender: self.sender.clone(),
+        }
+    }
+
+    /// Set the handler of the messages of a type, it replaces the handler of that type.
+    pub fn on_message<T: Any + Send, F: Fn(&Window, T) + 'static>(&self, func: F) {
+        self.handlers.borrow_mut().insert(
+            TypeId::of::<T>(),
+            Rc::new(move |window: &Window, message: Box<dyn Any + Send>| {
+                if let Ok(message) = message.downcast::<T>() {
+                    func(window, *message);
+                }
+            }),
+        );
+    }
+
+    /// Wait until a closure or message is queued or the timeout elapsed.
+    /// Returns true if something was queued.
+    pub fn wait(&self, timeout: Duration) -> bool {
+        if !self.pending.borrow().is_empty() {
+            return true;
+        }
+
+        match self.receiver.recv_timeout(timeout) {
+            Ok(message) => {
+                self.pending.borrow_mut().push_back(message);
+                true
+            }
+            // the queue holds a sender itself, so it is never disconnected
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => false,
+        }
+    }
+
+    /// Run the queued closures and the handlers of the queued messages.
+    /// Returns true if something was run.
+    pub fn run(&self, window: &Window) -> bool {
+        let mut messages: Vec<Message> = self.pending.borrow_mut().drain(..).collect();
+        messages.extend(self.receiver.try_iter());
+
+        let ran = !messages.is_empty();
+        for message in messages {
+            match message {
+                Message::Invoke(func) => func(window),
+                Message::Custom(type_id, message) => {
+                    // the handler could set other handlers
+                    let handler = self.handlers.borrow().get(&type_id).cloned();
+                    if let Some(handler) = handler {
+                        handler(window, message);
+                    }
+                }
+            }
+        }
+
+        ran
+    }
+}
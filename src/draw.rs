@@ -1,9 +1,50 @@
-use orbclient::Renderer;
+use orbclient::{Color, Mode, Renderer};
+use orbimage;
+use std::cell::{Cell, RefCell};
+use std::cmp::min;
+use std::collections::HashMap;
+use std::rc::Rc;
 
+use point::Point;
 use theme::{Theme, Selector};
 use rect::Rect;
+use thickness::Thickness;
 
+thread_local! {
+    static THEME_IMAGES: RefCell<HashMap<String, Option<Rc<orbimage::Image>>>> = RefCell::new(HashMap::new());
+    static THEME_BOXES: ScaleCache = ScaleCache::new(64);
+}
+
+/// Load an image of a theme, every path is only loaded once.
+fn theme_image(path: &str) -> Option<Rc<orbimage::Image>> {
+    THEME_IMAGES.with(|images| {
+        images.borrow_mut().entry(path.to_string()).or_insert_with(|| {
+            match orbimage::Image::from_path(path) {
+                Ok(image) => Some(Rc::new(image)),
+                Err(err) => {
+                    eprintln!("WARNING: failed to load theme image {}: {}", path, err);
+                    None
+                }
+            }
+        }).clone()
+    })
+}
+
+/// Draws the background of a widget. If the theme sets a `background-image`, the image is
+/// nine-slice scaled to the rect, `background-slice` is the size of its unscaled borders.
+/// Otherwise a rounded rect with the `background` and `border-color` is drawn.
 pub fn draw_box(renderer: &mut dyn Renderer, rect: Rect, theme: &Theme, selector: &Selector) {
+    let path = theme.string("background-image", selector);
+    if !path.is_empty() {
+        if let Some(image) = theme_image(&path) {
+            let slice = theme.uint("background-slice", selector) as i32;
+            THEME_BOXES.with(|cache| {
+                draw_nine_slice(renderer, rect, &image, Thickness::new(slice, slice, slice, slice), Resampling::Bilinear, rect, cache);
+            });
+            return;
+        }
+    }
+
     let b_r = theme.uint("border-radius", selector);
 
     let fill = theme.color("background", selector);
@@ -16,3 +57,321 @@ pub fn draw_box(renderer: &mut dyn Renderer, rect: Rect, theme: &Theme, selector
         renderer.rounded_rect(rect.x, rect.y, rect.width, rect.height, b_r, false, border_color);
     }
 }
+
+/// The filter that is used to scale images.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Resampling {
+    /// Use the nearest pixel, keeps hard edges.
+    Nearest,
+    /// Interpolate between the four nearest pixels.
+    Bilinear,
+}
+
+/// Identifies the pixels that were scaled from an image.
+#[derive(Clone, Copy, PartialEq)]
+struct ScaleKey {
+    image: usize,
+    image_size: (u32, u32),
+    size: (u32, u32),
+    part: (i32, i32, u32, u32),
+    slice: Option<(i32, i32, i32, i32)>,
+    resampling: Resampling,
+}
+
+impl ScaleKey {
+    fn new(image: &orbimage::Image, width: u32, height: u32, part: Rect, slice: Option<Thickness>, resampling: Resampling) -> Self {
+        ScaleKey {
+            image: image.data().as_ptr() as usize,
+            image_size: (image.width(), image.height()),
+            size: (width, height),
+            part: (part.x, part.y, part.width, part.height),
+            slice: slice.map(|slice| (slice.left, slice.top, slice.right, slice.bottom)),
+            resampling: resampling,
+        }
+    }
+}
+
+/// Keeps the most recently scaled images, so that an image that is drawn in the same size
+/// again is not scaled again. Images are recognized by their pixel buffer, so the cache has
+/// to be cleared if the pixels of an image are changed in place.
+pub struct ScaleCache {
+    capacity: usize,
+    scaled: RefCell<Vec<(ScaleKey, Rc<Vec<Color>>)>>,
+}
+
+impl ScaleCache {
+    pub fn new(capacity: usize) -> Self {
+        ScaleCache {
+            capacity: capacity,
+            scaled: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Forget all scaled images.
+    pub fn clear(&self) {
+        self.scaled.borrow_mut().clear();
+    }
+
+    /// Get the scaled pixels for the key, they are only computed if they are not cached.
+    fn get<F: FnOnce() -> Vec<Color>>(&self, key: ScaleKey, scale: F) -> Rc<Vec<Color>> {
+        let mut scaled = self.scaled.borrow_mut();
+        if let Some(i) = scaled.iter().position(|entry| entry.0 == key) {
+            let entry = scaled.remove(i);
+            let pixels = entry.1.clone();
+            scaled.insert(0, entry);
+            return pixels;
+        }
+
+        let pixels = Rc::new(scale());
+        scaled.insert(0, (key, pixels.clone()));
+        scaled.truncate(self.capacity);
+        pixels
+    }
+}
+
+/// Scale the pixels of an image to a new size. Only the part of the scaled image that is
+/// inside of `part` is computed, it is given in the coordinates of the scaled image.
+pub fn scale(data: &[Color], width: u32, height: u32, new_width: u32, new_height: u32, part: Rect, resampling: Resampling) -> Vec<Color> {
+    let part = part.intersection(&Rect::new(0, 0, new_width, new_height));
+    let len = part.width as usize * part.height as usize;
+    if width == new_width && height == new_height {
+        return crop(data, width, part);
+    }
+
+    let mut scaled = Vec::with_capacity(len);
+    if width == 0 || height == 0 {
+        scaled.resize(len, Color::rgba(0, 0, 0, 0));
+        return scaled;
+    }
+
+    let x_ratio = width as f32 / new_width as f32;
+    let y_ratio = height as f32 / new_height as f32;
+    let pixel = |x: u32, y: u32| data[y as usize * width as usize + x as usize];
+
+    for y in part.y as u32..part.y as u32 + part.height {
+        for x in part.x as u32..part.x as u32 + part.width {
+            match resampling {
+                Resampling::Nearest => {
+                    let src_x = min((x as f32 * x_ratio) as u32, width - 1);
+                    let src_y = min((y as f32 * y_ratio) as u32, height - 1);
+                    scaled.push(pixel(src_x, src_y));
+                }
+                Resampling::Bilinear => {
+                    // sample at the pixel centers
+                    let src_x = ((x as f32 + 0.5) * x_ratio - 0.5).max(0.0).min((width - 1) as f32);
+                    let src_y = ((y as f32 + 0.5) * y_ratio - 0.5).max(0.0).min((height - 1) as f32);
+                    let x0 = src_x as u32;
+                    let y0 = src_y as u32;
+                    let x1 = min(x0 + 1, width - 1);
+                    let y1 = min(y0 + 1, height - 1);
+                    let fx = src_x - x0 as f32;
+                    let fy = src_y - y0 as f32;
+
+                    let (c00, c10, c01, c11) = (pixel(x0, y0), pixel(x1, y0), pixel(x0, y1), pixel(x1, y1));
+                    let channel = |f: fn(&Color) -> u8| {
+                        let top = f(&c00) as f32 * (1.0 - fx) + f(&c10) as f32 * fx;
+                        let bottom = f(&c01) as f32 * (1.0 - fx) + f(&c11) as f32 * fx;
+                        (top * (1.0 - fy) + bottom * fy + 0.5) as u8
+                    };
+
+                    scaled.push(Color::rgba(channel(Color::r), channel(Color::g), channel(Color::b), channel(Color::a)));
+                }
+            }
+        }
+    }
+
+    scaled
+}
+
+/// Copy a part of an image.
+fn crop(data: &[Color], width: u32, rect: Rect) -> Vec<Color> {
+    let mut cropped = Vec::with_capacity(rect.width as usize * rect.height as usize);
+    for y in rect.y..rect.y + rect.height as i32 {
+        let start = y as usize * width as usize + rect.x as usize;
+        cropped.extend_from_slice(&data[start..start + rect.width as usize]);
+    }
+    cropped
+}
+
+/// Draw an image, only the part of it that is inside of the clip rect is drawn.
+pub fn draw_image(renderer: &mut dyn Renderer, x: i32, y: i32, width: u32, height: u32, data: &[Color], clip: Rect) {
+    let rect = Rect::new(x, y, width, height);
+    let visible = clip.intersection(&rect);
+    if visible.width == 0 || visible.height == 0 {
+        return;
+    }
+
+    if visible.width == width && visible.height == height {
+        renderer.image(x, y, width, height, data);
+    } else {
+        let part = Rect::new(visible.x - x, visible.y - y, visible.width, visible.height);
+        renderer.image(visible.x, visible.y, visible.width, visible.height, &crop(data, width, part));
+    }
+}
+
+/// Draw an image scaled to the rect, only the part of it that is inside of the clip rect is
+/// scaled and drawn. The scaled pixels are kept in the cache.
+pub fn draw_image_scaled(renderer: &mut dyn Renderer, rect: Rect, image: &orbimage::Image, resampling: Resampling, clip: Rect, cache: &ScaleCache) {
+    let visible = clip.intersection(&rect);
+    if visible.width == 0 || visible.height == 0 {
+        return;
+    }
+
+    let part = Rect::new(visible.x - rect.x, visible.y - rect.y, visible.width, visible.height);
+    let key = ScaleKey::new(image, rect.width, rect.height, part, None, resampling);
+    let scaled = cache.get(key, || scale(image.data(), image.width(), image.height(), rect.width, rect.height, part, resampling));
+    renderer.image(visible.x, visible.y, visible.width, visible.height, &scaled);
+}
+
+/// Draw an image with nine-slice scaling. The corners given by the slice keep their size,
+/// the edges are only stretched along them and the center is stretched to fill the rest.
+/// If the rect is smaller than the corners, they are shrunk. Only the part of it that is
+/// inside of the clip rect is scaled and drawn, the scaled pixels are kept in the cache.
+pub fn draw_nine_slice(renderer: &mut dyn Renderer, rect: Rect, image: &orbimage::Image, slice: Thickness, resampling: Resampling, clip: Rect, cache: &ScaleCache) {
+    let visible = clip.intersection(&rect);
+    if visible.width == 0 || visible.height == 0 {
+        return;
+    }
+
+    let part = Rect::new(visible.x - rect.x, visible.y - rect.y, visible.width, visible.height);
+    let key = ScaleKey::new(image, rect.width, rect.height, part, Some(slice), resampling);
+    let scaled = cache.get(key, || nine_slice(image, rect.width, rect.height, slice, resampling, part));
+    renderer.image(visible.x, visible.y, visible.width, visible.height, &scaled);
+}
+
+/// Scale an image with nine-slice scaling to a new size, see `draw_nine_slice`. Only the
+/// part of the scaled image that is inside of `part` is computed.
+fn nine_slice(image: &orbimage::Image, new_width: u32, new_height: u32, slice: Thickness, resampling: Resampling, part: Rect) -> Vec<Color> {
+    let width = image.width() as i32;
+    let height = image.height() as i32;
+
+    // the slice in the image, clamped so that the corners don't overlap
+    let src_left = slice.left.max(0).min(width);
+    let src_right = slice.right.max(0).min(width - src_left);
+    let src_top = slice.top.max(0).min(height);
+    let src_bottom = slice.bottom.max(0).min(height - src_top);
+
+    // the slice in the scaled image, shrunk if it is smaller than the corners
+    fn shrink(first: i32, second: i32, size: i32) -> (i32, i32) {
+        if first + second > size && first + second > 0 {
+            let first = first * size / (first + second);
+            (first, size - first)
+        } else {
+            (first, second)
+        }
+    }
+    let (dst_left, dst_right) = shrink(src_left, src_right, new_width as i32);
+    let (dst_top, dst_bottom) = shrink(src_top, src_bottom, new_height as i32);
+
+    let src_columns = [(0, src_left), (src_left, width - src_left - src_right), (width - src_right, src_right)];
+    let src_rows = [(0, src_top), (src_top, height - src_top - src_bottom), (height - src_bottom, src_bottom)];
+    let dst_columns = [
+        (0, dst_left),
+        (dst_left, new_width as i32 - dst_left - dst_right),
+        (new_width as i32 - dst_right, dst_right),
+    ];
+    let dst_rows = [
+        (0, dst_top),
+        (dst_top, new_height as i32 - dst_top - dst_bottom),
+        (new_height as i32 - dst_bottom, dst_bottom),
+    ];
+
+    let mut pixels = vec![Color::rgba(0, 0, 0, 0); part.width as usize * part.height as usize];
+    for row in 0..3 {
+        for column in 0..3 {
+            let (src_x, src_width) = src_columns[column];
+            let (src_y, src_height) = src_rows[row];
+            let (dst_x, dst_width) = dst_columns[column];
+            let (dst_y, dst_height) = dst_rows[row];
+            if src_width <= 0 || src_height <= 0 || dst_width <= 0 || dst_height <= 0 {
+                continue;
+            }
+
+            let visible = part.intersection(&Rect::new(dst_x, dst_y, dst_width as u32, dst_height as u32));
+            if visible.width == 0 || visible.height == 0 {
+                continue;
+            }
+
+            let src = crop(image.data(), image.width(), Rect::new(src_x, src_y, src_width as u32, src_height as u32));
+            let scaled = scale(&src, src_width as u32, src_height as u32, dst_width as u32, dst_height as u32,
+                               Rect::new(visible.x - dst_x, visible.y - dst_y, visible.width, visible.height), resampling);
+            for (y, line) in scaled.chunks(visible.width as usize).enumerate() {
+                let start = (visible.y - part.y) as usize * part.width as usize + y * part.width as usize + (visible.x - part.x) as usize;
+                pixels[start..start + line.len()].copy_from_slice(line);
+            }
+        }
+    }
+    pixels
+}
+
+/// Wraps a renderer and discards everything that is drawn outside of the clip rect.
+/// Characters are only drawn if they are completely inside of it, images are cropped.
+pub struct ClipRenderer<'a> {
+    inner: &'a mut dyn Renderer,
+    clip: Rect,
+}
+
+impl<'a> ClipRenderer<'a> {
+    pub fn new(inner: &'a mut dyn Renderer, clip: Rect) -> ClipRenderer<'a> {
+        ClipRenderer {
+            inner: inner,
+            clip: clip,
+        }
+    }
+
+    fn clips(&self, x: i32, y: i32, width: u32, height: u32) -> bool {
+        x < self.clip.x || y < self.clip.y
+            || x + width as i32 > self.clip.x + self.clip.width as i32
+            || y + height as i32 > self.clip.y + self.clip.height as i32
+    }
+}
+
+impl<'a> Renderer for ClipRenderer<'a> {
+    fn width(&self) -> u32 {
+        self.inner.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.inner.height()
+    }
+
+    fn data(&self) -> &[Color] {
+        self.inner.data()
+    }
+
+    fn data_mut(&mut self) -> &mut [Color] {
+        self.inner.data_mut()
+    }
+
+    fn sync(&mut self) -> bool {
+        self.inner.sync()
+    }
+
+    fn mode(&self) -> &Cell<Mode> {
+        self.inner.mode()
+    }
+
+    fn pixel(&mut self, x: i32, y: i32, color: Color) {
+        if self.clip.contains(Point::new(x, y)) {
+            self.inner.pixel(x, y, color);
+        }
+    }
+
+    fn rect(&mut self, x: i32, y: i32, w: u32, h: u32, color: Color) {
+        let rect = self.clip.intersection(&Rect::new(x, y, w, h));
+        if rect.width > 0 && rect.height > 0 {
+            self.inner.rect(rect.x, rect.y, rect.width, rect.height, color);
+        }
+    }
+
+    fn char(&mut self, x: i32, y: i32, c: char, color: Color) {
+        if !self.clips(x, y, 8, 16) {
+            self.inner.char(x, y, c, color);
+        }
+    }
+
+    /// Images are cropped to the clip rect.
+    fn image(&mut self, x: i32, y: i32, w: u32, h: u32, data: &[Color]) {
+        draw_image(self.inner, x, y, w, h, data, self.clip);
+    }
+}
@@ -2,6 +2,36 @@ use super::Point;
 
 use orbclient;
 
+use timer::TimerId;
+
+/// The modifier keys that were held down while a key event occured
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl Modifiers {
+    /// Check if none of the modifier keys is held down
+    pub fn is_empty(&self) -> bool {
+        !self.ctrl && !self.shift && !self.alt
+    }
+
+    /// Update the modifier state from a pressed or released orbital key event.
+    /// Returns true if the key event was a modifier key.
+    pub fn update(&mut self, key_event: &orbclient::KeyEvent) -> bool {
+        match key_event.scancode {
+            orbclient::K_CTRL => self.ctrl = key_event.pressed,
+            orbclient::K_LEFT_SHIFT | orbclient::K_RIGHT_SHIFT => self.shift = key_event.pressed,
+            orbclient::K_ALT => self.alt = key_event.pressed,
+            _ => return false,
+        }
+
+        true
+    }
+}
+
 /// A key event (such as a pressed key)
 #[derive(Copy, Clone, Debug)]
 pub struct KeyEvent {
@@ -9,6 +39,8 @@ pub struct KeyEvent {
     pub character: Option<char>,
     /// The scancode of the key
     pub scancode: u8,
+    /// The modifier keys held down
+    pub modifiers: Modifiers,
 }
 
 impl KeyEvent {
@@ -34,6 +66,7 @@ impl KeyEvent {
         KeyEvent {
             character,
             scancode,
+            modifiers: Modifiers::default(),
         }
     }
 }
@@ -47,6 +80,8 @@ pub enum Event {
         left_button: bool,
         middle_button: bool,
         right_button: bool,
+        /// The modifier keys held down
+        modifiers: Modifiers,
     },
 
     KeyPressed(KeyEvent),
@@ -63,5 +98,8 @@ pub enum Event {
         height: u32,
     },
 
+    /// A timer expired, it is sent to all widgets
+    Timer(TimerId),
+
     Unknown,
 }
@@ -0,0 +1,123 @@
+//! The icons that are shared by the widgets of a thread.
+//!
+//! Icons are looked up by name, either from an image that was registered with that name
+//! or from `<name>.png` in one of the icon directories. Themes use the names in the `icon`
+//! property, for example `button.open { icon: "document-open"; }`.
+
+use orbimage;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use primitives::Image;
+use rect::Rect;
+
+/// The space between an icon and the caption of a widget.
+pub const ICON_SPACING: u32 = 4;
+
+/// Where an icon is placed relative to the caption of a widget.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IconPlacement {
+    Left,
+    Right,
+    Top,
+    /// Only the icon is shown, the caption is hidden.
+    IconOnly,
+}
+
+impl IconPlacement {
+    /// Get the size that is needed to show an icon and a caption of the given sizes.
+    pub fn size(&self, icon: (u32, u32), caption: (u32, u32)) -> (u32, u32) {
+        match *self {
+            IconPlacement::Left | IconPlacement::Right => {
+                (icon.0 + ICON_SPACING + caption.0, icon.1.max(caption.1))
+            }
+            IconPlacement::Top => (icon.0.max(caption.0), icon.1 + ICON_SPACING + caption.1),
+            IconPlacement::IconOnly => icon,
+        }
+    }
+
+    /// Split a rect into the rect of an icon of the given size and the rect of the caption.
+    /// The caption rect is empty if only the icon is shown.
+    pub fn layout(&self, rect: Rect, icon_width: u32, icon_height: u32) -> (Rect, Rect) {
+        let center_x = rect.x + (rect.width as i32 - icon_width as i32) / 2;
+        let center_y = rect.y + (rect.height as i32 - icon_height as i32) / 2;
+        let used_width = icon_width + ICON_SPACING;
+        let used_height = icon_height + ICON_SPACING;
+
+        match *self {
+            IconPlacement::Left => (
+                Rect::new(rect.x, center_y, icon_width, icon_height),
+                Rect::new(rect.x + used_width as i32, rect.y, rect.width.saturating_sub(used_width), rect.height),
+            ),
+            IconPlacement::Right => (
+                Rect::new(rect.x + rect.width as i32 - icon_width as i32, center_y, icon_width, icon_height),
+                Rect::new(rect.x, rect.y, rect.width.saturating_sub(used_width), rect.height),
+            ),
+            IconPlacement::Top => (
+                Rect::new(center_x, rect.y, icon_width, icon_height),
+                Rect::new(rect.x, rect.y + used_height as i32, rect.width, rect.height.saturating_sub(used_height)),
+            ),
+            IconPlacement::IconOnly => (
+                Rect::new(center_x, center_y, icon_width, icon_height),
+                Rect::new(rect.x, rect.y, 0, 0),
+            ),
+        }
+    }
+}
+
+struct Icons {
+    dirs: Vec<PathBuf>,
+    /// The loaded icons, `None` if an icon was not found.
+    images: HashMap<String, Option<orbimage::Image>>,
+}
+
+thread_local! {
+    static ICONS: RefCell<Icons> = RefCell::new(Icons {
+        dirs: vec![],
+        images: HashMap::new(),
+    });
+}
+
+/// Add a directory that is searched for icons, the directories are searched in the order
+/// they were added.
+pub fn add_dir<P: Into<PathBuf>>(dir: P) {
+    ICONS.with(|icons| {
+        let mut icons = icons.borrow_mut();
+        icons.dirs.push(dir.into());
+        // icons that were not found could be in the new directory
+        icons.images.retain(|_, image| image.is_some());
+    });
+}
+
+/// Register an icon with a name, it replaces an icon with the same name.
+pub fn register<S: Into<String>>(name: S, image: orbimage::Image) {
+    ICONS.with(|icons| {
+        icons.borrow_mut().images.insert(name.into(), Some(image));
+    });
+}
+
+/// Get the image of an icon. An icon that is not registered is loaded from the icon
+/// directories once.
+pub fn image(name: &str) -> Option<orbimage::Image> {
+    ICONS.with(|icons| {
+        let mut icons = icons.borrow_mut();
+        if !icons.images.contains_key(name) {
+            let file_name = format!("{}.png", name);
+            let image = icons.dirs.iter()
+                .map(|dir| dir.join(&file_name))
+                .filter(|path| path.is_file())
+                .filter_map(|path| orbimage::Image::from_path(path).ok())
+                .next();
+            icons.images.insert(name.to_string(), image);
+        }
+
+        icons.images.get(name).and_then(|image| image.clone())
+    })
+}
+
+/// Get a new image widget that shows an icon.
+pub fn get(name: &str) -> Option<Arc<Image>> {
+    image(name).map(Image::from_image)
+}
@@ -1,5 +1,6 @@
 use orbclient::Renderer;
 use std::cell::{Cell, RefCell};
+use std::cmp::max;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
@@ -25,6 +26,8 @@ pub struct Grid {
     row_count: Cell<usize>,
     column_count: Cell<usize>,
     entries: RefCell<BTreeMap<(usize, usize), Arc<dyn Widget>>>,
+    /// The size of the cells with the spacing between them, computed by `arrange`
+    content_size: Cell<(u32, u32)>,
     focused: Cell<Option<(usize, usize)>>
 }
 
@@ -43,6 +46,7 @@ impl Grid {
             row_count: Cell::new(0),
             column_count: Cell::new(0),
             entries: RefCell::new(BTreeMap::new()),
+            content_size: Cell::new((0, 0)),
             focused: Cell::new(None),
         })
     }
@@ -123,6 +127,12 @@ impl Grid {
             y += row.height as i32 + space_y;
         }
 
+        // containers like a scroll viewer need the size of the content
+        self.content_size.set((
+            max(0, x - space_x - rect.x) as u32,
+            max(0, y - space_y - rect.y) as u32,
+        ));
+
         for (&(col, row), entry) in self.entries.borrow().iter() {
             let mut rect = entry.rect().get();
             rect.x = cols[col].x;
@@ -176,6 +186,14 @@ impl Widget for Grid {
         &self.margin
     }
 
+    fn arrange(&self) {
+        Grid::arrange(self, false);
+    }
+
+    fn content_size(&self) -> (u32, u32) {
+        self.content_size.get()
+    }
+
     fn draw(&self, renderer: &mut dyn Renderer, _focused: bool, theme: &Theme) {
         fn draw_widget(widget: &Arc<dyn Widget>, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
             widget.update();
@@ -214,4 +232,10 @@ impl Widget for Grid {
     fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
         &self.children
     }
+
+    fn visible_children(&self) -> Vec<Arc<dyn Widget>> {
+        let mut children = self.children.borrow().clone();
+        children.extend(self.entries.borrow().values().cloned());
+        children
+    }
 }
@@ -6,34 +6,55 @@ extern crate orbimage;
 extern crate cssparser;
 #[macro_use]
 extern crate lazy_static;
+extern crate unicode_segmentation;
 
 pub use orbclient::color::Color;
 pub use orbclient::renderer::Renderer;
 
+pub use animation::{Animation, AnimationId, Easing, Interpolate};
 pub use cell::CloneCell;
+pub use collection::{CollectionChange, ObservableCollection, SubscriptionId};
+pub use context_menu::ContextMenuManager;
 pub use dialogs::*;
+pub use dispatcher::Dispatcher;
+pub use draw::Resampling;
 pub use layouts::*;
 pub use primitives::*;
-pub use event::{Event, KeyEvent};
+pub use event::{Event, KeyEvent, Modifiers};
 pub use self::focus_manager::FocusManager;
+pub use icons::IconPlacement;
 pub use point::Point;
 pub use rect::Rect;
+pub use shortcut::{KeyChord, ShortcutRegistry};
 pub use traits::*;
 pub use thickness::Thickness;
+pub use timer::TimerId;
+pub use tooltip::TooltipManager;
+pub use validator::{CharClass, Validator};
 pub use widgets::*;
-pub use window::{InnerWindow, Window, WindowBuilder};
+pub use window::{InnerWindow, Window, WindowBuilder, WindowId};
 
+pub mod animation;
 pub mod cell;
+pub mod clipboard;
+pub mod collection;
+pub mod context_menu;
 pub mod dialogs;
+pub mod dispatcher;
 pub mod layouts;
 pub mod primitives;
 pub mod event;
 pub mod focus_manager;
+pub mod icons;
 pub mod point;
 pub mod rect;
+pub mod shortcut;
 pub mod traits;
 pub mod widgets;
 pub mod window;
 pub mod draw;
 pub mod theme;
 pub mod thickness;
+pub mod timer;
+pub mod tooltip;
+pub mod validator;
@@ -4,6 +4,7 @@ use std::cell::{Cell, RefCell};
 use std::path::Path;
 use std::sync::Arc;
 
+use draw::{draw_image, draw_image_scaled, draw_nine_slice, Resampling, ScaleCache};
 use event::Event;
 use point::Point;
 use rect::Rect;
@@ -12,6 +13,35 @@ use theme::{Theme};
 use traits::{Click, Place};
 use widgets::{Widget, VerticalPlacement, HorizontalPlacement};
 
+/// How an image is scaled to the rect of its widget.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stretch {
+    /// Keep the size of the image.
+    None,
+    /// Scale the image to the size of the rect, its aspect ratio is not kept.
+    Fill,
+    /// Scale the image as large as possible while it fits into the rect.
+    Uniform,
+    /// Scale the image as small as possible while it covers the rect, the rest is clipped.
+    UniformToFill,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HorizontalAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VerticalAlignment {
+    Top,
+    Center,
+    Bottom,
+}
+
+/// Draws an image in its rect. By default the image keeps its size and is placed at the
+/// top left, see `stretch`, `alignment` and `nine_slice` to scale it.
 pub struct Image {
     pub rect: Cell<Rect>,
     local_position: Cell<Point>,
@@ -19,7 +49,15 @@ pub struct Image {
     horizontal_placement: Cell<HorizontalPlacement>,
     margin: Cell<Thickness>,
     children: RefCell<Vec<Arc<dyn Widget>>>,
+    /// Call `image_changed` after its pixels were changed in place
     pub image: RefCell<orbimage::Image>,
+    pub stretch: Cell<Stretch>,
+    pub resampling: Cell<Resampling>,
+    pub horizontal_alignment: Cell<HorizontalAlignment>,
+    pub vertical_alignment: Cell<VerticalAlignment>,
+    /// The unscaled borders of the image if it is drawn with nine-slice scaling
+    pub nine_slice: Cell<Option<Thickness>>,
+    scaled: ScaleCache,
     click_callback: RefCell<Option<Arc<dyn Fn(&Image, Point)>>>,
 }
 
@@ -41,6 +79,12 @@ impl Image {
             margin: Cell::new(Thickness::default()),
             children: RefCell::new(vec![]),
             image: RefCell::new(image),
+            stretch: Cell::new(Stretch::None),
+            resampling: Cell::new(Resampling::Bilinear),
+            horizontal_alignment: Cell::new(HorizontalAlignment::Left),
+            vertical_alignment: Cell::new(VerticalAlignment::Top),
+            nine_slice: Cell::new(None),
+            scaled: ScaleCache::new(1),
             click_callback: RefCell::new(None)
         })
     }
@@ -48,6 +92,71 @@ impl Image {
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Arc<Self>, String> {
         Ok(Self::from_image(orbimage::Image::from_path(path)?))
     }
+
+    pub fn stretch(&self, stretch: Stretch) -> &Self {
+        self.stretch.set(stretch);
+        self
+    }
+
+    pub fn resampling(&self, resampling: Resampling) -> &Self {
+        self.resampling.set(resampling);
+        self
+    }
+
+    /// Set where the image is placed in the rect if it is smaller or larger than the rect.
+    pub fn alignment(&self, horizontal: HorizontalAlignment, vertical: VerticalAlignment) -> &Self {
+        self.horizontal_alignment.set(horizontal);
+        self.vertical_alignment.set(vertical);
+        self
+    }
+
+    /// Draw the image with nine-slice scaling, the corners of the given size keep their size
+    /// and the rest is stretched to the rect. It ignores the stretch mode and alignment.
+    pub fn nine_slice(&self, slice: Option<Thickness>) -> &Self {
+        self.nine_slice.set(slice);
+        self
+    }
+
+    /// Scale the image again the next time it is drawn, its scaled pixels are kept until
+    /// its size changes otherwise.
+    pub fn image_changed(&self) {
+        self.scaled.clear();
+    }
+
+    /// Get the rect the image is drawn in, it can be larger than the rect of the widget.
+    fn image_rect(&self, image: &orbimage::Image) -> Rect {
+        let rect = self.rect.get();
+        let (image_width, image_height) = (image.width(), image.height());
+
+        let (width, height) = match self.stretch.get() {
+            Stretch::None => (image_width, image_height),
+            Stretch::Fill => (rect.width, rect.height),
+            Stretch::Uniform | Stretch::UniformToFill if image_width > 0 && image_height > 0 => {
+                let x_scale = rect.width as f32 / image_width as f32;
+                let y_scale = rect.height as f32 / image_height as f32;
+                let scale = if self.stretch.get() == Stretch::Uniform {
+                    x_scale.min(y_scale)
+                } else {
+                    x_scale.max(y_scale)
+                };
+                ((image_width as f32 * scale).round() as u32, (image_height as f32 * scale).round() as u32)
+            }
+            _ => (0, 0),
+        };
+
+        let x = match self.horizontal_alignment.get() {
+            HorizontalAlignment::Left => rect.x,
+            HorizontalAlignment::Center => rect.x + (rect.width as i32 - width as i32) / 2,
+            HorizontalAlignment::Right => rect.x + rect.width as i32 - width as i32,
+        };
+        let y = match self.vertical_alignment.get() {
+            VerticalAlignment::Top => rect.y,
+            VerticalAlignment::Center => rect.y + (rect.height as i32 - height as i32) / 2,
+            VerticalAlignment::Bottom => rect.y + rect.height as i32 - height as i32,
+        };
+
+        Rect::new(x, y, width, height)
+    }
 }
 
 impl Click for Image {
@@ -93,7 +202,18 @@ impl Widget for Image {
     fn draw(&self, renderer: &mut dyn Renderer, _focused: bool, _theme: &Theme) {
         let rect = self.rect.get();
         let image = self.image.borrow();
-        renderer.image(rect.x, rect.y, image.width(), image.height(), image.data());
+
+        if let Some(slice) = self.nine_slice.get() {
+            draw_nine_slice(renderer, rect, &image, slice, self.resampling.get(), rect, &self.scaled);
+            return;
+        }
+
+        let image_rect = self.image_rect(&image);
+        if image_rect.width == image.width() && image_rect.height == image.height() {
+            draw_image(renderer, image_rect.x, image_rect.y, image.width(), image.height(), image.data(), rect);
+        } else {
+            draw_image_scaled(renderer, image_rect, &image, self.resampling.get(), rect, &self.scaled);
+        }
     }
 
     fn event(&self, event: Event, focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
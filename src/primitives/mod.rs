@@ -1,6 +1,6 @@
-pub use self::image::Image;
+pub use self::image::{HorizontalAlignment, Image, Stretch, VerticalAlignment};
 pub use self::rectangle::Rectangle;
-pub use self::text_widget::TextWidget;
+pub use self::text_widget::{TextAlignment, TextWidget, TextWrap, VerticalTextAlignment};
 
 mod image;
 mod rectangle;
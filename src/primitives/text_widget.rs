@@ -1,5 +1,6 @@
 use orbclient::Renderer;
 use std::cell::{Cell, RefCell};
+use std::str::FromStr;
 use std::sync::Arc;
 
 use cell::CloneCell;
@@ -11,6 +12,141 @@ use theme::{Selector, Theme};
 use traits::{Place, Style};
 use widgets::{HorizontalPlacement, VerticalPlacement, Widget};
 
+/// Describes the horizontal alignment of text lines inside the rect of a widget.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum TextAlignment {
+    Left,
+    Center,
+    Right,
+    /// Stretch the spaces of wrapped lines to fill the whole width.
+    Justify,
+}
+
+impl FromStr for TextAlignment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(TextAlignment::Left),
+            "center" => Ok(TextAlignment::Center),
+            "right" => Ok(TextAlignment::Right),
+            "justify" => Ok(TextAlignment::Justify),
+            _ => Err(format!("unknown text alignment `{}`", s)),
+        }
+    }
+}
+
+/// Describes the vertical alignment of text inside the rect of a widget.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum VerticalTextAlignment {
+    Top,
+    Center,
+    Bottom,
+}
+
+impl FromStr for VerticalTextAlignment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "top" => Ok(VerticalTextAlignment::Top),
+            "center" | "middle" => Ok(VerticalTextAlignment::Center),
+            "bottom" => Ok(VerticalTextAlignment::Bottom),
+            _ => Err(format!("unknown vertical text alignment `{}`", s)),
+        }
+    }
+}
+
+/// Describes how lines that are wider than the rect of a widget are handled.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum TextWrap {
+    /// Characters that don't fit are not drawn.
+    Clip,
+    /// Lines are broken between words.
+    Wrap,
+    /// Lines are truncated and end with `...`.
+    Ellipsis,
+}
+
+impl TextWrap {
+    /// Get the count of lines the text takes in the given width.
+    pub fn line_count(&self, text: &str, width: u32) -> usize {
+        layout(text, width as usize / 8, *self).len()
+    }
+}
+
+impl FromStr for TextWrap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clip" | "none" => Ok(TextWrap::Clip),
+            "wrap" => Ok(TextWrap::Wrap),
+            "ellipsis" => Ok(TextWrap::Ellipsis),
+            _ => Err(format!("unknown text wrap `{}`", s)),
+        }
+    }
+}
+
+/// A single line of laid out text. Lines that were broken by word wrap could be justified.
+struct Line {
+    text: String,
+    wrapped: bool,
+}
+
+/// Break the text into lines that fit into the given count of columns.
+fn layout(text: &str, columns: usize, wrap: TextWrap) -> Vec<Line> {
+    let mut lines = vec![];
+
+    for paragraph in text.split('\n') {
+        match wrap {
+            TextWrap::Clip => lines.push(Line { text: paragraph.to_owned(), wrapped: false }),
+            TextWrap::Ellipsis => {
+                let mut text: String = paragraph.to_owned();
+                if paragraph.chars().count() > columns {
+                    text = paragraph.chars().take(columns.saturating_sub(3)).collect();
+                    text.push_str(&"..."[..columns.min(3)]);
+                }
+                lines.push(Line { text, wrapped: false });
+            }
+            TextWrap::Wrap => {
+                let mut line = String::new();
+                let mut line_len = 0;
+
+                for word in paragraph.split(' ') {
+                    let mut word: String = word.to_owned();
+                    let mut word_len = word.chars().count();
+
+                    if line_len > 0 && line_len + 1 + word_len > columns {
+                        lines.push(Line { text: line, wrapped: true });
+                        line = String::new();
+                        line_len = 0;
+                    }
+
+                    // break words that are wider than a whole line
+                    while columns > 0 && word_len > columns {
+                        let rest = word.chars().skip(columns).collect();
+                        lines.push(Line { text: word.chars().take(columns).collect(), wrapped: false });
+                        word = rest;
+                        word_len -= columns;
+                    }
+
+                    if line_len > 0 {
+                        line.push(' ');
+                        line_len += 1;
+                    }
+                    line.push_str(&word);
+                    line_len += word_len;
+                }
+
+                lines.push(Line { text: line, wrapped: false });
+            }
+        }
+    }
+
+    lines
+}
+
 pub struct TextWidget {
     pub rect: Cell<Rect>,
     local_position: Cell<Point>,
@@ -20,6 +156,14 @@ pub struct TextWidget {
     children: RefCell<Vec<Arc<dyn Widget>>>,
     pub selector: CloneCell<Selector>,
     pub text: CloneCell<String>,
+    /// If not set the `text-wrap` property of the theme is used.
+    pub text_wrap: Cell<Option<TextWrap>>,
+    /// If not set the `text-align` property of the theme is used.
+    pub text_alignment: Cell<Option<TextAlignment>>,
+    /// If not set the `vertical-align` property of the theme is used.
+    pub vertical_text_alignment: Cell<Option<VerticalTextAlignment>>,
+    /// The `text-wrap` of the theme the widget was drawn with last
+    theme_text_wrap: Cell<Option<TextWrap>>,
 }
 
 impl TextWidget {
@@ -33,6 +177,10 @@ impl TextWidget {
             children: RefCell::new(vec![]),
             selector: CloneCell::new(Selector::new(Some("Text"))),
             text: CloneCell::new(String::new()),
+            text_wrap: Cell::new(None),
+            text_alignment: Cell::new(None),
+            vertical_text_alignment: Cell::new(None),
+            theme_text_wrap: Cell::new(None),
         });
 
         let text_widget_clone = text_widget.clone();
@@ -53,7 +201,31 @@ impl TextWidget {
         &self.text
     }
 
+    pub fn text_wrap(&self, text_wrap: TextWrap) -> &Self {
+        self.text_wrap.set(Some(text_wrap));
+        self
+    }
+
+    pub fn text_alignment(&self, alignment: TextAlignment, vertical_alignment: VerticalTextAlignment) -> &Self {
+        self.text_alignment.set(Some(alignment));
+        self.vertical_text_alignment.set(Some(vertical_alignment));
+        self
+    }
+
+    /// Get the text wrap that is used, the one set on the widget or the `text-wrap` of the
+    /// theme. The default theme is used until the widget is drawn.
+    pub fn current_text_wrap(&self) -> TextWrap {
+        self.text_wrap.get()
+            .or_else(|| self.theme_text_wrap.get())
+            .unwrap_or_else(|| Theme::new().string("text-wrap", &self.selector.get()).parse().unwrap_or(TextWrap::Clip))
+    }
+
     fn adjust_width(&self, text_len: u32) {
+        // a stretched text widget gets its width from the parent
+        if self.horizontal_placement.get() == HorizontalPlacement::Stretch {
+            return;
+        }
+
         let mut rect = self.rect.get();
         rect.width = text_len * 8;
         self.rect.set(rect);
@@ -95,28 +267,71 @@ impl Widget for TextWidget {
 
     fn draw(&self, renderer: &mut dyn Renderer, _focused: bool, theme: &Theme) {
         let rect = self.rect().get();
-        let mut current_rect = self.rect().get();
-        let x = rect.x;
         let selector = &self.selector().get();
-        let text = self.text.get();
-
-        for c in text.chars() {
-            if c == '\n' {
-                current_rect.x = x;
-                current_rect.y += 16;
-            } else {
-                if current_rect.x + 8 <= rect.x + rect.width as i32
-                    && current_rect.y + 16 <= rect.y + rect.height as i32
+        let color = theme.color("color", selector);
+
+        // the properties that are not set on the widget are looked up in one pass over the theme
+        let (mut text_wrap, mut alignment, mut vertical_alignment) = (
+            self.text_wrap.get(),
+            self.text_alignment.get(),
+            self.vertical_text_alignment.get(),
+        );
+        if text_wrap.is_none() || alignment.is_none() || vertical_alignment.is_none() {
+            let values = theme.get_all(&["text-wrap", "text-align", "vertical-align"], selector);
+            let value = |i: usize| values[i].as_ref().and_then(|value| value.string()).unwrap_or_default();
+            let theme_text_wrap = value(0).parse().unwrap_or(TextWrap::Clip);
+            self.theme_text_wrap.set(Some(theme_text_wrap));
+            text_wrap = text_wrap.or(Some(theme_text_wrap));
+            alignment = alignment.or_else(|| value(1).parse().ok());
+            vertical_alignment = vertical_alignment.or_else(|| value(2).parse().ok());
+        }
+
+        let text_wrap = text_wrap.unwrap_or(TextWrap::Clip);
+        let alignment = alignment.unwrap_or(TextAlignment::Left);
+        let vertical_alignment = vertical_alignment.unwrap_or(VerticalTextAlignment::Top);
+
+        let lines = layout(&self.text.borrow(), rect.width as usize / 8, text_wrap);
+
+        let text_height = lines.len() as i32 * 16;
+        let mut y = match vertical_alignment {
+            VerticalTextAlignment::Top => rect.y,
+            VerticalTextAlignment::Center => rect.y + (rect.height as i32 - text_height) / 2,
+            VerticalTextAlignment::Bottom => rect.y + rect.height as i32 - text_height,
+        };
+
+        for line in lines {
+            let line_width = line.text.chars().count() as i32 * 8;
+            let free_width = rect.width as i32 - line_width;
+
+            let mut x = match alignment {
+                TextAlignment::Left | TextAlignment::Justify => rect.x,
+                TextAlignment::Center => rect.x + free_width / 2,
+                TextAlignment::Right => rect.x + free_width,
+            };
+
+            // spread the free space over the spaces of wrapped lines
+            let spaces = line.text.chars().filter(|&c| c == ' ').count() as i32;
+            let justify = alignment == TextAlignment::Justify && line.wrapped && spaces > 0 && free_width > 0;
+            let mut space_i = 0;
+
+            for c in line.text.chars() {
+                if x >= rect.x && x + 8 <= rect.x + rect.width as i32
+                    && y >= rect.y && y + 16 <= rect.y + rect.height as i32
                 {
-                    renderer.char(
-                        current_rect.x,
-                        current_rect.y,
-                        c,
-                        theme.color("color", selector),
-                    );
+                    renderer.char(x, y, c, color);
+                }
+                x += 8;
+
+                if justify && c == ' ' {
+                    x += free_width / spaces;
+                    if space_i < free_width % spaces {
+                        x += 1;
+                    }
+                    space_i += 1;
                 }
-                current_rect.x += 8;
             }
+
+            y += 16;
         }
     }
 
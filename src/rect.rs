@@ -1,3 +1,5 @@
+use std::cmp::{max, min};
+
 use super::Point;
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -45,4 +47,13 @@ impl Rect {
             self.y >= (r.y + r.height as i32)
         )
     }
+
+    // Get the area that is part of both rects, its size is zero if they don't intersect
+    pub fn intersection(&self, r: &Rect) -> Rect {
+        let x = max(self.x, r.x);
+        let y = max(self.y, r.y);
+        let right = min(self.x + self.width as i32, r.x + r.width as i32);
+        let bottom = min(self.y + self.height as i32, r.y + r.height as i32);
+        Rect::new(x, y, max(0, right - x) as u32, max(0, bottom - y) as u32)
+    }
 }
@@ -0,0 +1,278 @@
+use orbclient;
+use std::cell::RefCell;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use event::{KeyEvent, Modifiers};
+use widgets::{contains_widget, Widget};
+use window::Window;
+
+/// Names of the keys that could be used in a key chord.
+static KEY_NAMES: &'static [(u8, &'static str)] = &[
+    (orbclient::K_A, "A"), (orbclient::K_B, "B"), (orbclient::K_C, "C"), (orbclient::K_D, "D"),
+    (orbclient::K_E, "E"), (orbclient::K_F, "F"), (orbclient::K_G, "G"), (orbclient::K_H, "H"),
+    (orbclient::K_I, "I"), (orbclient::K_J, "J"), (orbclient::K_K, "K"), (orbclient::K_L, "L"),
+    (orbclient::K_M, "M"), (orbclient::K_N, "N"), (orbclient::K_O, "O"), (orbclient::K_P, "P"),
+    (orbclient::K_Q, "Q"), (orbclient::K_R, "R"), (orbclient::K_S, "S"), (orbclient::K_T, "T"),
+    (orbclient::K_U, "U"), (orbclient::K_V, "V"), (orbclient::K_W, "W"), (orbclient::K_X, "X"),
+    (orbclient::K_Y, "Y"), (orbclient::K_Z, "Z"),
+    (orbclient::K_0, "0"), (orbclient::K_1, "1"), (orbclient::K_2, "2"), (orbclient::K_3, "3"),
+    (orbclient::K_4, "4"), (orbclient::K_5, "5"), (orbclient::K_6, "6"), (orbclient::K_7, "7"),
+    (orbclient::K_8, "8"), (orbclient::K_9, "9"),
+    (orbclient::K_F1, "F1"), (orbclient::K_F2, "F2"), (orbclient::K_F3, "F3"),
+    (orbclient::K_F4, "F4"), (orbclient::K_F5, "F5"), (orbclient::K_F6, "F6"),
+    (orbclient::K_F7, "F7"), (orbclient::K_F8, "F8"), (orbclient::K_F9, "F9"),
+    (orbclient::K_F10, "F10"), (orbclient::K_F11, "F11"), (orbclient::K_F12, "F12"),
+    (orbclient::K_ENTER, "Enter"), (orbclient::K_ESC, "Esc"), (orbclient::K_TAB, "Tab"),
+    (orbclient::K_SPACE, "Space"), (orbclient::K_BKSP, "Backspace"), (orbclient::K_DEL, "Del"),
+    (orbclient::K_HOME, "Home"), (orbclient::K_END, "End"),
+    (orbclient::K_PGUP, "PageUp"), (orbclient::K_PGDN, "PageDown"),
+    (orbclient::K_UP, "Up"), (orbclient::K_DOWN, "Down"),
+    (orbclient::K_LEFT, "Left"), (orbclient::K_RIGHT, "Right"),
+    (orbclient::K_MINUS, "-"), (orbclient::K_EQUALS, "="), (orbclient::K_COMMA, ","),
+    (orbclient::K_PERIOD, "."), (orbclient::K_SLASH, "/"),
+];
+
+/// A key combined with modifier keys, for example Ctrl+Shift+P.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub scancode: u8,
+    pub modifiers: Modifiers,
+}
+
+impl KeyChord {
+    pub fn new(scancode: u8, modifiers: Modifiers) -> Self {
+        KeyChord {
+            scancode,
+            modifiers,
+        }
+    }
+
+    /// Check if the given key event triggers this chord.
+    pub fn matches(&self, key_event: &KeyEvent) -> bool {
+        self.scancode == key_event.scancode && self.modifiers == key_event.modifiers
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    /// Parse a chord like `Ctrl+Shift+P`. Names are matched case insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = Modifiers::default();
+        let mut scancode = None;
+
+        for part in s.split('+').map(|part| part.trim()) {
+            let modifier = match part.to_lowercase().as_str() {
+                "ctrl" | "control" => &mut modifiers.ctrl,
+                "shift" => &mut modifiers.shift,
+                "alt" => &mut modifiers.alt,
+                name => {
+                    if scancode.is_some() {
+                        return Err(format!("key chord `{}` contains more than one key", s));
+                    }
+
+                    scancode = KEY_NAMES
+                        .iter()
+                        .find(|&&(_, key_name)| key_name.to_lowercase() == name)
+                        .map(|&(scancode, _)| scancode);
+
+                    if scancode.is_none() {
+                        return Err(format!("unknown key `{}` in key chord `{}`", part, s));
+                    }
+                    continue;
+                }
+            };
+
+            if *modifier {
+                return Err(format!("key chord `{}` contains `{}` more than once", s, part));
+            }
+            *modifier = true;
+        }
+
+        match scancode {
+            Some(scancode) => Ok(KeyChord::new(scancode, modifiers)),
+            None => Err(format!("key chord `{}` contains no key", s)),
+        }
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.modifiers.alt {
+            write!(f, "Alt+")?;
+        }
+
+        match KEY_NAMES.iter().find(|&&(scancode, _)| scancode == self.scancode) {
+            Some(&(_, name)) => write!(f, "{}", name),
+            None => write!(f, "0x{:02X}", self.scancode),
+        }
+    }
+}
+
+/// A named command that is triggered by a key chord.
+#[derive(Clone)]
+pub struct Command {
+    pub name: String,
+    pub chord: KeyChord,
+    scope: Option<Arc<dyn Widget>>,
+    callback: Arc<dyn Fn(&Window)>,
+}
+
+impl Command {
+    /// Check if the command is active for the given focused widget. A command without a
+    /// scope is always active, a scoped command only if the focused widget is part of
+    /// the scope's subtree.
+    fn in_scope(&self, focused_widget: Option<&Arc<dyn Widget>>) -> bool {
+        match (&self.scope, focused_widget) {
+            (&None, _) => true,
+            (&Some(ref scope), Some(focused_widget)) => contains_widget(scope, focused_widget),
+            (&Some(_), None) => false,
+        }
+    }
+}
+
+/// Maps key chords to named commands. Shortcuts are processed by the window before the
+/// events are dispatched to the widgets.
+pub struct ShortcutRegistry {
+    commands: RefCell<Vec<Command>>,
+}
+
+impl ShortcutRegistry {
+    pub fn new() -> Self {
+        ShortcutRegistry {
+            commands: RefCell::new(vec![]),
+        }
+    }
+
+    /// Register a command that is active in the whole window. A command with the same
+    /// name is replaced.
+    pub fn register<S: Into<String>, F: Fn(&Window) + 'static>(&self, name: S, chord: KeyChord, func: F) -> &Self {
+        self.insert(Command {
+            name: name.into(),
+            chord,
+            scope: None,
+            callback: Arc::new(func),
+        });
+        self
+    }
+
+    /// Register a command that is only active if the focused widget is the given
+    /// widget or one of its children.
+    pub fn register_scoped<S: Into<String>, T: Widget, F: Fn(&Window) + 'static>(
+        &self,
+        name: S,
+        chord: KeyChord,
+        scope: &Arc<T>,
+        func: F,
+    ) -> &Self {
+        self.insert(Command {
+            name: name.into(),
+            chord,
+            scope: Some(scope.clone()),
+            callback: Arc::new(func),
+        });
+        self
+    }
+
+    fn insert(&self, command: Command) {
+        let mut commands = self.commands.borrow_mut();
+        commands.retain(|c| c.name != command.name);
+
+        // scoped commands take precedence over window wide commands with the same chord
+        if command.scope.is_some() {
+            commands.insert(0, command);
+        } else {
+            commands.push(command);
+        }
+    }
+
+    /// Remove the command with the given name.
+    pub fn unregister(&self, name: &str) {
+        self.commands.borrow_mut().retain(|c| c.name != name);
+    }
+
+    /// Get the key chord of the command with the given name.
+    pub fn chord(&self, name: &str) -> Option<KeyChord> {
+        self.commands.borrow().iter().find(|c| c.name == name).map(|c| c.chord)
+    }
+
+    /// Get the callback of the command with the given name.
+    pub fn command(&self, name: &str) -> Option<Arc<dyn Fn(&Window)>> {
+        self.commands.borrow().iter().find(|c| c.name == name).map(|c| c.callback.clone())
+    }
+
+    /// Find the callback of the active command that is triggered by the given key event.
+    pub fn find(&self, key_event: &KeyEvent, focused_widget: Option<&Arc<dyn Widget>>) -> Option<Arc<dyn Fn(&Window)>> {
+        self.commands
+            .borrow()
+            .iter()
+            .find(|c| c.chord.matches(key_event) && c.in_scope(focused_widget))
+            .map(|c| c.callback.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use orbclient;
+    use std::sync::Arc;
+
+    use event::{KeyEvent, Modifiers};
+    use layouts::Grid;
+    use widgets::{Label, Widget};
+    use super::{KeyChord, ShortcutRegistry};
+
+    fn key_event(chord: &str) -> KeyEvent {
+        let chord: KeyChord = chord.parse().unwrap();
+        KeyEvent {
+            character: None,
+            scancode: chord.scancode,
+            modifiers: chord.modifiers,
+        }
+    }
+
+    #[test]
+    fn chord_round_trip() {
+        let chord: KeyChord = "Ctrl+Shift+P".parse().unwrap();
+        assert_eq!(chord, KeyChord::new(orbclient::K_P, Modifiers { ctrl: true, shift: true, alt: false }));
+        assert_eq!(chord.to_string(), "Ctrl+Shift+P");
+        assert_eq!("shift + control + p".parse::<KeyChord>(), Ok(chord));
+    }
+
+    #[test]
+    fn chord_rejects_invalid() {
+        assert!("Ctrl+Foo".parse::<KeyChord>().is_err());
+        assert!("Ctrl+Ctrl+P".parse::<KeyChord>().is_err());
+        assert!("Shift+Ctrl+Shift+P".parse::<KeyChord>().is_err());
+        assert!("Ctrl+P+Q".parse::<KeyChord>().is_err());
+        assert!("Ctrl+Shift".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn scoped_command_matches_inside_scope() {
+        let scope = Grid::new();
+        let inside = Label::new();
+        scope.add(&inside);
+        let outside = Label::new();
+
+        let registry = ShortcutRegistry::new();
+        registry.register_scoped("save", "Ctrl+S".parse().unwrap(), &scope, |_| {});
+
+        let scope: Arc<dyn Widget> = scope;
+        let inside: Arc<dyn Widget> = inside;
+        let outside: Arc<dyn Widget> = outside;
+        let event = key_event("Ctrl+S");
+        assert!(registry.find(&event, Some(&scope)).is_some());
+        assert!(registry.find(&event, Some(&inside)).is_some());
+        assert!(registry.find(&event, Some(&outside)).is_none());
+        assert!(registry.find(&event, None).is_none());
+        assert!(registry.find(&key_event("Ctrl+Shift+S"), Some(&inside)).is_none());
+    }
+}
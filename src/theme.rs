@@ -55,21 +55,32 @@ impl Theme {
     }
 
     pub fn get(&self, property: &str, query: &Selector) -> Option<Value> {
-        let mut matches: Vec<(bool, Specificity, Value)> = Vec::new();
+        self.get_all(&[property], query).pop().and_then(|value| value)
+    }
+
+    /// Get the values of several properties with a single pass over the rules.
+    pub fn get_all(&self, properties: &[&str], query: &Selector) -> Vec<Option<Value>> {
+        let mut matches: Vec<Vec<(bool, Specificity, Value)>> = properties.iter().map(|_| Vec::new()).collect();
 
         for rule in self.all_rules().iter().rev() {
-            let matching_selectors = rule.selectors.iter().filter(|x| x.matches(query)).collect::<Vec<_>>();
+            let highest_specifity = rule.selectors.iter().filter(|x| x.matches(query)).map(|sel| sel.specificity()).max();
 
-            if matching_selectors.len() > 0 {
-                if let Some(decl) = rule.declarations.iter().find(|decl| decl.property == property) {
-                    let highest_specifity = matching_selectors.iter().map(|sel| sel.specificity()).max().unwrap();
-                    matches.push((decl.important, highest_specifity, decl.value.clone()));
+            if let Some(highest_specifity) = highest_specifity {
+                for (property, matches) in properties.iter().zip(matches.iter_mut()) {
+                    if let Some(decl) = rule.declarations.iter().find(|decl| decl.property == *property) {
+                        matches.push((decl.important, highest_specifity, decl.value.clone()));
+                    }
                 }
             }
         }
 
-        matches.sort_by_key(|x| (x.0, x.1));
-        matches.last().map(|x| x.2.clone())
+        matches
+            .into_iter()
+            .map(|mut matches| {
+                matches.sort_by_key(|x| (x.0, x.1));
+                matches.pop().map(|x| x.2)
+            })
+            .collect()
     }
 
     pub fn color(&self, property: &str, query: &Selector) -> Color {
@@ -80,6 +91,10 @@ impl Theme {
     pub fn uint(&self, property: &str, query: &Selector) -> u32 {
         self.get(property, query).map(|v| v.uint().unwrap_or(0)).unwrap_or(0)
     }
+
+    pub fn string(&self, property: &str, query: &Selector) -> String {
+        self.get(property, query).and_then(|v| v.string()).unwrap_or_default()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -209,6 +224,7 @@ pub struct Declaration {
 pub enum Value {
     UInt(u32),
     Color(Color),
+    Str(String),
 }
 
 impl Value {
@@ -225,6 +241,13 @@ impl Value {
             _ => None,
         }
     }
+
+    pub fn string(&self) -> Option<String> {
+        match *self {
+            Value::Str(ref x) => Some(x.clone()),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -366,13 +389,19 @@ impl<'i> cssparser::DeclarationParser<'i> for DeclarationParser {
 
             "background" | "foreground" => Value::Color(parse_basic_color(input)?),
 
-            "border-radius" | "border-width" => {
+            "background-image" => Value::Str(input.expect_url_or_string()?.into_owned()),
+
+            "icon" => Value::Str(input.expect_ident_or_string()?.into_owned()),
+
+            "border-radius" | "border-width" | "background-slice" => {
                 match input.next()? {
                     Token::Number { int_value: Some(x), has_sign, .. } if !has_sign && x >= 0 => Value::UInt(x as u32),
                     t => return Err(BasicParseError::UnexpectedToken(t).into())
                 }
             }
 
+            "text-align" | "vertical-align" | "text-wrap" => Value::Str(input.expect_ident()?.into_owned()),
+
             _ => return Err(BasicParseError::UnexpectedToken(input.next()?).into()),
         };
 
@@ -0,0 +1,137 @@
+//! Timers of the widgets of a window.
+//!
+//! A timer belongs to the window whose event loop runs when it is started, a timer that
+//! is started before any event loop runs belongs to the first window that runs its loop.
+//! The window runs its timers in its event loop. When a timer expires, its callback is
+//! called and an `Event::Timer` with its id is sent to the widgets of the window. A widget
+//! that started a timer should only handle the events with its id.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use window::{current_window, WindowId};
+
+/// Identifies a timer, it is sent with the `Event::Timer` of the timer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+struct Timer {
+    id: TimerId,
+    /// The window that runs the timer, `None` until a window runs it
+    window: Option<WindowId>,
+    deadline: Instant,
+    /// The interval of a timer that repeats, `None` for a one-shot timer
+    interval: Option<Duration>,
+    callback: Option<Rc<dyn Fn(TimerId)>>,
+}
+
+thread_local! {
+    static TIMERS: RefCell<Vec<Timer>> = RefCell::new(vec![]);
+    static NEXT_ID: Cell<u64> = Cell::new(0);
+}
+
+fn start(delay: Duration, interval: Option<Duration>) -> TimerId {
+    let id = NEXT_ID.with(|next_id| {
+        let id = next_id.get();
+        next_id.set(id + 1);
+        TimerId(id)
+    });
+
+    TIMERS.with(|timers| {
+        timers.borrow_mut().push(Timer {
+            id,
+            window: current_window(),
+            deadline: Instant::now() + delay,
+            interval,
+            callback: None,
+        });
+    });
+
+    id
+}
+
+/// Start a timer that expires once after the delay.
+pub fn timeout(delay: Duration) -> TimerId {
+    start(delay, None)
+}
+
+/// Start a timer that expires after every interval until it is stopped.
+pub fn interval(interval: Duration) -> TimerId {
+    start(interval, Some(interval))
+}
+
+/// Set a callback that is called when the timer expires, before its event is sent.
+pub fn on_timer<F: Fn(TimerId) + 'static>(id: TimerId, func: F) {
+    TIMERS.with(|timers| {
+        if let Some(timer) = timers.borrow_mut().iter_mut().find(|timer| timer.id == id) {
+            timer.callback = Some(Rc::new(func));
+        }
+    });
+}
+
+/// Stop a timer, it does not expire any more.
+pub fn stop(id: TimerId) {
+    TIMERS.with(|timers| timers.borrow_mut().retain(|timer| timer.id != id));
+}
+
+/// Returns true if the timer was not stopped and, if it is a one-shot timer, did not expire yet.
+pub fn is_active(id: TimerId) -> bool {
+    TIMERS.with(|timers| timers.borrow().iter().any(|timer| timer.id == id))
+}
+
+/// Stop the timers of a window, e.g. when its event loop ended.
+pub fn stop_window(window: WindowId) {
+    TIMERS.with(|timers| timers.borrow_mut().retain(|timer| timer.window != Some(window)));
+}
+
+/// Check if the timer is run by the window. A timer without a window is run by the first window.
+fn runs_in(timer: &Timer, window: WindowId) -> bool {
+    timer.window.map_or(true, |timer_window| timer_window == window)
+}
+
+/// Get the time the next timer of the window expires.
+pub fn next_deadline(window: WindowId) -> Option<Instant> {
+    TIMERS.with(|timers| {
+        timers
+            .borrow()
+            .iter()
+            .filter(|timer| runs_in(timer, window))
+            .map(|timer| timer.deadline)
+            .min()
+    })
+}
+
+/// Expire the timers of the window whose deadline passed and call their callbacks. One-shot
+/// timers are removed, repeating timers are scheduled again. Returns the ids of the expired timers.
+pub fn run(window: WindowId, now: Instant) -> Vec<TimerId> {
+    let expired: Vec<(TimerId, Option<Rc<dyn Fn(TimerId)>>)> = TIMERS.with(|timers| {
+        let mut timers = timers.borrow_mut();
+        let mut expired = vec![];
+        for timer in timers.iter_mut().filter(|timer| runs_in(timer, window)) {
+            timer.window = Some(window);
+            if timer.deadline > now {
+                continue;
+            }
+
+            expired.push((timer.id, timer.callback.clone()));
+            if let Some(interval) = timer.interval {
+                // skip the intervals that were missed instead of catching up
+                while timer.deadline <= now {
+                    timer.deadline += interval.max(Duration::from_millis(1));
+                }
+            }
+        }
+        timers.retain(|timer| timer.window != Some(window) || timer.interval.is_some() || timer.deadline > now);
+        expired
+    });
+
+    // the callbacks could start and stop timers
+    for &(id, ref callback) in expired.iter() {
+        if let Some(ref callback) = *callback {
+            callback(id);
+        }
+    }
+
+    expired.into_iter().map(|(id, _)| id).collect()
+}
@@ -0,0 +1,210 @@
+use orbclient::Renderer;
+use std::cell::{Cell, RefCell};
+use std::cmp::{max, min};
+use std::sync::Arc;
+use std::time::Duration;
+
+use cell::CheckSet;
+use draw::draw_box;
+use point::Point;
+use rect::Rect;
+use theme::{Selector, Theme};
+use timer::{self, TimerId};
+use widgets::{find_widget_at, Widget};
+
+/// The default time the mouse has to rest on a widget until its tooltip appears.
+const DEFAULT_DELAY: u64 = 600;
+const PADDING: i32 = 4;
+/// The distance between the mouse cursor and the top of the tooltip.
+const CURSOR_OFFSET: i32 = 20;
+
+enum Content {
+    Text(String),
+    Widget(Arc<dyn Widget>),
+}
+
+struct Tooltip {
+    widget: Arc<dyn Widget>,
+    content: Content,
+}
+
+/// Shows a tooltip when the mouse rests on a widget with a tooltip. The tooltip
+/// disappears when the mouse leaves the widget, on a click and on a key press.
+/// Text tooltips are drawn with the `tooltip` selector.
+pub struct TooltipManager {
+    tooltips: RefCell<Vec<Tooltip>>,
+    delay: Cell<Duration>,
+    /// The hovered widget with a tooltip and the timer that expires after the delay.
+    hovered: RefCell<Option<(Arc<dyn Widget>, TimerId)>>,
+    /// After a click the tooltip stays hidden until the mouse leaves the widget.
+    suppressed: Cell<bool>,
+    visible: Cell<bool>,
+    mouse_point: Cell<Point>,
+    position: Cell<Point>,
+}
+
+impl TooltipManager {
+    pub fn new() -> Self {
+        TooltipManager {
+            tooltips: RefCell::new(vec![]),
+            delay: Cell::new(Duration::from_millis(DEFAULT_DELAY)),
+            hovered: RefCell::new(None),
+            suppressed: Cell::new(false),
+            visible: Cell::new(false),
+            mouse_point: Cell::new(Point::default()),
+            position: Cell::new(Point::default()),
+        }
+    }
+
+    /// Set the tooltip text of a widget, lines are separated by `\n`.
+    pub fn set<T: Widget, S: Into<String>>(&self, widget: &Arc<T>, text: S) -> &Self {
+        self.insert(widget.clone(), Content::Text(text.into()));
+        self
+    }
+
+    /// Show another widget as the tooltip of a widget.
+    pub fn set_widget<T: Widget, U: Widget>(&self, widget: &Arc<T>, tooltip: &Arc<U>) -> &Self {
+        self.insert(widget.clone(), Content::Widget(tooltip.clone()));
+        self
+    }
+
+    /// Remove the tooltip of a widget.
+    pub fn remove<T: Widget>(&self, widget: &Arc<T>) {
+        let widget: Arc<dyn Widget> = widget.clone();
+        self.tooltips.borrow_mut().retain(|tooltip| !Arc::ptr_eq(&tooltip.widget, &widget));
+    }
+
+    /// Set the time the mouse has to rest on a widget until its tooltip appears.
+    pub fn delay(&self, delay: Duration) -> &Self {
+        self.delay.set(delay);
+        self
+    }
+
+    fn insert(&self, widget: Arc<dyn Widget>, content: Content) {
+        let mut tooltips = self.tooltips.borrow_mut();
+        tooltips.retain(|tooltip| !Arc::ptr_eq(&tooltip.widget, &widget));
+        tooltips.push(Tooltip { widget, content });
+    }
+
+    fn has_tooltip(&self, widget: &Arc<dyn Widget>) -> bool {
+        self.tooltips.borrow().iter().any(|tooltip| Arc::ptr_eq(&tooltip.widget, widget))
+    }
+
+    /// Track the widget under the mouse. Returns true if the tooltip was hidden.
+    pub fn mouse_moved(&self, point: Point, widgets: &[Arc<dyn Widget>]) -> bool {
+        self.mouse_point.set(point);
+
+        let target = find_widget_at(widgets, point, &|widget: &Arc<dyn Widget>| self.has_tooltip(widget));
+        let mut hovered = self.hovered.borrow_mut();
+        let same = match (&*hovered, &target) {
+            (&Some((ref hovered, _)), &Some(ref target)) => Arc::ptr_eq(hovered, target),
+            (&None, &None) => true,
+            _ => false,
+        };
+
+        if same {
+            return false;
+        }
+
+        if let Some((_, timer_id)) = hovered.take() {
+            timer::stop(timer_id);
+        }
+        *hovered = target.map(|target| (target, timer::timeout(self.delay.get())));
+        self.suppressed.set(false);
+        self.visible.check_set(false)
+    }
+
+    /// Hide the tooltip until the mouse leaves the hovered widget, e.g. on a click.
+    /// Returns true if the tooltip was visible.
+    pub fn hide(&self) -> bool {
+        self.suppressed.set(true);
+        self.visible.check_set(false)
+    }
+
+    /// Show the tooltip if the mouse rested long enough on its widget, i.e. if the timer of
+    /// the hovered widget expired. Returns true if the tooltip appeared.
+    pub fn update(&self) -> bool {
+        if self.visible.get() || self.suppressed.get() {
+            return false;
+        }
+
+        match *self.hovered.borrow() {
+            Some((_, timer_id)) if !timer::is_active(timer_id) => {
+                let mouse_point = self.mouse_point.get();
+                self.position.set(Point::new(mouse_point.x, mouse_point.y + CURSOR_OFFSET));
+                self.visible.set(true);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Draw the visible tooltip on top of everything else. It is moved to stay
+    /// inside of the renderer.
+    pub fn draw(&self, renderer: &mut dyn Renderer, theme: &Theme) {
+        fn draw_widget(widget: &Arc<dyn Widget>, renderer: &mut dyn Renderer, theme: &Theme) {
+            widget.update();
+            widget.draw(renderer, false, theme);
+
+            for child in widget.children().borrow().iter() {
+                draw_widget(child, renderer, theme);
+            }
+        }
+
+        if !self.visible.get() {
+            return;
+        }
+
+        let hovered = match *self.hovered.borrow() {
+            Some((ref hovered, _)) => hovered.clone(),
+            None => return,
+        };
+
+        let tooltips = self.tooltips.borrow();
+        let tooltip = match tooltips.iter().find(|tooltip| Arc::ptr_eq(&tooltip.widget, &hovered)) {
+            Some(tooltip) => tooltip,
+            None => return,
+        };
+
+        let (width, height) = match tooltip.content {
+            Content::Text(ref text) => {
+                let columns = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+                let rows = max(1, text.lines().count());
+                (columns as i32 * 8 + 2 * PADDING, rows as i32 * 16 + 2 * PADDING)
+            }
+            Content::Widget(ref widget) => {
+                let rect = widget.rect().get();
+                (rect.width as i32, rect.height as i32)
+            }
+        };
+
+        // keep the tooltip inside of the window, above the cursor if there is no space below
+        let position = self.position.get();
+        let mut y = position.y;
+        if y + height > renderer.height() as i32 {
+            y = self.mouse_point.get().y - height - PADDING;
+        }
+        let x = max(0, min(position.x, renderer.width() as i32 - width));
+        let rect = Rect::new(x, max(0, y), width as u32, height as u32);
+
+        match tooltip.content {
+            Content::Text(ref text) => {
+                let selector = Selector::new(Some("tooltip"));
+                draw_box(renderer, rect, theme, &selector);
+
+                let color = theme.color("color", &selector);
+                for (row, line) in text.lines().enumerate() {
+                    let y = rect.y + PADDING + row as i32 * 16;
+                    for (column, c) in line.chars().enumerate() {
+                        renderer.char(rect.x + PADDING + column as i32 * 8, y, c, color);
+                    }
+                }
+            }
+            Content::Widget(ref widget) => {
+                widget.rect().set(rect);
+                widget.arrange();
+                draw_widget(widget, renderer, theme);
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub trait Change {
+    type Value;
+
+    fn emit_change(&self, value: Self::Value);
+    fn on_change<T: Fn(&Self, Self::Value) + 'static>(&self, func: T) -> &Self;
+}
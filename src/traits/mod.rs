@@ -1,3 +1,4 @@
+pub use self::change::Change;
 pub use self::click::Click;
 pub use self::enter::Enter;
 pub use self::event_filter::EventFilter;
@@ -6,6 +7,7 @@ pub use self::resize::Resize;
 pub use self::text::Text;
 pub use self::style::Style;
 
+mod change;
 mod click;
 mod enter;
 mod event_filter;
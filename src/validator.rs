@@ -0,0 +1,328 @@
+/// Describes a class of characters a validator accepts.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CharClass {
+    /// Alphabetic characters as defined by unicode
+    Alphabetic,
+    /// Alphabetic or numeric characters as defined by unicode
+    Alphanumeric,
+    /// Numeric characters as defined by unicode
+    Numeric,
+    /// The ascii digits `0` to `9`
+    Digit,
+    /// Whitespace characters
+    Whitespace,
+    /// Ascii punctuation characters
+    Punctuation,
+}
+
+impl CharClass {
+    /// Check if the given character is part of the class.
+    pub fn matches(&self, c: char) -> bool {
+        match *self {
+            CharClass::Alphabetic => c.is_alphabetic(),
+            CharClass::Alphanumeric => c.is_alphanumeric(),
+            CharClass::Numeric => c.is_numeric(),
+            CharClass::Digit => c.is_ascii_digit(),
+            CharClass::Whitespace => c.is_whitespace(),
+            CharClass::Punctuation => c.is_ascii_punctuation(),
+        }
+    }
+}
+
+/// Check if a character matches a placeholder of a mask. Characters that are
+/// no placeholder must match literally.
+///
+/// * `#` - an ascii digit
+/// * `A` - an alphabetic character
+/// * `*` - an alphanumeric character
+fn mask_matches(mask_c: char, c: char) -> bool {
+    match mask_c {
+        '#' => c.is_ascii_digit(),
+        'A' => c.is_alphabetic(),
+        '*' => c.is_alphanumeric(),
+        _ => mask_c == c,
+    }
+}
+
+fn is_mask_literal(mask_c: char) -> bool {
+    mask_c != '#' && mask_c != 'A' && mask_c != '*'
+}
+
+/// Check if the text could be completed to a number.
+fn is_number_prefix(text: &str, integer: bool) -> bool {
+    match text {
+        "" | "-" | "+" => true,
+        "." | "-." | "+." => !integer,
+        _ if integer => text.parse::<i64>().is_ok(),
+        _ => text.parse::<f64>().is_ok(),
+    }
+}
+
+/// Declarative description of the input a text box accepts.
+///
+/// Keystrokes that would break the rules are rejected while typing. Rules that can
+/// only be fulfilled by a complete input, like a numeric range or a mask, are
+/// checked by `is_valid`. An empty text is always valid.
+#[derive(Clone, Debug, Default)]
+pub struct Validator {
+    max_length: Option<usize>,
+    char_classes: Vec<CharClass>,
+    range: Option<(f64, f64)>,
+    /// Set if the numbers of the range must be integers
+    integer: bool,
+    mask: Option<Vec<char>>,
+}
+
+impl Validator {
+    /// Create a validator that accepts any input.
+    pub fn new() -> Self {
+        Validator::default()
+    }
+
+    /// Limit the input to the given count of characters.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Allow the given character class. If no class is allowed, every character
+    /// is accepted.
+    pub fn allow(mut self, char_class: CharClass) -> Self {
+        self.char_classes.push(char_class);
+        self
+    }
+
+    /// Only accept numbers between min and max (inclusive).
+    pub fn numeric(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self.integer = false;
+        self
+    }
+
+    /// Only accept integers between min and max (inclusive).
+    pub fn integer(mut self, min: i64, max: i64) -> Self {
+        self.range = Some((min as f64, max as f64));
+        self.integer = true;
+        self
+    }
+
+    /// Only accept input matching the given mask, e.g. `####-##-##` for a date.
+    /// `#` stands for a digit, `A` for a letter and `*` for a letter or digit. All
+    /// other characters of the mask are inserted automatically while typing.
+    pub fn mask<S: Into<String>>(mut self, mask: S) -> Self {
+        self.mask = Some(mask.into().chars().collect());
+        self
+    }
+
+    fn accepts_char(&self, c: char) -> bool {
+        if self.range.is_some() {
+            let number_char = c.is_ascii_digit() || c == '-' || c == '+' || (c == '.' && !self.integer);
+            if !number_char {
+                return false;
+            }
+        }
+
+        self.char_classes.is_empty() || self.char_classes.iter().any(|class| class.matches(c))
+    }
+
+    fn matches_mask(&self, text: &str, complete: bool) -> bool {
+        if let Some(ref mask) = self.mask {
+            let len = text.chars().count();
+            if len > mask.len() || (complete && len != mask.len()) {
+                return false;
+            }
+
+            text.chars().zip(mask.iter()).all(|(c, &mask_c)| mask_matches(mask_c, c))
+        } else {
+            true
+        }
+    }
+
+    /// Try to insert the character at the given byte index of the text. Literal
+    /// characters of the mask are inserted in front of it if needed. Returns the
+    /// index behind the inserted characters or None if the input was rejected.
+    pub fn insert(&self, text: &mut String, text_i: usize, c: char) -> Option<usize> {
+        let mut insertion = String::new();
+        let mut literal = false;
+
+        if let Some(ref mask) = self.mask {
+            let mut position = text[..text_i].chars().count();
+            while position < mask.len() && is_mask_literal(mask[position]) && mask[position] != c {
+                insertion.push(mask[position]);
+                position += 1;
+            }
+
+            if position >= mask.len() || !mask_matches(mask[position], c) {
+                return None;
+            }
+
+            literal = is_mask_literal(mask[position]);
+        }
+
+        if !literal && !self.accepts_char(c) {
+            return None;
+        }
+
+        insertion.push(c);
+
+        let mut new_text = String::with_capacity(text.len() + insertion.len());
+        new_text.push_str(&text[..text_i]);
+        new_text.push_str(&insertion);
+        new_text.push_str(&text[text_i..]);
+
+        if let Some(max_length) = self.max_length {
+            if new_text.chars().count() > max_length {
+                return None;
+            }
+        }
+
+        if self.range.is_some() && !is_number_prefix(&new_text, self.integer) {
+            return None;
+        }
+
+        if !self.matches_mask(&new_text, false) {
+            return None;
+        }
+
+        *text = new_text;
+        Some(text_i + insertion.len())
+    }
+
+    /// Check if the text could be completed to a valid input, e.g. the text left
+    /// after a deletion.
+    pub fn accepts(&self, text: &str) -> bool {
+        if let Some(max_length) = self.max_length {
+            if text.chars().count() > max_length {
+                return false;
+            }
+        }
+
+        if self.range.is_some() && !is_number_prefix(text, self.integer) {
+            return false;
+        }
+
+        if let Some(ref mask) = self.mask {
+            if !self.matches_mask(text, false) {
+                return false;
+            }
+
+            return text.chars()
+                .zip(mask.iter())
+                .all(|(c, &mask_c)| is_mask_literal(mask_c) || self.accepts_char(c));
+        }
+
+        text.chars().all(|c| self.accepts_char(c))
+    }
+
+    /// Check if the text is a complete and valid input.
+    pub fn is_valid(&self, text: &str) -> bool {
+        if text.is_empty() {
+            return true;
+        }
+
+        if let Some(max_length) = self.max_length {
+            if text.chars().count() > max_length {
+                return false;
+            }
+        }
+
+        if let Some((min, max)) = self.range {
+            let value = if self.integer {
+                text.parse::<i64>().map(|value| value as f64).ok()
+            } else {
+                text.parse::<f64>().ok()
+            };
+
+            match value {
+                Some(value) => if value < min || value > max {
+                    return false;
+                },
+                None => return false,
+            }
+        }
+
+        if self.mask.is_some() {
+            return self.matches_mask(text, true);
+        }
+
+        text.chars().all(|c| self.accepts_char(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CharClass, Validator};
+
+    fn type_text(validator: &Validator, input: &str) -> String {
+        let mut text = String::new();
+        let mut text_i = 0;
+        for c in input.chars() {
+            if let Some(next_i) = validator.insert(&mut text, text_i, c) {
+                text_i = next_i;
+            }
+        }
+        text
+    }
+
+    #[test]
+    fn mask_inserts_literals() {
+        let validator = Validator::new().mask("####-##-##");
+        assert_eq!(type_text(&validator, "20240115"), "2024-01-15");
+        assert_eq!(type_text(&validator, "2024-01-15"), "2024-01-15");
+        assert!(validator.is_valid("2024-01-15"));
+        assert!(!validator.is_valid("2024-01"));
+    }
+
+    #[test]
+    fn mask_rejects_wrong_characters() {
+        let validator = Validator::new().mask("AA-##");
+        assert_eq!(type_text(&validator, "a1b2-3"), "ab-23");
+        assert!(!validator.is_valid("ab-2c"));
+    }
+
+    #[test]
+    fn mask_rejects_deleted_literals() {
+        let validator = Validator::new().mask("####-##-##");
+        assert!(validator.accepts("2024-01-1"));
+        assert!(validator.accepts("2024-"));
+        assert!(!validator.accepts("202401-15"));
+    }
+
+    #[test]
+    fn numeric_range() {
+        let validator = Validator::new().numeric(-10.0, 10.0);
+        assert_eq!(type_text(&validator, "-1a.5"), "-1.5");
+        assert!(validator.is_valid("-1.5"));
+        assert!(validator.is_valid("10"));
+        assert!(!validator.is_valid("10.5"));
+        assert!(!validator.is_valid("-"));
+        assert!(validator.accepts("-"));
+        assert!(!validator.accepts("1-"));
+    }
+
+    #[test]
+    fn integer_range() {
+        let validator = Validator::new().integer(0, 100);
+        assert_eq!(type_text(&validator, "4.2"), "42");
+        assert!(validator.is_valid("100"));
+        assert!(!validator.is_valid("101"));
+        assert!(!validator.accepts("4.2"));
+    }
+
+    #[test]
+    fn char_classes() {
+        let validator = Validator::new().allow(CharClass::Alphabetic).allow(CharClass::Whitespace);
+        assert_eq!(type_text(&validator, "ab 1c!"), "ab c");
+        assert!(validator.is_valid("ab c"));
+        assert!(!validator.is_valid("ab1"));
+        assert!(!validator.accepts("ab1"));
+    }
+
+    #[test]
+    fn max_length() {
+        let validator = Validator::new().max_length(3);
+        assert_eq!(type_text(&validator, "abcd"), "abc");
+        assert!(!validator.is_valid("abcd"));
+        assert!(validator.is_valid(""));
+    }
+}
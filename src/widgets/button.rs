@@ -1,17 +1,20 @@
 use std::cell::{Cell, RefCell};
 use std::sync::Arc;
 
+use orbclient::Renderer;
+
 use cell::{CheckSet, CloneCell};
 use event::Event;
+use icons::{self, IconPlacement};
 use point::Point;
 use rect::Rect;
 use thickness::Thickness;
-use theme::Selector;
+use theme::{Selector, Theme};
 use traits::{Click, Place, Style, Text};
 use widgets::{HorizontalPlacement, VerticalPlacement, Widget};
 
-use primitives::Rectangle;
-use primitives::TextWidget;
+use primitives::{Image, Rectangle};
+use primitives::{TextAlignment, TextWidget, TextWrap, VerticalTextAlignment};
 
 const BUTTON_SELECTOR: &str = "button";
 
@@ -25,6 +28,12 @@ pub struct Button {
     pub selector: CloneCell<Selector>,
     pub text: CloneCell<String>,
     pub text_offset: Cell<Point>,
+    text_widget: Arc<TextWidget>,
+    background: Arc<Rectangle>,
+    icon: RefCell<Option<Arc<Image>>>,
+    /// The icon that is set by the `icon` property of the theme, with its name.
+    theme_icon: RefCell<Option<(String, Arc<Image>)>>,
+    pub icon_placement: Cell<IconPlacement>,
     click_callback: RefCell<Option<Arc<dyn Fn(&Button, Point)>>>,
     hover: Cell<bool>,
     pressed: Cell<bool>,
@@ -38,12 +47,13 @@ impl Button {
         let text_widget = TextWidget::new();
         text_widget.inner_text().bind(&inner_text);
         text_widget.selector().bind(&selector);
-        text_widget.placement(VerticalPlacement::Center, HorizontalPlacement::Center);
+        text_widget.placement(VerticalPlacement::Stretch, HorizontalPlacement::Stretch);
+        text_widget.margin().set(Thickness::new(6, 6, 6, 6));
 
         let background = Rectangle::new();
         background.selector().bind(&selector);
         background.placement(VerticalPlacement::Stretch, HorizontalPlacement::Stretch);
-        background.add(text_widget);
+        background.add(text_widget.clone());
 
         Arc::new(Button {
             rect: Cell::new(Rect::new(0, 0, 0, 28)),
@@ -51,21 +61,96 @@ impl Button {
             vertical_placement: Cell::new(VerticalPlacement::Absolute),
             horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
             margin: Cell::new(Thickness::default()),
-            children: RefCell::new(vec![background]),
+            children: RefCell::new(vec![background.clone()]),
             selector,
             text: inner_text,
             text_offset: Cell::new(Point::new(6, 6)),
+            text_widget,
+            background,
+            icon: RefCell::new(None),
+            theme_icon: RefCell::new(None),
+            icon_placement: Cell::new(IconPlacement::Left),
             click_callback: RefCell::new(None),
             hover: Cell::new(false),
             pressed: Cell::new(false),
         })
     }
 
+    /// Set how text that doesn't fit into the widget is handled. If the text is
+    /// wrapped or truncated the widget keeps its size on text changes.
+    pub fn text_wrap(&self, text_wrap: TextWrap) -> &Self {
+        self.text_widget.text_wrap(text_wrap);
+        self
+    }
+
+    pub fn text_alignment(&self, alignment: TextAlignment, vertical_alignment: VerticalTextAlignment) -> &Self {
+        self.text_widget.text_alignment(alignment, vertical_alignment);
+        self
+    }
+
+    /// Show an icon next to the text, it replaces the icon that is set by the theme.
+    pub fn icon(&self, icon: &Arc<Image>) -> &Self {
+        *self.icon.borrow_mut() = Some(icon.clone());
+        self.icon_changed();
+        self
+    }
+
+    /// Set where the icon is placed relative to the text.
+    pub fn icon_placement(&self, icon_placement: IconPlacement) -> &Self {
+        self.icon_placement.set(icon_placement);
+        self.icon_changed();
+        self
+    }
+
+    fn current_icon(&self) -> Option<Arc<Image>> {
+        self.icon.borrow().clone().or_else(|| self.theme_icon.borrow().as_ref().map(|&(_, ref icon)| icon.clone()))
+    }
+
+    fn icon_changed(&self) {
+        let mut children: Vec<Arc<dyn Widget>> = vec![];
+        let icon = self.current_icon();
+        if icon.is_none() || self.icon_placement.get() != IconPlacement::IconOnly {
+            children.push(self.text_widget.clone());
+        }
+        if let Some(icon) = icon {
+            children.push(icon);
+        }
+        *self.background.children().borrow_mut() = children;
+
+        self.adjust_size();
+        self.arrange();
+    }
+
+    /// Load the icon that is set by the theme if its name changed.
+    fn update_theme_icon(&self, theme: &Theme) {
+        let name = theme.string("icon", &self.selector.get());
+        let changed = match *self.theme_icon.borrow() {
+            Some((ref current, _)) => *current != name,
+            None => !name.is_empty(),
+        };
+
+        if changed {
+            *self.theme_icon.borrow_mut() = icons::get(&name).map(|icon| (name, icon));
+            if self.icon.borrow().is_none() {
+                self.icon_changed();
+            }
+        }
+    }
+
     fn adjust_size(&self) {
-        self.size(
-            self.text.get().len() as u32 * 8 + 2 * self.text_offset.get().x as u32,
-            16 + 2 * self.text_offset.get().y as u32,
-        );
+        match self.text_widget.current_text_wrap() {
+            TextWrap::Wrap | TextWrap::Ellipsis => return,
+            TextWrap::Clip => (),
+        }
+
+        let text_offset = self.text_offset.get();
+        let mut size = (self.text.get().len() as u32 * 8, 16);
+        if let Some(icon) = self.current_icon() {
+            let icon_rect = icon.rect.get();
+            size = self.icon_placement.get().size((icon_rect.width, icon_rect.height), size);
+        }
+
+        self.size(size.0 + 2 * text_offset.x as u32, size.1 + 2 * text_offset.y as u32);
     }
 }
 
@@ -94,6 +179,7 @@ impl Text for Button {
     fn text_offset(&self, x: i32, y: i32) -> &Self {
         self.text_offset.set(Point::new(x, y));
         self.adjust_size();
+        self.arrange();
         self
     }
 }
@@ -130,7 +216,10 @@ impl Widget for Button {
     }
 
     fn update(&self) {
-        let mut selector = Selector::new(Some(BUTTON_SELECTOR)).with_pseudo_class(
+        // keep the classes of the selector, themes could set an icon for them
+        let mut selector = self.selector.get();
+        selector.pseudo_classes.clear();
+        selector = selector.with_pseudo_class(
             if self.pressed.get() {
                 "active"
             } else {
@@ -145,6 +234,39 @@ impl Widget for Button {
         self.selector().set(selector);
     }
 
+    fn arrange(&self) {
+        let rect = self.rect.get();
+        let text_offset = self.text_offset.get();
+        let inner_rect = Rect::new(
+            rect.x + text_offset.x,
+            rect.y + text_offset.y,
+            (rect.width as i32 - 2 * text_offset.x).max(0) as u32,
+            (rect.height as i32 - 2 * text_offset.y).max(0) as u32,
+        );
+
+        let mut text_rect = inner_rect;
+        if let Some(icon) = self.current_icon() {
+            let icon_rect = icon.rect.get();
+            let (icon_rect, caption_rect) = self.icon_placement.get().layout(inner_rect, icon_rect.width, icon_rect.height);
+            icon.local_position().set(Point::new(icon_rect.x - rect.x, icon_rect.y - rect.y));
+            text_rect = caption_rect;
+        }
+
+        self.text_widget.margin().set(Thickness::new(
+            text_rect.x - rect.x,
+            text_rect.y - rect.y,
+            rect.x + rect.width as i32 - text_rect.x - text_rect.width as i32,
+            rect.y + rect.height as i32 - text_rect.y - text_rect.height as i32,
+        ));
+
+        self.background.rect().set(rect);
+        self.background.arrange();
+    }
+
+    fn draw(&self, _renderer: &mut dyn Renderer, _focused: bool, theme: &Theme) {
+        self.update_theme_icon(theme);
+    }
+
     fn event(&self, event: Event, focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
         match event {
             Event::Mouse {
@@ -0,0 +1,436 @@
+use orbclient::{Color, Renderer};
+use orbimage;
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+
+use draw::draw_image;
+use event::Event;
+use point::Point;
+use rect::Rect;
+use thickness::Thickness;
+use theme::Theme;
+use traits::{Click, Place};
+use widgets::{HorizontalPlacement, VerticalPlacement, Widget};
+
+/// Draws shapes, gradients and text on a renderer. The coordinates are relative to the
+/// top left of the renderer. Lines and outlines use the stroke width, antialiasing
+/// smooths lines, circles and the edges of polygons.
+pub struct Painter<'a> {
+    renderer: &'a mut dyn Renderer,
+    stroke_width: u32,
+    antialiasing: bool,
+}
+
+impl<'a> Painter<'a> {
+    pub fn new(renderer: &'a mut dyn Renderer) -> Self {
+        Painter {
+            renderer,
+            stroke_width: 1,
+            antialiasing: false,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.renderer.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.renderer.height()
+    }
+
+    pub fn stroke_width(&mut self, stroke_width: u32) -> &mut Self {
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    pub fn antialiasing(&mut self, antialiasing: bool) -> &mut Self {
+        self.antialiasing = antialiasing;
+        self
+    }
+
+    /// Replace all pixels with a color.
+    pub fn clear(&mut self, color: Color) {
+        self.renderer.set(color);
+    }
+
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: Color) {
+        if self.stroke_width <= 1 {
+            if self.antialiasing {
+                self.renderer.wu_line(x0, y0, x1, y1, color);
+            } else {
+                self.renderer.line(x0, y0, x1, y1, color);
+            }
+            return;
+        }
+
+        // a wide line is a rectangle along the line with round caps
+        let (dx, dy) = ((x1 - x0) as f64, (y1 - y0) as f64);
+        let length = (dx * dx + dy * dy).sqrt();
+        let half_width = self.stroke_width as f64 / 2.0;
+        if length > 0.0 {
+            let (nx, ny) = (-dy / length * half_width, dx / length * half_width);
+            let (x0, y0, x1, y1) = (x0 as f64 + 0.5, y0 as f64 + 0.5, x1 as f64 + 0.5, y1 as f64 + 0.5);
+            let corners = [(x0 + nx, y0 + ny), (x1 + nx, y1 + ny), (x1 - nx, y1 - ny), (x0 - nx, y0 - ny)];
+            self.fill_shape(&corners, color);
+        }
+        self.fill_disc(x0 as f64 + 0.5, y0 as f64 + 0.5, half_width, color);
+        self.fill_disc(x1 as f64 + 0.5, y1 as f64 + 0.5, half_width, color);
+    }
+
+    /// Draw lines between the points.
+    pub fn polyline(&mut self, points: &[Point], color: Color) {
+        for pair in points.windows(2) {
+            self.line(pair[0].x, pair[0].y, pair[1].x, pair[1].y, color);
+        }
+    }
+
+    pub fn stroke_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: Color) {
+        let stroke = self.stroke_width.min(width / 2).min(height / 2).max(1);
+        self.renderer.rect(x, y, width, stroke, color);
+        self.renderer.rect(x, y + height as i32 - stroke as i32, width, stroke, color);
+        self.renderer.rect(x, y + stroke as i32, stroke, height.saturating_sub(2 * stroke), color);
+        self.renderer.rect(x + width as i32 - stroke as i32, y + stroke as i32, stroke, height.saturating_sub(2 * stroke), color);
+    }
+
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: Color) {
+        self.renderer.rect(x, y, width, height, color);
+    }
+
+    pub fn stroke_rounded_rect(&mut self, x: i32, y: i32, width: u32, height: u32, radius: u32, color: Color) {
+        // wide outlines are drawn as nested outlines
+        for i in 0..self.stroke_width.min(width / 2).min(height / 2).max(1) {
+            self.renderer.rounded_rect(
+                x + i as i32,
+                y + i as i32,
+                width - 2 * i,
+                height - 2 * i,
+                radius.saturating_sub(i),
+                false,
+                color,
+            );
+        }
+    }
+
+    pub fn fill_rounded_rect(&mut self, x: i32, y: i32, width: u32, height: u32, radius: u32, color: Color) {
+        self.renderer.rounded_rect(x, y, width, height, radius, true, color);
+    }
+
+    pub fn stroke_circle(&mut self, x: i32, y: i32, radius: u32, color: Color) {
+        if self.stroke_width <= 1 {
+            if self.antialiasing {
+                self.renderer.wu_circle(x, y, radius as i32, color);
+            } else {
+                self.renderer.circle(x, y, radius as i32, color);
+            }
+            return;
+        }
+
+        // the stroke is centered on the circle
+        let outer = radius as f64 + self.stroke_width as f64 / 2.0;
+        let inner = (outer - self.stroke_width as f64).max(0.0);
+        self.fill_ring(x as f64 + 0.5, y as f64 + 0.5, inner, outer, color);
+    }
+
+    pub fn fill_circle(&mut self, x: i32, y: i32, radius: u32, color: Color) {
+        if self.antialiasing {
+            self.renderer.wu_circle(x, y, radius as i32, color);
+        }
+        self.renderer.circle(x, y, -(radius as i32), color);
+    }
+
+    /// Draw a part of a circle. The angles are in degrees, clockwise from the right side
+    /// of the circle.
+    pub fn arc(&mut self, x: i32, y: i32, radius: u32, start_angle: f64, end_angle: f64, color: Color) {
+        let sweep = (end_angle - start_angle).to_radians();
+        let segments = ((radius as f64 * sweep.abs() / 4.0).ceil() as usize).max(4);
+        let points: Vec<Point> = (0..segments + 1)
+            .map(|i| {
+                let angle = start_angle.to_radians() + sweep * i as f64 / segments as f64;
+                Point::new(
+                    x + (radius as f64 * angle.cos()).round() as i32,
+                    y + (radius as f64 * angle.sin()).round() as i32,
+                )
+            })
+            .collect();
+        self.polyline(&points, color);
+    }
+
+    /// Fill the inside of a polygon, a point inside is filled if a line from it crosses
+    /// the outline an odd number of times.
+    pub fn fill_polygon(&mut self, points: &[Point], color: Color) {
+        let shape: Vec<(f64, f64)> = points.iter().map(|point| (point.x as f64 + 0.5, point.y as f64 + 0.5)).collect();
+        self.fill_shape(&shape, color);
+
+        if self.antialiasing && points.len() > 1 {
+            for i in 0..points.len() {
+                let (start, end) = (points[i], points[(i + 1) % points.len()]);
+                self.renderer.wu_line(start.x, start.y, end.x, end.y, color);
+            }
+        }
+    }
+
+    /// Fill a rect with a gradient from the start color at the start point to the end
+    /// color at the end point.
+    pub fn linear_gradient(&mut self, rect: Rect, start: Point, end: Point, start_color: Color, end_color: Color) {
+        self.renderer.linear_gradient(
+            rect.x, rect.y, rect.width, rect.height,
+            start.x, start.y, end.x, end.y,
+            start_color, end_color,
+        );
+    }
+
+    /// Draw a line of text, the top left of its first character is at the given point.
+    pub fn text(&mut self, x: i32, y: i32, text: &str, color: Color) {
+        let mut x = x;
+        for c in text.chars() {
+            self.renderer.char(x, y, c, color);
+            x += 8;
+        }
+    }
+
+    /// Fill a polygon with floating point corners, pixels are inside if their center is.
+    fn fill_shape(&mut self, points: &[(f64, f64)], color: Color) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let min_y = points.iter().map(|point| point.1).fold(f64::INFINITY, f64::min).floor() as i32;
+        let max_y = points.iter().map(|point| point.1).fold(f64::NEG_INFINITY, f64::max).ceil() as i32;
+        let mut crossings = vec![];
+
+        for y in min_y.max(0)..max_y.min(self.renderer.height() as i32) {
+            let center_y = y as f64 + 0.5;
+            crossings.clear();
+            for i in 0..points.len() {
+                let (x0, y0) = points[i];
+                let (x1, y1) = points[(i + 1) % points.len()];
+                if (y0 <= center_y && y1 > center_y) || (y1 <= center_y && y0 > center_y) {
+                    crossings.push(x0 + (center_y - y0) / (y1 - y0) * (x1 - x0));
+                }
+            }
+            crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            for span in crossings.chunks(2) {
+                if span.len() == 2 {
+                    let start = (span[0] - 0.5).ceil() as i32;
+                    let end = (span[1] - 0.5).ceil() as i32;
+                    if end > start {
+                        self.renderer.rect(start, y, (end - start) as u32, 1, color);
+                    }
+                }
+            }
+        }
+    }
+
+    fn fill_disc(&mut self, x: f64, y: f64, radius: f64, color: Color) {
+        self.fill_ring(x, y, 0.0, radius, color);
+    }
+
+    /// Fill the pixels whose centers are between the inner and the outer radius.
+    fn fill_ring(&mut self, x: f64, y: f64, inner: f64, outer: f64, color: Color) {
+        let span = |renderer: &mut dyn Renderer, row: i32, from: f64, to: f64| {
+            let start = (from - 0.5).ceil() as i32;
+            let end = (to - 0.5).ceil() as i32;
+            if end > start {
+                renderer.rect(start, row, (end - start) as u32, 1, color);
+            }
+        };
+
+        for row in (y - outer).floor() as i32..(y + outer).ceil() as i32 {
+            let dy = row as f64 + 0.5 - y;
+            if dy.abs() >= outer {
+                continue;
+            }
+
+            let outer_x = (outer * outer - dy * dy).sqrt();
+            if dy.abs() < inner {
+                let inner_x = (inner * inner - dy * dy).sqrt();
+                span(self.renderer, row, x - outer_x, x - inner_x);
+                span(self.renderer, row, x + inner_x, x + outer_x);
+            } else {
+                span(self.renderer, row, x - outer_x, x + outer_x);
+            }
+        }
+    }
+}
+
+/// A widget that keeps the pixels that are drawn on it by a `Painter`. The draw callback
+/// is called after the canvas was resized or invalidated, `paint` draws on the current
+/// pixels, for example in a click callback.
+pub struct Canvas {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub image: RefCell<orbimage::Image>,
+    pub background: Cell<Color>,
+    pub stroke_width: Cell<u32>,
+    pub antialiasing: Cell<bool>,
+    /// Set if the draw callback has to be called before the next draw.
+    dirty: Cell<bool>,
+    draw_callback: RefCell<Option<Arc<dyn Fn(&mut Painter)>>>,
+    click_callback: RefCell<Option<Arc<dyn Fn(&Canvas, Point)>>>,
+}
+
+impl Canvas {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Canvas {
+            rect: Cell::new(Rect::default()),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            image: RefCell::new(orbimage::Image::new(0, 0)),
+            background: Cell::new(Color::rgba(0, 0, 0, 0)),
+            stroke_width: Cell::new(1),
+            antialiasing: Cell::new(false),
+            dirty: Cell::new(true),
+            draw_callback: RefCell::new(None),
+            click_callback: RefCell::new(None),
+        })
+    }
+
+    /// Set the color the pixels are cleared with before the draw callback is called.
+    pub fn background(&self, color: Color) -> &Self {
+        self.background.set(color);
+        self.invalidate();
+        self
+    }
+
+    /// Set the stroke width the painters of the canvas start with.
+    pub fn stroke_width(&self, stroke_width: u32) -> &Self {
+        self.stroke_width.set(stroke_width);
+        self
+    }
+
+    /// Set if the painters of the canvas start with antialiasing.
+    pub fn antialiasing(&self, antialiasing: bool) -> &Self {
+        self.antialiasing.set(antialiasing);
+        self
+    }
+
+    /// Called to draw the content of the canvas after it was resized or invalidated.
+    /// The pixels are cleared with the background color before.
+    pub fn on_draw<F: Fn(&mut Painter) + 'static>(&self, func: F) -> &Self {
+        *self.draw_callback.borrow_mut() = Some(Arc::new(func));
+        self.invalidate();
+        self
+    }
+
+    /// Call the draw callback before the canvas is drawn the next time.
+    pub fn invalidate(&self) {
+        self.dirty.set(true);
+    }
+
+    /// Draw on the current pixels of the canvas. They are kept until the canvas is
+    /// resized or invalidated.
+    pub fn paint<F: FnOnce(&mut Painter)>(&self, func: F) {
+        self.update_image();
+        let mut image = self.image.borrow_mut();
+        let mut painter = self.painter(&mut *image);
+        func(&mut painter);
+    }
+
+    fn painter<'a>(&self, renderer: &'a mut dyn Renderer) -> Painter<'a> {
+        let mut painter = Painter::new(renderer);
+        painter.stroke_width(self.stroke_width.get()).antialiasing(self.antialiasing.get());
+        painter
+    }
+
+    /// Give the pixels the size of the rect and clear them and call the draw callback if
+    /// the size changed or the canvas was invalidated.
+    fn update_image(&self) {
+        let rect = self.rect.get();
+        let mut image = self.image.borrow_mut();
+        if image.width() != rect.width || image.height() != rect.height {
+            *image = orbimage::Image::from_color(rect.width, rect.height, self.background.get());
+            self.dirty.set(true);
+        }
+
+        if self.dirty.get() {
+            self.dirty.set(false);
+            image.set(self.background.get());
+
+            let draw_callback = self.draw_callback.borrow().clone();
+            if let Some(draw_callback) = draw_callback {
+                let mut painter = self.painter(&mut *image);
+                draw_callback(&mut painter);
+            }
+        }
+    }
+}
+
+impl Click for Canvas {
+    fn emit_click(&self, point: Point) {
+        if let Some(ref click_callback) = *self.click_callback.borrow() {
+            click_callback(self, point);
+        }
+    }
+
+    fn on_click<T: Fn(&Self, Point) + 'static>(&self, func: T) -> &Self {
+        *self.click_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+}
+
+impl Place for Canvas {}
+
+impl Widget for Canvas {
+    fn name(&self) -> &str {
+        "Canvas"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, _focused: bool, _theme: &Theme) {
+        self.update_image();
+
+        let rect = self.rect.get();
+        let image = self.image.borrow();
+        draw_image(renderer, rect.x, rect.y, image.width(), image.height(), image.data(), rect);
+    }
+
+    fn event(&self, event: Event, focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                let rect = self.rect.get();
+                if rect.contains(point) {
+                    if left_button {
+                        let click_point: Point = point - rect.point();
+                        self.emit_click(click_point);
+                        *redraw = true;
+                    }
+                    *caught = true;
+                }
+            }
+            _ => (),
+        }
+
+        focused
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
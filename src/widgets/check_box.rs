@@ -0,0 +1,254 @@
+use orbclient::Renderer;
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+use orbclient;
+
+use cell::{CheckSet, CloneCell};
+use draw::draw_box;
+use event::Event;
+use point::Point;
+use rect::Rect;
+use thickness::Thickness;
+use theme::{Selector, Theme};
+use traits::{Click, Place, Style, Text};
+use widgets::{HorizontalPlacement, VerticalPlacement, Widget};
+
+/// A box that could be checked and unchecked by clicking it or by pressing space.
+/// A tristate check box also cycles through the indeterminate state.
+pub struct CheckBox {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub selector: CloneCell<Selector>,
+    pub text: CloneCell<String>,
+    pub text_offset: Cell<Point>,
+    pub checked: CloneCell<bool>,
+    pub indeterminate: CloneCell<bool>,
+    pub tristate: Cell<bool>,
+    click_callback: RefCell<Option<Arc<dyn Fn(&CheckBox, Point)>>>,
+    hover: Cell<bool>,
+    pressed: Cell<bool>,
+}
+
+impl CheckBox {
+    pub fn new() -> Arc<Self> {
+        Arc::new(CheckBox {
+            rect: Cell::new(Rect::new(0, 0, 16, 16)),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector: CloneCell::new(Selector::new(Some("check-box"))),
+            text: CloneCell::new(String::new()),
+            text_offset: Cell::new(Point::new(8, 0)),
+            checked: CloneCell::new(false),
+            indeterminate: CloneCell::new(false),
+            tristate: Cell::new(false),
+            click_callback: RefCell::new(None),
+            hover: Cell::new(false),
+            pressed: Cell::new(false),
+        })
+    }
+
+    pub fn checked(&self, checked: bool) -> &Self {
+        self.indeterminate.set(false);
+        self.checked.set(checked);
+        self
+    }
+
+    /// Allow the indeterminate state when toggling the check box.
+    pub fn tristate(&self, tristate: bool) -> &Self {
+        self.tristate.set(tristate);
+        self
+    }
+
+    /// Switch to the next state: unchecked, checked and for tristate check boxes indeterminate.
+    pub fn toggle(&self) {
+        if self.indeterminate.get() {
+            self.checked(false);
+        } else if self.checked.get() && self.tristate.get() {
+            self.checked.set(false);
+            self.indeterminate.set(true);
+        } else {
+            self.checked(!self.checked.get());
+        }
+    }
+
+    fn adjust_size(&self) {
+        let text_offset = self.text_offset.get();
+        let text_width = self.text.borrow().len() as u32 * 8;
+        self.size(
+            16 + if text_width > 0 { text_offset.x as u32 + text_width } else { 0 },
+            16 + 2 * text_offset.y as u32,
+        );
+    }
+}
+
+impl Click for CheckBox {
+    fn emit_click(&self, point: Point) {
+        if let Some(ref click_callback) = *self.click_callback.borrow() {
+            click_callback(self, point);
+        }
+    }
+
+    fn on_click<T: Fn(&Self, Point) + 'static>(&self, func: T) -> &Self {
+        *self.click_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+}
+
+impl Place for CheckBox {}
+
+impl Text for CheckBox {
+    fn text<S: Into<String>>(&self, text: S) -> &Self {
+        self.text.set(text.into());
+        self.adjust_size();
+        self
+    }
+
+    fn text_offset(&self, x: i32, y: i32) -> &Self {
+        self.text_offset.set(Point::new(x, y));
+        self.adjust_size();
+        self
+    }
+}
+
+impl Style for CheckBox {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.selector
+    }
+}
+
+impl Widget for CheckBox {
+    fn name(&self) -> &str {
+        "CheckBox"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn update(&self) {
+        let mut selector = self.selector.get()
+            .without_pseudo_class("checked")
+            .without_pseudo_class("indeterminate")
+            .without_pseudo_class("hover");
+
+        if self.indeterminate.get() {
+            selector = selector.with_pseudo_class("indeterminate");
+        } else if self.checked.get() {
+            selector = selector.with_pseudo_class("checked");
+        }
+
+        if self.hover.get() {
+            selector = selector.with_pseudo_class("hover");
+        }
+
+        self.selector.set(selector);
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+        let rect = self.rect.get();
+        let mut selector = self.selector.get();
+
+        if focused {
+            selector = selector.with_pseudo_class("focus");
+        }
+
+        let box_rect = Rect::new(rect.x, rect.y + rect.height as i32 / 2 - 8, 16, 16);
+        draw_box(renderer, box_rect, theme, &selector);
+
+        //TODO: set this selector as the child of self.selector
+        let mark_selector = Selector::new(Some("check-box-mark"));
+        if self.indeterminate.get() {
+            draw_box(renderer, Rect::new(box_rect.x + 4, box_rect.y + 7, 8, 2), theme, &mark_selector);
+        } else if self.checked.get() {
+            draw_box(renderer, Rect::new(box_rect.x + 4, box_rect.y + 4, 8, 8), theme, &mark_selector);
+        }
+
+        let mut x = box_rect.x + 16 + self.text_offset.get().x;
+        for c in self.text.borrow().chars() {
+            if x + 8 <= rect.x + rect.width as i32 {
+                renderer.char(x, box_rect.y, c, theme.color("color", &selector));
+            }
+            x += 8;
+        }
+    }
+
+    fn event(&self, event: Event, mut focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                let mut click = false;
+
+                let rect = self.rect.get();
+                if rect.contains(point) {
+                    if self.hover.check_set(true) {
+                        *redraw = true;
+                    }
+
+                    if left_button {
+                        if self.pressed.check_set(true) {
+                            *redraw = true;
+                        }
+                    } else {
+                        if self.pressed.check_set(false) {
+                            click = true;
+                            *redraw = true;
+                        }
+                    }
+
+                    *caught = true;
+                } else {
+                    if self.hover.check_set(false) {
+                        *redraw = true;
+                    }
+
+                    if !left_button {
+                        if self.pressed.check_set(false) {
+                            *redraw = true;
+                        }
+                    }
+                }
+
+                if click {
+                    focused = true;
+                    self.toggle();
+                    let click_point: Point = point - rect.point();
+                    self.emit_click(click_point);
+                }
+            }
+            Event::KeyPressed(key_event) if focused => if key_event.scancode == orbclient::K_SPACE {
+                self.toggle();
+                self.emit_click(Point::new(0, 0));
+                *redraw = true;
+            },
+            _ => (),
+        }
+
+        focused
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
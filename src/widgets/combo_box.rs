@@ -1,11 +1,13 @@
 use orbclient::Renderer;
 use std::cell::{Cell, RefCell};
+use std::cmp::{max, min};
 use std::sync::Arc;
 use orbimage;
 use orbclient;
 
 use cell::{CheckSet, CloneCell};
-use widgets::{Widget, VerticalPlacement, HorizontalPlacement};
+use collection::{CollectionChange, ObservableCollection};
+use widgets::{TextBox, Widget, VerticalPlacement, HorizontalPlacement};
 use primitives::Image;
 use draw::draw_box;
 use event::Event;
@@ -13,11 +15,41 @@ use rect::Rect;
 use point::Point;
 use thickness::Thickness;
 use theme::{Selector, Theme};
-use traits::{Place, Style, Text};
+use traits::{Change, Place, Style, Text};
 
 static TOGGLE_ICON: &'static [u8; 703] = include_bytes!("../../res/icon-down-black.png");
 static TOGGLE_ICON_ACTIVE: &'static [u8; 706] = include_bytes!("../../res/icon-down-white.png");
 
+/// Decides which entries the flyout of an editable combo box shows for the typed text.
+#[derive(Clone)]
+pub enum ComboBoxFilter {
+    /// Entries that start with the text, ignoring the case
+    Prefix,
+    /// Entries that contain the text, ignoring the case
+    Substring,
+    /// Entries that contain the characters of the text in the same order, ignoring the case
+    Fuzzy,
+    /// Entries for which the function returns true, it gets the entry text and the typed text
+    Custom(Arc<dyn Fn(&str, &str) -> bool>),
+}
+
+impl ComboBoxFilter {
+    pub fn matches(&self, entry: &str, text: &str) -> bool {
+        let entry_lower = entry.to_lowercase();
+        let text_lower = text.to_lowercase();
+
+        match *self {
+            ComboBoxFilter::Prefix => entry_lower.starts_with(&text_lower),
+            ComboBoxFilter::Substring => entry_lower.contains(&text_lower),
+            ComboBoxFilter::Fuzzy => {
+                let mut entry_chars = entry_lower.chars();
+                text_lower.chars().all(|c| entry_chars.any(|entry_c| entry_c == c))
+            }
+            ComboBoxFilter::Custom(ref func) => func(entry, text),
+        }
+    }
+}
+
 struct Entry {
     pub rect: Cell<Rect>,
     local_position: Cell<Point>,
@@ -30,8 +62,10 @@ struct Entry {
     pub text_offset: Cell<Point>,
     hover: Cell<bool>,
     pressed: Cell<bool>,
-    index: u32,
+    index: Cell<u32>,
     active: Cell<bool>,
+    /// Entries that don't match the filter of an editable combo box are hidden.
+    visible: Cell<bool>,
 }
 
 impl Entry {
@@ -48,8 +82,9 @@ impl Entry {
             text_offset: Cell::new(Point::default()),
             hover: Cell::new(false),
             pressed: Cell::new(false),
-            index,
+            index: Cell::new(index),
             active: Cell::new(false),
+            visible: Cell::new(true),
         })
     }
 }
@@ -177,6 +212,98 @@ impl Widget for Entry {
     }
 }
 
+/// The list of entries that is shown below an activated combo box.
+struct Flyout {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    entries: RefCell<Vec<Arc<Entry>>>,
+    select_callback: RefCell<Option<Arc<dyn Fn(u32)>>>,
+}
+
+impl Flyout {
+    fn new() -> Arc<Self> {
+        Arc::new(Flyout {
+            rect: Cell::new(Rect::default()),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            entries: RefCell::new(vec![]),
+            select_callback: RefCell::new(None),
+        })
+    }
+}
+
+impl Widget for Flyout {
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+        //TODO: set this selector as the child of the combo box selector
+        let selector = Selector::new(Some("combo-box-flyout"));
+        draw_box(renderer, self.rect.get(), theme, &selector);
+
+        for entry in self.entries.borrow().iter().filter(|entry| entry.visible.get()) {
+            entry.draw(renderer, focused, theme);
+        }
+    }
+
+    fn event(&self, event: Event, focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        let entries = self.entries.borrow().clone();
+        for entry in entries.iter().filter(|entry| entry.visible.get()) {
+            if entry.event(event, focused, redraw, caught) {
+                let select_callback = self.select_callback.borrow().clone();
+                if let Some(select_callback) = select_callback {
+                    select_callback(entry.index.get());
+                }
+            }
+
+            if *caught {
+                break;
+            }
+        }
+
+        // the flyout covers the widgets below it
+        if let Event::Mouse { point, .. } = event {
+            if self.rect.get().contains(point) {
+                *caught = true;
+            }
+        }
+
+        focused
+    }
+
+    fn name(&self) -> &str {
+        "ComboBoxFlyout"
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
+
 pub struct ComboBox {
     pub rect: Cell<Rect>,
     children: RefCell<Vec<Arc<dyn Widget>>>,
@@ -189,11 +316,19 @@ pub struct ComboBox {
     activated: Cell<bool>,
     pub offset: Cell<Point>,
     selected: Cell<Option<u32>>,
-    entries: RefCell<Vec<Arc<Entry>>>,
+    flyout: Arc<Flyout>,
     text: CloneCell<String>,
-    flyout_height: Cell<u32>,
+    /// Edits the text of an editable combo box.
+    text_box: Arc<TextBox>,
+    editable: Cell<bool>,
+    filter: RefCell<ComboBoxFilter>,
+    /// Allows to commit a text of an editable combo box that is not in the list.
+    custom_text: Cell<bool>,
+    change_callback: RefCell<Option<Arc<dyn Fn(&ComboBox, String)>>>,
     toggle_icon: RefCell<Option<Arc<Image>>>,
     toggle_icon_active: RefCell<Option<Arc<Image>>>,
+    /// Removes the change callback of the bound collection
+    unbind: RefCell<Option<Box<dyn Fn()>>>,
 }
 
 impl ComboBox {
@@ -208,7 +343,10 @@ impl ComboBox {
             *toggle_icon_active.borrow_mut() = Some(Image::from_image(icon))
         };
 
-        Arc::new(ComboBox {
+        let text_box = TextBox::new();
+        text_box.selector().set(Selector::new(Some("combo-box-text")));
+
+        let combo_box = Arc::new(ComboBox {
             rect: Cell::new(Rect::new(0, 0, 332, 28)),
             local_position: Cell::new(Point::new(0, 0)),
             vertical_placement: Cell::new(VerticalPlacement::Absolute),
@@ -220,12 +358,62 @@ impl ComboBox {
             activated: Cell::new(false),
             offset: Cell::new(Point::new(4, 4)),
             selected: Cell::new(None),
-            entries: RefCell::new(vec![]),
+            flyout: Flyout::new(),
             text: CloneCell::new(String::new()),
-            flyout_height: Cell::new(0),
+            text_box,
+            editable: Cell::new(false),
+            filter: RefCell::new(ComboBoxFilter::Substring),
+            custom_text: Cell::new(false),
+            change_callback: RefCell::new(None),
             toggle_icon,
             toggle_icon_active,
-        })
+            unbind: RefCell::new(None),
+        });
+
+        let weak_combo_box = Arc::downgrade(&combo_box);
+        *combo_box.flyout.select_callback.borrow_mut() = Some(Arc::new(move |index| {
+            if let Some(combo_box) = weak_combo_box.upgrade() {
+                combo_box.select_entry(index);
+            }
+        }));
+
+        // typing narrows the entries of the flyout
+        let weak_combo_box = Arc::downgrade(&combo_box);
+        combo_box.text_box.on_change(move |_text_box: &TextBox, text: String| {
+            if let Some(combo_box) = weak_combo_box.upgrade() {
+                combo_box.apply_filter(&text);
+                combo_box.activated.set(true);
+            }
+        });
+
+        combo_box
+    }
+
+    /// An editable combo box has a text box in its header. Typing narrows the entries
+    /// of the flyout with the filter, Up and Down highlight an entry and Enter selects it.
+    pub fn editable(&self, editable: bool) -> &Self {
+        self.editable.set(editable);
+        self.text_box.text(self.text.get());
+        self.arrange();
+        self
+    }
+
+    /// Set the filter of an editable combo box, the default is `ComboBoxFilter::Substring`.
+    pub fn filter(&self, filter: ComboBoxFilter) -> &Self {
+        *self.filter.borrow_mut() = filter;
+        self
+    }
+
+    /// Allow to commit a text of an editable combo box with Enter that matches no entry.
+    /// Then `selected` returns -1 and `text` returns the typed text.
+    pub fn custom_text(&self, custom_text: bool) -> &Self {
+        self.custom_text.set(custom_text);
+        self
+    }
+
+    /// Get the text of the selected entry or the committed custom text.
+    pub fn text(&self) -> String {
+        self.text.get()
     }
 
     pub fn selected(&self) -> i32 {
@@ -237,37 +425,331 @@ impl ComboBox {
     }
 
     pub fn push(&self, text: &str) {
-        let rect = self.rect().get();
-        let entry = Entry::new(text, self.entries.borrow().len() as u32);
-        entry.rect.set(Rect::new(
-            rect.x + 1,
-            rect.y + rect.height as i32 * (self.entries.borrow().len() as i32 + 1),
-            rect.width - 2,
-            rect.height,
-        ));
+        let len = self.len();
+        self.insert(len, text);
+    }
+
+    pub fn pop(&self) -> String {
+        match self.len() {
+            0 => String::new(),
+            len => self.remove(len - 1).unwrap_or_default(),
+        }
+    }
+
+    /// Insert an entry at the index. Panics if the index is greater than the count of entries.
+    /// The first entry that is added gets selected.
+    pub fn insert(&self, index: usize, text: &str) {
+        let entry = Entry::new(text, index as u32);
         entry.text_offset(self.offset.get().x, self.offset.get().y);
-        self.flyout_height
-            .set(self.flyout_height.get() + rect.height);
+        self.flyout.entries.borrow_mut().insert(index, entry);
 
-        self.entries.borrow_mut().push(entry);
+        let selected = match self.selected.get() {
+            Some(i) if i >= index as u32 => Some(i + 1),
+            Some(i) => Some(i),
+            None => Some(0),
+        };
+        self.entries_changed(selected);
+    }
 
-        if self.entries.borrow().len() == 1 {
-            self.change_selection(0);
+    /// Remove the entry at the index and return its text. If it was selected, its
+    /// neighbour gets selected.
+    pub fn remove(&self, index: usize) -> Option<String> {
+        if index >= self.len() {
+            return None;
         }
+
+        let entry = self.flyout.entries.borrow_mut().remove(index);
+        let len = self.len() as u32;
+        let index = index as u32;
+
+        let selected = match self.selected.get() {
+            _ if len == 0 => None,
+            Some(i) if i > index || i == len => Some(i - 1),
+            selected => selected,
+        };
+        self.entries_changed(selected);
+
+        Some(entry.text.get())
     }
 
-    pub fn pop(&self) -> String {
-        if let Some(entry) = self.entries.borrow_mut().pop() {
-            self.change_selection(0);
-            return entry.text.get();
+    /// Move the entry at `from` to the index `to`, the other entries keep their order.
+    pub fn move_entry(&self, from: usize, to: usize) {
+        {
+            let mut entries = self.flyout.entries.borrow_mut();
+            if from >= entries.len() || to >= entries.len() || from == to {
+                return;
+            }
+
+            let entry = entries.remove(from);
+            entries.insert(to, entry);
+        }
+
+        let (from, to) = (from as u32, to as u32);
+        let selected = self.selected.get().map(|i| if i == from {
+            to
+        } else if from < to && i > from && i <= to {
+            i - 1
+        } else if to < from && i >= to && i < from {
+            i + 1
+        } else {
+            i
+        });
+        self.entries_changed(selected);
+    }
+
+    pub fn clear(&self) {
+        self.flyout.entries.borrow_mut().clear();
+        self.entries_changed(None);
+    }
+
+    pub fn len(&self) -> usize {
+        self.flyout.entries.borrow().len()
+    }
+
+    /// Keep the entries in sync with the items of the collection, `text` returns the
+    /// text of an item. The current entries are replaced by the entries of the items.
+    pub fn bind<T, F>(self: &Arc<Self>, items: &ObservableCollection<T>, text: F)
+    where
+        T: Clone,
+        F: Fn(&T) -> String + 'static,
+    {
+        self.unbind();
+        self.clear();
+        for item in items.borrow().iter() {
+            self.push(&text(item));
+        }
+
+        let combo_box = Arc::downgrade(self);
+        let subscription = items.subscribe(move |items, change| {
+            if let Some(combo_box) = combo_box.upgrade() {
+                match change {
+                    CollectionChange::Insert(index) => {
+                        let item_text = text(&items.borrow()[index]);
+                        combo_box.insert(index, &item_text);
+                    }
+                    CollectionChange::Remove(index) => {
+                        combo_box.remove(index);
+                    }
+                    CollectionChange::Move { from, to } => combo_box.move_entry(from, to),
+                    CollectionChange::Reset => {
+                        let texts: Vec<String> = items.borrow().iter().map(|item| text(item)).collect();
+                        combo_box.clear();
+                        for item_text in texts.iter() {
+                            combo_box.push(item_text);
+                        }
+                    }
+                }
+            }
+        });
+
+        let items = items.clone();
+        *self.unbind.borrow_mut() = Some(Box::new(move || items.unsubscribe(subscription)));
+    }
+
+    /// Stop keeping the entries in sync with the bound collection, the entries are kept.
+    pub fn unbind(&self) {
+        let unbind = self.unbind.borrow_mut().take();
+        if let Some(unbind) = unbind {
+            unbind();
+        }
+    }
+
+    /// Update the indices, the layout and the selection after the entries changed.
+    fn entries_changed(&self, selected: Option<u32>) {
+        for (i, entry) in self.flyout.entries.borrow().iter().enumerate() {
+            entry.index.set(i as u32);
+            entry.active.set(false);
+        }
+        self.arrange();
+
+        self.selected.set(None);
+        match selected {
+            Some(i) => self.change_selection(i),
+            None => self.set_text(String::new()),
+        }
+    }
+
+    fn set_text(&self, text: String) {
+        if self.editable.get() {
+            self.text_box.text(text.clone());
+        }
+        self.text.set(text);
+    }
+
+    /// Show only the entries that match the text, the first match is highlighted.
+    fn apply_filter(&self, text: &str) {
+        let filter = self.filter.borrow().clone();
+        let mut first = true;
+        for entry in self.flyout.entries.borrow().iter() {
+            let visible = text.is_empty() || filter.matches(&entry.text.get(), text);
+            entry.visible.set(visible);
+            entry.hover.set(visible && first && !text.is_empty());
+            first &= !visible;
+        }
+        self.arrange();
+    }
+
+    /// Open the flyout with all entries and the selected entry highlighted.
+    fn open(&self) {
+        self.apply_filter("");
+        if let Some(index) = self.selected.get() {
+            if let Some(entry) = self.flyout.entries.borrow().get(index as usize) {
+                entry.hover.set(true);
+            }
+        }
+        self.activated.set(true);
+    }
+
+    /// Get the index of the highlighted entry.
+    fn highlighted(&self) -> Option<u32> {
+        self.flyout.entries.borrow().iter()
+            .find(|entry| entry.visible.get() && entry.hover.get())
+            .map(|entry| entry.index.get())
+    }
+
+    /// Highlight the next visible entry in the given direction.
+    fn move_highlight(&self, delta: isize) {
+        let entries = self.flyout.entries.borrow();
+        let visible: Vec<&Arc<Entry>> = entries.iter().filter(|entry| entry.visible.get()).collect();
+        if visible.is_empty() {
+            return;
+        }
+
+        let next = match visible.iter().position(|entry| entry.hover.get()) {
+            Some(i) => max(0, min(visible.len() as isize - 1, i as isize + delta)) as usize,
+            None if delta > 0 => 0,
+            None => visible.len() - 1,
+        };
+
+        for (i, entry) in visible.iter().enumerate() {
+            entry.hover.set(i == next);
+        }
+    }
+
+    /// Select an entry by the user, the flyout is closed.
+    fn select_entry(&self, index: u32) {
+        let changed = self.selected.get() != Some(index);
+        self.change_selection(index);
+        self.close_popup();
+
+        if changed {
+            self.emit_change(self.text.get());
+        }
+    }
+
+    /// Commit the text of an editable combo box. Enter selects the highlighted entry, an
+    /// entry with the same text or the typed text if custom texts are allowed.
+    fn commit(&self) {
+        let text = self.text_box.text.get();
+        let index = self.highlighted().or_else(|| {
+            self.flyout.entries.borrow().iter()
+                .find(|entry| entry.text.get() == text)
+                .map(|entry| entry.index.get())
+        });
+
+        match index {
+            Some(index) => self.select_entry(index),
+            None if self.custom_text.get() => {
+                if let Some(index) = self.selected.get() {
+                    if let Some(entry) = self.flyout.entries.borrow().get(index as usize) {
+                        entry.active.set(false);
+                    }
+                }
+                self.selected.set(None);
+                self.close_popup();
+
+                if self.text.get() != text {
+                    self.text.set(text.clone());
+                    self.emit_change(text);
+                }
+            }
+            None => {
+                // the typed text is not allowed, restore the text of the selected entry
+                self.close_popup();
+                self.set_text(self.text.get());
+            }
+        }
+    }
+
+    fn editable_event(&self, event: Event, mut focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                // the toggle opens and closes the flyout, the text area places the caret
+                if self.toggle_rect().contains(point) {
+                    if left_button {
+                        if self.pressed.check_set(true) {
+                            if self.activated.get() {
+                                self.close_popup();
+                            } else {
+                                self.open();
+                            }
+                            focused = true;
+                            *redraw = true;
+                        }
+                    } else {
+                        self.pressed.set(false);
+                    }
+
+                    *caught = true;
+                    return focused;
+                }
+
+                if !left_button {
+                    self.pressed.set(false);
+                }
+
+                if self.text_box.event(event, focused, redraw, caught) {
+                    focused = true;
+                }
+                if self.rect.get().contains(point) {
+                    *caught = true;
+                }
+            }
+            Event::KeyPressed(key_event) if focused => match key_event.scancode {
+                orbclient::K_DOWN => {
+                    if self.activated.get() {
+                        self.move_highlight(1);
+                    } else {
+                        self.open();
+                    }
+                    *redraw = true;
+                }
+                orbclient::K_UP => if self.activated.get() {
+                    self.move_highlight(-1);
+                    *redraw = true;
+                },
+                orbclient::K_ENTER => {
+                    self.commit();
+                    *redraw = true;
+                }
+                _ => {
+                    self.text_box.event(event, focused, redraw, caught);
+                }
+            },
+            _ => {
+                self.text_box.event(event, focused, redraw, caught);
+            }
         }
 
-        String::new()
+        focused
+    }
+
+    fn toggle_rect(&self) -> Rect {
+        let rect = self.rect.get();
+        let offset = self.offset.get();
+        let toggle_size = rect.height - 2 * offset.y as u32;
+
+        Rect::new(
+            rect.x + rect.width as i32 - toggle_size as i32 - offset.y,
+            rect.y + offset.y,
+            toggle_size,
+            toggle_size,
+        )
     }
 
     pub fn change_selection(&self, i: u32) {
         if let Some(index) = self.selected.get() {
-            if let Some(entry) = self.entries.borrow().get(index as usize) {
+            if let Some(entry) = self.flyout.entries.borrow().get(index as usize) {
                 entry.active.set(false)
             }
         }
@@ -275,9 +757,9 @@ impl ComboBox {
         self.selected.set(Some(i));
 
         if let Some(index) = self.selected.get() {
-            if let Some(entry) = self.entries.borrow().get(index as usize) {
+            if let Some(entry) = self.flyout.entries.borrow().get(index as usize) {
                 entry.active.set(true);
-                self.text.set(entry.text.get());
+                self.set_text(entry.text.get());
             }
         }
     }
@@ -288,6 +770,24 @@ impl ComboBox {
     }
 }
 
+impl Change for ComboBox {
+    type Value = String;
+
+    /// Emitted with the text of the entry that was selected by the user, or the custom
+    /// text that was committed.
+    fn emit_change(&self, text: String) {
+        let change_callback = self.change_callback.borrow().clone();
+        if let Some(change_callback) = change_callback {
+            change_callback(self, text);
+        }
+    }
+
+    fn on_change<T: Fn(&Self, String) + 'static>(&self, func: T) -> &Self {
+        *self.change_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+}
+
 impl Style for ComboBox {
     fn selector(&self) -> &CloneCell<Selector> {
         &self.selector
@@ -315,35 +815,43 @@ impl Widget for ComboBox {
         &self.margin
     }
 
-    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+    /// Place the visible entries of the flyout below the combo box and the text box
+    /// left of the toggle.
+    fn arrange(&self) {
         let rect = self.rect.get();
-        let activated = self.activated.get();
-        let offset = self.offset.get();
+        let mut count = 0;
+        for entry in self.flyout.entries.borrow().iter().filter(|entry| entry.visible.get()) {
+            count += 1;
+            entry.rect.set(Rect::new(
+                rect.x + 1,
+                rect.y + rect.height as i32 * count,
+                rect.width - 2,
+                rect.height,
+            ));
+        }
 
-        // draw flyout
-        if activated {
-            //TODO: set this selector as the child of self.selector
-            let selector = Selector::new(Some("combo-box-flyout"));
-
-            let flyout_rect = Rect::new(
-                rect.x,
-                rect.y + rect.height as i32 - 2,
-                rect.width,
-                self.flyout_height.get() + 2,
-            );
-            draw_box(renderer, flyout_rect, theme, &selector);
+        self.flyout.rect.set(Rect::new(
+            rect.x,
+            rect.y + rect.height as i32 - 2,
+            rect.width,
+            rect.height * count as u32 + 2,
+        ));
 
-            // draw entries
-            for entry in self.entries.borrow().iter() {
-                let point = Point::new(entry.rect.get().x, entry.rect.get().y);
+        let toggle_rect = self.toggle_rect();
+        let offset = self.offset.get();
+        self.text_box.rect.set(Rect::new(
+            rect.x + 1,
+            rect.y + 1,
+            max(0, toggle_rect.x - rect.x - 2) as u32,
+            rect.height.saturating_sub(2),
+        ));
+        self.text_box.text_offset(offset.x - 1, rect.height as i32 / 2 - 9);
+    }
 
-                if point.y >= rect.y
-                    && point.y + rect.height as i32 <= flyout_rect.y + flyout_rect.height as i32
-                {
-                    entry.draw(renderer, focused, theme);
-                }
-            }
-        }
+    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+        let rect = self.rect.get();
+        let activated = self.activated.get();
+        let offset = self.offset.get();
 
         // draw the combobox
         let mut selector = self.selector.get();
@@ -366,14 +874,7 @@ impl Widget for ComboBox {
             selector = selector.with_pseudo_class("active");
         }
 
-        let toggle_size = rect.height - 2 * offset.y as u32;
-
-        let toggle_rect = Rect::new(
-            rect.x + rect.width as i32 - toggle_size as i32 - offset.y,
-            rect.y + offset.y,
-            toggle_size,
-            toggle_size,
-        );
+        let toggle_rect = self.toggle_rect();
 
         draw_box(renderer, toggle_rect, theme, &selector);
 
@@ -390,6 +891,11 @@ impl Widget for ComboBox {
             }
         }
 
+        if self.editable.get() {
+            self.text_box.draw(renderer, focused, theme);
+            return;
+        }
+
         // draw selected text
         let mut point = Point::new(rect.x + offset.x, rect.y + rect.height as i32 / 2 - 8);
         for c in self.text.get().chars() {
@@ -406,21 +912,8 @@ impl Widget for ComboBox {
     }
 
     fn event(&self, event: Event, mut focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
-        let mut ignore_event = false;
-        if self.activated.get() {
-            for entry in self.entries.borrow().iter() {
-                if entry.event(event, focused, redraw, caught) {
-                    ignore_event = true;
-                    self.change_selection(entry.index);
-                    if self.activated.check_set(false) {
-                        *redraw = true;
-                    }
-                }
-
-                if *caught {
-                    break;
-                }
-            }
+        if self.editable.get() {
+            return self.editable_event(event, focused, redraw, caught);
         }
 
         match event {
@@ -445,14 +938,12 @@ impl Widget for ComboBox {
 
                     *caught = true;
                 } else {
-                    if !ignore_event {
-                        if left_button {
-                            self.pressed.set(false);
-                        } else {
-                            if !self.pressed.get() {
-                                if self.activated.check_set(false) {
-                                    *redraw = true;
-                                }
+                    if left_button {
+                        self.pressed.set(false);
+                    } else {
+                        if !self.pressed.get() {
+                            if self.activated.check_set(false) {
+                                *redraw = true;
                             }
                         }
                     }
@@ -462,20 +953,24 @@ impl Widget for ComboBox {
                 orbclient::K_UP => match self.selected.get() {
                     None => {
                         self.change_selection(0);
+                        self.emit_change(self.text.get());
                         *redraw = true;
                     }
                     Some(i) => if i > 0 {
                         self.change_selection(i - 1);
+                        self.emit_change(self.text.get());
                         *redraw = true;
                     },
                 },
                 orbclient::K_DOWN => match self.selected.get() {
                     None => {
                         self.change_selection(0);
+                        self.emit_change(self.text.get());
                         *redraw = true;
                     }
-                    Some(i) => if i < self.entries.borrow().len() as u32 - 1 {
+                    Some(i) => if i < self.flyout.entries.borrow().len() as u32 - 1 {
                         self.change_selection(i + 1);
+                        self.emit_change(self.text.get());
                         *redraw = true;
                     },
                 },
@@ -492,6 +987,20 @@ impl Widget for ComboBox {
         focused
     }
 
+    fn popup(&self) -> Option<Arc<dyn Widget>> {
+        let visible = self.flyout.entries.borrow().iter().any(|entry| entry.visible.get());
+        if self.activated.get() && visible {
+            Some(self.flyout.clone())
+        } else {
+            None
+        }
+    }
+
+    fn close_popup(&self) {
+        self.activated.set(false);
+        self.pressed.set(false);
+    }
+
     fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
         &self.children
     }
@@ -0,0 +1,482 @@
+use orbclient::Renderer;
+use std::cell::{Cell, RefCell};
+use std::cmp::{max, Ordering};
+use std::sync::Arc;
+
+use cell::{CheckSet, CloneCell};
+use draw::{draw_box, ClipRenderer};
+use event::Event;
+use point::Point;
+use rect::Rect;
+use thickness::Thickness;
+use theme::{Selector, Theme};
+use traits::{Change, Place, Style};
+use widgets::{HorizontalPlacement, VerticalPlacement, VirtualList, Widget};
+
+const HEADER_HEIGHT: u32 = 24;
+const CELL_PADDING: i32 = 4;
+const RESIZE_BORDER: i32 = 4;
+const MIN_COLUMN_WIDTH: u32 = 16;
+
+struct Column {
+    title: String,
+    width: u32,
+}
+
+/// The text of a cell that rows are sorted by, with its number if it is one.
+struct SortKey {
+    text: String,
+    number: Option<f64>,
+}
+
+impl SortKey {
+    fn new(text: String) -> Self {
+        let number = text.parse::<f64>().ok();
+        SortKey { text, number }
+    }
+}
+
+/// Compare cell texts, numbers are compared by their value and come before other texts.
+fn compare_cells(a: &SortKey, b: &SortKey) -> Ordering {
+    match (a.number, b.number) {
+        (Some(a_number), Some(b_number)) => a_number.total_cmp(&b_number).then_with(|| a.text.cmp(&b.text)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.text.cmp(&b.text),
+    }
+}
+
+/// A row of a data grid, shows the texts of its cells in the columns of the grid.
+struct DataGridRow {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub selector: CloneCell<Selector>,
+    cells: RefCell<Vec<String>>,
+    columns: Arc<RefCell<Vec<Column>>>,
+}
+
+impl DataGridRow {
+    fn new(columns: Arc<RefCell<Vec<Column>>>) -> Arc<Self> {
+        Arc::new(DataGridRow {
+            rect: Cell::new(Rect::default()),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector: CloneCell::new(Selector::new(Some("data-grid-cell"))),
+            cells: RefCell::new(vec![]),
+            columns,
+        })
+    }
+}
+
+impl Style for DataGridRow {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.selector
+    }
+}
+
+impl Widget for DataGridRow {
+    fn name(&self) -> &str {
+        "DataGridRow"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, _focused: bool, theme: &Theme) {
+        let rect = self.rect.get();
+        let color = theme.color("color", &self.selector.get());
+
+        let mut x = rect.x;
+        for (column, text) in self.columns.borrow().iter().zip(self.cells.borrow().iter()) {
+            let cell_rect = Rect::new(x + CELL_PADDING, rect.y, column.width.saturating_sub(2 * CELL_PADDING as u32), rect.height);
+            let mut renderer = ClipRenderer::new(renderer, cell_rect);
+
+            let mut char_x = cell_rect.x;
+            let y = rect.y + (rect.height as i32 - 16) / 2;
+            for c in text.chars() {
+                renderer.char(char_x, y, c, color);
+                char_x += 8;
+            }
+
+            x += column.width as i32;
+        }
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
+
+/// A virtualized table with resizable column headers. Clicking a header sorts the
+/// rows by the column, clicking it again reverses the order.
+pub struct DataGrid {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub selector: CloneCell<Selector>,
+    list: Arc<VirtualList<DataGridRow>>,
+    columns: Arc<RefCell<Vec<Column>>>,
+    cell: RefCell<Option<Arc<dyn Fn(usize, usize) -> String>>>,
+    /// Maps the shown rows to the rows of the source, empty if the grid is not sorted
+    order: Arc<RefCell<Vec<usize>>>,
+    sort: Cell<Option<(usize, bool)>>,
+    /// The sort keys of the rows in the column `sort_key_column`, fetched once from the source
+    sort_keys: RefCell<Vec<SortKey>>,
+    sort_key_column: Cell<Option<usize>>,
+    change_callback: RefCell<Option<Arc<dyn Fn(&DataGrid, usize)>>>,
+    pressed_header: Cell<Option<usize>>,
+    /// The resized column with the mouse position and column width at the start of the drag
+    resizing: Cell<Option<(usize, i32, u32)>>,
+}
+
+impl DataGrid {
+    pub fn new() -> Arc<Self> {
+        let columns = Arc::new(RefCell::new(vec![]));
+        let list = VirtualList::new();
+        list.selector.set(Selector::new(Some("data-grid")));
+
+        let data_grid = Arc::new(DataGrid {
+            rect: Cell::new(Rect::default()),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector: CloneCell::new(Selector::new(Some("data-grid-header"))),
+            list,
+            columns,
+            cell: RefCell::new(None),
+            order: Arc::new(RefCell::new(vec![])),
+            sort: Cell::new(None),
+            sort_keys: RefCell::new(vec![]),
+            sort_key_column: Cell::new(None),
+            change_callback: RefCell::new(None),
+            pressed_header: Cell::new(None),
+            resizing: Cell::new(None),
+        });
+
+        data_grid.source(0, |_, _| String::new());
+        data_grid
+    }
+
+    /// Add a column with the given title and width.
+    pub fn column<S: Into<String>>(&self, title: S, width: u32) -> &Self {
+        self.columns.borrow_mut().push(Column {
+            title: title.into(),
+            width: max(MIN_COLUMN_WIDTH, width),
+        });
+        self.list.refresh();
+        self
+    }
+
+    /// Set the data source. The function returns the text of a cell by row and column.
+    pub fn source<F: Fn(usize, usize) -> String + 'static>(&self, row_count: usize, cell: F) -> &Self {
+        let cell: Arc<dyn Fn(usize, usize) -> String> = Arc::new(cell);
+        *self.cell.borrow_mut() = Some(cell.clone());
+        self.sort_keys.borrow_mut().clear();
+        self.sort_key_column.set(None);
+        let selected = self.selected();
+        self.order.borrow_mut().clear();
+
+        let columns = self.columns.clone();
+        let bind_columns = self.columns.clone();
+        let order = self.order.clone();
+        self.list.source(
+            row_count,
+            move || DataGridRow::new(columns.clone()),
+            move |row: &Arc<DataGridRow>, index: usize| {
+                let source_index = order.borrow().get(index).cloned().unwrap_or(index);
+                let column_count = bind_columns.borrow().len();
+                *row.cells.borrow_mut() = (0..column_count).map(|column| cell(source_index, column)).collect();
+            },
+        );
+
+        self.rows_changed(selected);
+        self
+    }
+
+    /// Set the count of rows, e.g. after rows were appended to the data. A sorted grid is sorted again,
+    /// set the source again if the texts of existing rows changed.
+    pub fn row_count(&self, row_count: usize) -> &Self {
+        let selected = self.selected();
+        self.order.borrow_mut().clear();
+        self.list.item_count(row_count);
+        self.rows_changed(selected);
+        self
+    }
+
+    /// Sort the rows by the texts of the given column.
+    pub fn sort_by(&self, column: usize, ascending: bool) {
+        let selected = self.selected();
+        self.sort_rows(column, ascending, selected);
+    }
+
+    /// Sort the rows again after they changed and select the given source row.
+    fn rows_changed(&self, selected: Option<usize>) {
+        if let Some((column, ascending)) = self.sort.get() {
+            self.sort_rows(column, ascending, selected);
+        } else if let Some(selected) = selected {
+            self.list.select(selected);
+        }
+    }
+
+    fn sort_rows(&self, column: usize, ascending: bool, selected: Option<usize>) {
+        let cell = match *self.cell.borrow() {
+            Some(ref cell) => cell.clone(),
+            None => return,
+        };
+
+        // the keys of the rows that were already fetched for this column are reused
+        let row_count = self.list.len();
+        let order = {
+            let mut keys = self.sort_keys.borrow_mut();
+            if self.sort_key_column.get() != Some(column) {
+                keys.clear();
+                self.sort_key_column.set(Some(column));
+            }
+            keys.truncate(row_count);
+            for row in keys.len()..row_count {
+                keys.push(SortKey::new(cell(row, column)));
+            }
+
+            let mut order: Vec<usize> = (0..row_count).collect();
+            order.sort_by(|&a, &b| {
+                let ordering = compare_cells(&keys[a], &keys[b]);
+                if ascending { ordering } else { ordering.reverse() }
+            });
+            order
+        };
+
+        // keep the selection on the same source row
+        *self.order.borrow_mut() = order;
+        self.sort.set(Some((column, ascending)));
+        if let Some(selected) = selected {
+            if let Some(index) = self.order.borrow().iter().position(|&row| row == selected) {
+                self.list.select(index);
+            }
+        }
+
+        self.list.refresh();
+    }
+
+    /// Get the index of the selected row in the source.
+    pub fn selected(&self) -> Option<usize> {
+        self.list
+            .selected()
+            .map(|index| self.order.borrow().get(index).cloned().unwrap_or(index))
+    }
+
+    fn header_rects(&self) -> Vec<Rect> {
+        let rect = self.rect.get();
+        let mut x = rect.x;
+        self.columns
+            .borrow()
+            .iter()
+            .map(|column| {
+                let header_rect = Rect::new(x, rect.y, column.width, HEADER_HEIGHT);
+                x += column.width as i32;
+                header_rect
+            })
+            .collect()
+    }
+}
+
+impl Change for DataGrid {
+    type Value = usize;
+
+    fn emit_change(&self, row: usize) {
+        if let Some(ref change_callback) = *self.change_callback.borrow() {
+            change_callback(self, row);
+        }
+    }
+
+    fn on_change<T: Fn(&Self, usize) + 'static>(&self, func: T) -> &Self {
+        *self.change_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+}
+
+impl Place for DataGrid {}
+
+impl Style for DataGrid {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.selector
+    }
+}
+
+impl Widget for DataGrid {
+    fn name(&self) -> &str {
+        "DataGrid"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn arrange(&self) {
+        let rect = self.rect.get();
+        self.list.rect.set(Rect::new(
+            rect.x,
+            rect.y + HEADER_HEIGHT as i32,
+            rect.width,
+            rect.height.saturating_sub(HEADER_HEIGHT),
+        ));
+        self.list.arrange();
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+        let rect = self.rect.get();
+        let sort = self.sort.get();
+
+        {
+            let mut renderer = ClipRenderer::new(renderer, Rect::new(rect.x, rect.y, rect.width, HEADER_HEIGHT));
+            draw_box(&mut renderer, Rect::new(rect.x, rect.y, rect.width, HEADER_HEIGHT), theme, &self.selector.get());
+
+            for (i, (column, header_rect)) in self.columns.borrow().iter().zip(self.header_rects()).enumerate() {
+                let mut selector = self.selector.get();
+                if self.pressed_header.get() == Some(i) {
+                    selector = selector.with_pseudo_class("active");
+                }
+
+                draw_box(&mut renderer, header_rect, theme, &selector);
+
+                let color = theme.color("color", &selector);
+                let y = header_rect.y + (HEADER_HEIGHT as i32 - 16) / 2;
+                let mut text_width = header_rect.width as i32 - 2 * CELL_PADDING;
+                if let Some((sort_column, ascending)) = sort {
+                    if sort_column == i {
+                        let indicator_x = header_rect.x + header_rect.width as i32 - CELL_PADDING - 8;
+                        renderer.char(indicator_x, y, if ascending { '^' } else { 'v' }, color);
+                        text_width -= 8;
+                    }
+                }
+
+                let mut text_renderer = ClipRenderer::new(
+                    &mut renderer,
+                    Rect::new(header_rect.x + CELL_PADDING, header_rect.y, max(0, text_width) as u32, HEADER_HEIGHT),
+                );
+                let mut x = header_rect.x + CELL_PADDING;
+                for c in column.title.chars() {
+                    text_renderer.char(x, y, c, color);
+                    x += 8;
+                }
+            }
+        }
+
+        self.list.update();
+        self.list.draw(renderer, focused, theme);
+    }
+
+    fn event(&self, event: Event, mut focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                if let Some((column, start, start_width)) = self.resizing.get() {
+                    if left_button {
+                        let width = max(MIN_COLUMN_WIDTH as i32, start_width as i32 + point.x - start);
+                        if let Some(column) = self.columns.borrow_mut().get_mut(column) {
+                            column.width = width as u32;
+                        }
+                    } else {
+                        self.resizing.set(None);
+                    }
+
+                    *redraw = true;
+                    *caught = true;
+                    return focused;
+                }
+
+                let header_rects = self.header_rects();
+                if let Some(i) = header_rects.iter().position(|rect| rect.contains(point)) {
+                    let header_rect = header_rects[i];
+                    if left_button {
+                        if self.pressed_header.get().is_none() {
+                            let right = header_rect.x + header_rect.width as i32;
+                            if right - point.x <= RESIZE_BORDER {
+                                self.resizing.set(Some((i, point.x, header_rect.width)));
+                            } else {
+                                self.pressed_header.set(Some(i));
+                                *redraw = true;
+                            }
+                        }
+                    } else if let Some(pressed) = self.pressed_header.get() {
+                        // only a click that starts and ends on the same header sorts
+                        self.pressed_header.set(None);
+                        if pressed == i {
+                            let ascending = match self.sort.get() {
+                                Some((column, ascending)) if column == i => !ascending,
+                                _ => true,
+                            };
+                            self.sort_by(i, ascending);
+                        }
+                        *redraw = true;
+                    }
+
+                    *caught = true;
+                    return focused;
+                }
+
+                if !left_button && self.pressed_header.check_set(None) {
+                    *redraw = true;
+                }
+            }
+            _ => (),
+        }
+
+        let selected = self.selected();
+        focused = self.list.event(event, focused, redraw, caught);
+        if let Some(row) = self.selected() {
+            if selected != Some(row) {
+                self.emit_change(row);
+            }
+        }
+
+        focused
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
@@ -9,7 +9,7 @@ use thickness::Thickness;
 use theme::Selector;
 use traits::{Click, Place, Text, Style};
 use widgets::{Widget, VerticalPlacement, HorizontalPlacement};
-use primitives::{TextWidget, Rectangle};
+use primitives::{Rectangle, TextAlignment, TextWidget, TextWrap, VerticalTextAlignment};
 
 const LABEL_SELECTOR: &str = "label";
 
@@ -25,6 +25,7 @@ pub struct Label {
     pub border_radius: Cell<u32>,
     pub text: CloneCell<String>,
     pub text_offset: Cell<Point>,
+    text_widget: Arc<TextWidget>,
     click_callback: RefCell<Option<Arc<dyn Fn(&Label, Point)>>>,
     pressed: Cell<bool>,
 }
@@ -37,12 +38,12 @@ impl Label {
         let text_widget = TextWidget::new();
         text_widget.inner_text().bind(&inner_text);
         text_widget.selector().bind(&selector);
-        text_widget.placement(VerticalPlacement::Center, HorizontalPlacement::Center);
+        text_widget.placement(VerticalPlacement::Stretch, HorizontalPlacement::Stretch);
 
         let background = Rectangle::new();
         background.selector().bind(&selector);
         background.placement(VerticalPlacement::Stretch, HorizontalPlacement::Stretch);
-        background.add(text_widget);
+        background.add(text_widget.clone());
 
         Arc::new(Label {
             rect: Cell::new(Rect::default()),
@@ -56,12 +57,30 @@ impl Label {
             border_radius: Cell::new(0),
             text: inner_text,
             text_offset: Cell::new(Point::default()),
+            text_widget,
             click_callback: RefCell::new(None),
             pressed: Cell::new(false),
         })
     }
 
+    /// Set how text that doesn't fit into the widget is handled. If the text is
+    /// wrapped or truncated the widget keeps its size on text changes.
+    pub fn text_wrap(&self, text_wrap: TextWrap) -> &Self {
+        self.text_widget.text_wrap(text_wrap);
+        self
+    }
+
+    pub fn text_alignment(&self, alignment: TextAlignment, vertical_alignment: VerticalTextAlignment) -> &Self {
+        self.text_widget.text_alignment(alignment, vertical_alignment);
+        self
+    }
+
     fn adjust_size(&self) {
+        match self.text_widget.current_text_wrap() {
+            TextWrap::Wrap | TextWrap::Ellipsis => return,
+            TextWrap::Clip => (),
+        }
+
         self.size(
             self.text.get().len() as u32 * 8 + 2 * self.text_offset.get().x as u32,
             16 + 2 * self.text_offset.get().y as u32,
@@ -93,7 +112,9 @@ impl Text for Label {
 
     fn text_offset(&self, x: i32, y: i32) -> &Self {
         self.text_offset.set(Point::new(x, y));
+        self.text_widget.margin().set(Thickness::new(x, y, x, y));
         self.adjust_size();
+        self.arrange();
         self
     }
 }
@@ -1,21 +1,28 @@
-use orbclient;
+use orbclient::{self, Renderer};
 use std::cell::{Cell, RefCell};
 use std::cmp;
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use cell::{CheckSet, CloneCell};
-use event::Event;
+use collection::{CollectionChange, ObservableCollection};
+use event::{Event, Modifiers};
+use icons::{self, IconPlacement};
 use point::Point;
 use rect::Rect;
 use thickness::Thickness;
-use theme::Selector;
-use traits::{Click, Place, Style};
-use widgets::{HorizontalPlacement, VerticalPlacement, Widget};
+use theme::{Selector, Theme};
+use traits::{Change, Click, Place, Style};
+use widgets::{arrange_child, HorizontalPlacement, VerticalPlacement, Widget};
 use std::ops::Index;
-use primitives::Rectangle;
+use primitives::{Image, Rectangle};
+
+/// The space around the icon of an entry.
+const ICON_PADDING: u32 = 4;
 
 /// An entry in a list
-/// Each entry stores widgets within.
+/// Each entry stores widgets within. If it has an icon, the widgets are placed
+/// in the space next to the icon.
 pub struct Entry {
     pub rect: Cell<Rect>,
     local_position: Cell<Point>,
@@ -25,7 +32,15 @@ pub struct Entry {
     children: RefCell<Vec<Arc<dyn Widget>>>,
     click_callback: RefCell<Option<Arc<dyn Fn(&Entry, Point)>>>,
     highlighted: Cell<bool>,
+    hover: Cell<bool>,
     selector: CloneCell<Selector>,
+    background: Arc<Rectangle>,
+    /// The widgets that were added to the entry
+    content: RefCell<Vec<Arc<dyn Widget>>>,
+    icon: RefCell<Option<Arc<Image>>>,
+    /// The icon that is set by the `icon` property of the theme, with its name.
+    theme_icon: RefCell<Option<(String, Arc<Image>)>>,
+    pub icon_placement: Cell<IconPlacement>,
 }
 
 impl Entry {
@@ -43,17 +58,69 @@ impl Entry {
             vertical_placement: Cell::new(VerticalPlacement::Absolute),
             horizontal_placement: Cell::new(HorizontalPlacement::Stretch),
             margin: Cell::new(Thickness::default()),
-            children: RefCell::new(vec![background]),
+            children: RefCell::new(vec![background.clone()]),
             click_callback: RefCell::new(None),
             highlighted: Cell::new(false),
+            hover: Cell::new(false),
             selector,
+            background,
+            content: RefCell::new(vec![]),
+            icon: RefCell::new(None),
+            theme_icon: RefCell::new(None),
+            icon_placement: Cell::new(IconPlacement::Left),
         })
     }
 
     /// Adds a widget to the entry
     pub fn add<T: Widget>(&self, widget: &Arc<T>) {
-        if let Some(background) = (*self.children().borrow()).get(0) {
-            background.add(widget.clone());
+        self.content.borrow_mut().push(widget.clone());
+        self.update_children();
+    }
+
+    /// Show an icon next to the widgets of the entry, it replaces the icon that is set by the theme.
+    pub fn icon(&self, icon: &Arc<Image>) -> &Self {
+        *self.icon.borrow_mut() = Some(icon.clone());
+        self.update_children();
+        self
+    }
+
+    /// Set where the icon is placed relative to the widgets of the entry.
+    pub fn icon_placement(&self, icon_placement: IconPlacement) -> &Self {
+        self.icon_placement.set(icon_placement);
+        self.update_children();
+        self
+    }
+
+    fn current_icon(&self) -> Option<Arc<Image>> {
+        self.icon.borrow().clone().or_else(|| self.theme_icon.borrow().as_ref().map(|&(_, ref icon)| icon.clone()))
+    }
+
+    fn update_children(&self) {
+        let icon = self.current_icon();
+        let mut children = vec![];
+        if icon.is_none() || self.icon_placement.get() != IconPlacement::IconOnly {
+            children.extend(self.content.borrow().iter().cloned());
+        }
+        if let Some(icon) = icon {
+            children.push(icon);
+        }
+        *self.background.children().borrow_mut() = children;
+        self.arrange();
+    }
+
+    /// Load the icon that is set by the theme if its name changed.
+    fn update_theme_icon(&self, theme: &Theme) {
+        let name = theme.string("icon", &self.selector.get());
+        let changed = match *self.theme_icon.borrow() {
+            Some((ref current, _)) => *current != name,
+            None => !name.is_empty(),
+        };
+
+        if changed {
+            *self.theme_icon.borrow_mut() = icons::get(&name).map(|icon| (name, icon));
+            if self.icon.borrow().is_none() {
+                self.update_children();
+            }
         }
     }
 }
@@ -88,14 +155,53 @@ impl Widget for Entry {
     }
 
     fn update(&self) {
-        let selector = Selector::new(Some("entry")).with_pseudo_class(if self.highlighted.get() {
+        // keep the classes of the selector, themes could set an icon for them
+        let mut selector = self.selector.get();
+        selector.pseudo_classes.clear();
+        let selector = selector.with_pseudo_class(if self.highlighted.get() {
             "active"
+        } else if self.hover.get() {
+            "hover"
         } else {
             "inactive"
         });
 
         self.selector.set(selector);
     }
+
+    fn arrange(&self) {
+        let rect = self.rect.get();
+        self.background.rect().set(rect);
+
+        let icon = self.current_icon();
+        let mut content_rect = rect;
+        if let Some(ref icon) = icon {
+            let icon_rect = icon.rect.get();
+            let (slot, caption_rect) = self.icon_placement.get().layout(
+                rect,
+                icon_rect.width + 2 * ICON_PADDING,
+                icon_rect.height + 2 * ICON_PADDING,
+            );
+            icon.rect().set(Rect::new(
+                slot.x + ICON_PADDING as i32,
+                slot.y + ICON_PADDING as i32,
+                icon_rect.width,
+                icon_rect.height,
+            ));
+            content_rect = caption_rect;
+        }
+
+        // the icon is the last child of the background
+        let children = self.background.children().borrow();
+        let content_count = children.len() - if icon.is_some() { 1 } else { 0 };
+        for child in children.iter().take(content_count) {
+            arrange_child(content_rect, child);
+        }
+    }
+
+    fn draw(&self, _renderer: &mut dyn Renderer, _focused: bool, theme: &Theme) {
+        self.update_theme_icon(theme);
+    }
 }
 
 impl Click for Entry {
@@ -111,6 +217,19 @@ impl Click for Entry {
     }
 }
 
+/// Describes how the entries of a list can be selected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// No entry can be selected
+    None,
+    /// One entry can be selected at a time
+    Single,
+    /// Several entries can be selected, Ctrl+click and Space toggle an entry
+    Multiple,
+    /// Like `Multiple`, Shift+click and Shift+arrow keys select a range of entries
+    Extended,
+}
+
 pub struct List {
     pub rect: Cell<Rect>,
     local_position: Cell<Point>,
@@ -123,7 +242,16 @@ pub struct List {
     current_height: Cell<u32>,
     entries: RefCell<Vec<Arc<Entry>>>,
     pressed: Cell<bool>,
-    selected: Cell<Option<u32>>,
+    pub selection_mode: Cell<SelectionMode>,
+    selection: RefCell<BTreeSet<u32>>,
+    /// The entry a Shift range selection starts at
+    anchor: Cell<Option<u32>>,
+    /// The entry the keyboard navigates from
+    current: Cell<Option<u32>>,
+    hover: Cell<Option<u32>>,
+    change_callback: RefCell<Option<Arc<dyn Fn(&List, Vec<u32>)>>>,
+    /// Removes the change callback of the bound collection
+    unbind: RefCell<Option<Box<dyn Fn()>>>,
 }
 
 impl List {
@@ -146,18 +274,184 @@ impl List {
             current_height: Cell::new(0),
             entries: RefCell::new(vec![]),
             pressed: Cell::new(false),
-            selected: Cell::new(None),
+            selection_mode: Cell::new(SelectionMode::Single),
+            selection: RefCell::new(BTreeSet::new()),
+            anchor: Cell::new(None),
+            current: Cell::new(None),
+            hover: Cell::new(None),
+            change_callback: RefCell::new(None),
+            unbind: RefCell::new(None),
         })
     }
 
     pub fn push(&self, entry: &Arc<Entry>) {
-        let h = entry.rect().get().height;
-        self.entries.borrow_mut().push(entry.clone());
-        self.current_height.set(self.current_height.get() + h);
-        if let Some(background) = (*self.children().borrow()).get(0) {
-            background.add(entry.clone());
+        let len = self.len();
+        self.insert(len, entry);
+    }
+
+    /// Insert an entry at the index. Panics if the index is greater than the count of entries.
+    pub fn insert(&self, index: usize, entry: &Arc<Entry>) {
+        self.entries.borrow_mut().insert(index, entry.clone());
+
+        let index = index as u32;
+        self.entries_changed(|i| Some(if i >= index { i + 1 } else { i }));
+    }
+
+    /// Remove the entry at the index. A change is emitted if the entry was selected.
+    pub fn remove(&self, index: usize) -> Option<Arc<Entry>> {
+        if index >= self.len() {
+            return None;
         }
-        self.arrange();
+
+        let entry = self.entries.borrow_mut().remove(index);
+        entry.highlighted.set(false);
+        entry.hover.set(false);
+
+        let index = index as u32;
+        self.entries_changed(|i| if i == index {
+            None
+        } else if i > index {
+            Some(i - 1)
+        } else {
+            Some(i)
+        });
+
+        Some(entry)
+    }
+
+    /// Move the entry at `from` to the index `to`, the other entries keep their order.
+    pub fn move_entry(&self, from: usize, to: usize) {
+        {
+            let mut entries = self.entries.borrow_mut();
+            if from >= entries.len() || to >= entries.len() || from == to {
+                return;
+            }
+
+            let entry = entries.remove(from);
+            entries.insert(to, entry);
+        }
+
+        let (from, to) = (from as u32, to as u32);
+        self.entries_changed(|i| Some(if i == from {
+            to
+        } else if from < to && i > from && i <= to {
+            i - 1
+        } else if to < from && i >= to && i < from {
+            i + 1
+        } else {
+            i
+        }));
+    }
+
+    /// Remove all entries. A change is emitted if an entry was selected.
+    pub fn clear(&self) {
+        for entry in self.entries.borrow_mut().drain(..) {
+            entry.highlighted.set(false);
+            entry.hover.set(false);
+        }
+
+        self.entries_changed(|_| None);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    /// Keep the entries in sync with the items of the collection, `create` builds the
+    /// entry of an item. The current entries are replaced by the entries of the items,
+    /// a collection that was bound before is unbound.
+    pub fn bind<T, F>(self: &Arc<Self>, items: &ObservableCollection<T>, create: F)
+    where
+        T: Clone,
+        F: Fn(&T) -> Arc<Entry> + 'static,
+    {
+        self.unbind();
+        self.clear();
+        for item in items.borrow().iter() {
+            self.push(&create(item));
+        }
+
+        let list = Arc::downgrade(self);
+        let subscription = items.subscribe(move |items, change| {
+            if let Some(list) = list.upgrade() {
+                match change {
+                    CollectionChange::Insert(index) => {
+                        let entry = create(&items.borrow()[index]);
+                        list.insert(index, &entry);
+                    }
+                    CollectionChange::Remove(index) => {
+                        list.remove(index);
+                    }
+                    CollectionChange::Move { from, to } => list.move_entry(from, to),
+                    CollectionChange::Reset => {
+                        let entries: Vec<Arc<Entry>> = items.borrow().iter().map(|item| create(item)).collect();
+                        list.clear();
+                        for entry in entries.iter() {
+                            list.push(entry);
+                        }
+                    }
+                }
+            }
+        });
+
+        let items = items.clone();
+        *self.unbind.borrow_mut() = Some(Box::new(move || items.unsubscribe(subscription)));
+    }
+
+    /// Stop keeping the entries in sync with the bound collection, the entries are kept.
+    pub fn unbind(&self) {
+        let unbind = self.unbind.borrow_mut().take();
+        if let Some(unbind) = unbind {
+            unbind();
+        }
+    }
+
+    /// Set the selection mode. A selection that does not fit the new mode is cleared.
+    pub fn selection_mode(&self, selection_mode: SelectionMode) -> &Self {
+        self.selection_mode.set(selection_mode);
+        match selection_mode {
+            SelectionMode::None => self.clear_selection(),
+            SelectionMode::Single => if self.selection.borrow().len() > 1 {
+                self.clear_selection();
+            },
+            _ => (),
+        }
+        self
+    }
+
+    /// Get the indices of the selected entries in ascending order.
+    pub fn selected(&self) -> Vec<u32> {
+        self.selection.borrow().iter().cloned().collect()
+    }
+
+    pub fn is_selected(&self, i: u32) -> bool {
+        self.selection.borrow().contains(&i)
+    }
+
+    /// Select the entry with the given index without emitting a change. In single
+    /// selection mode the previously selected entry gets deselected.
+    pub fn select(&self, i: u32) {
+        let mut selection = self.selection.borrow().clone();
+        match self.selection_mode.get() {
+            SelectionMode::None => return,
+            SelectionMode::Single => selection.clear(),
+            _ => (),
+        }
+        selection.insert(i);
+        self.set_selection(selection);
+        self.set_current(i);
+    }
+
+    /// Deselect the entry with the given index without emitting a change.
+    pub fn deselect(&self, i: u32) {
+        let mut selection = self.selection.borrow().clone();
+        selection.remove(&i);
+        self.set_selection(selection);
+    }
+
+    /// Deselect all entries without emitting a change.
+    pub fn clear_selection(&self) {
+        self.set_selection(BTreeSet::new());
     }
 
     // Given absolute coordinates, returns the list entry index
@@ -199,20 +493,62 @@ impl List {
         self.v_scroll.set(set_to);
     }
 
-    fn change_selection(&self, i: u32) {
-        match self.selected.get() {
-            Some(i) => match self.entries.borrow().get(i as usize) {
-                Some(entry) => {
-                    entry.highlighted.set(false);
-                }
-                None => {}
-            },
-            _ => {}
+    /// Replace the selection and update the highlighting of the entries.
+    /// Returns true if the selection changed.
+    fn set_selection(&self, selection: BTreeSet<u32>) -> bool {
+        let len = self.entries.borrow().len() as u32;
+        let selection: BTreeSet<u32> = selection.into_iter().filter(|&i| i < len).collect();
+        if *self.selection.borrow() == selection {
+            return false;
+        }
+
+        for (i, entry) in self.entries.borrow().iter().enumerate() {
+            entry.highlighted.set(selection.contains(&(i as u32)));
         }
+        *self.selection.borrow_mut() = selection;
+        true
+    }
+
+    /// Update the selection and the layout after the entries changed. `map` returns
+    /// the new index of an entry from its previous index, or `None` if it was removed.
+    fn entries_changed<F: Fn(u32) -> Option<u32>>(&self, map: F) {
+        let previous_selection = self.selected();
+        let selection: BTreeSet<u32> = self.selection.borrow().iter().filter_map(|&i| map(i)).collect();
+        // a move changes the selected indices without changing their count
+        let selection_changed = selection.iter().cloned().collect::<Vec<u32>>() != previous_selection;
+
+        self.anchor.set(self.anchor.get().and_then(&map));
+        self.current.set(self.current.get().and_then(&map));
+        self.hover.set(self.hover.get().and_then(&map));
+
+        let mut height = 0;
+        for (i, entry) in self.entries.borrow().iter().enumerate() {
+            entry.highlighted.set(selection.contains(&(i as u32)));
+            height += entry.rect().get().height;
+        }
+        self.current_height.set(height);
+        *self.selection.borrow_mut() = selection;
+
+        if let Some(background) = (*self.children().borrow()).get(0) {
+            *background.children().borrow_mut() = self.entries
+                .borrow()
+                .iter()
+                .map(|entry| entry.clone() as Arc<dyn Widget>)
+                .collect();
+        }
+        self.scroll(0);
+        self.arrange();
+
+        if selection_changed {
+            let selected = self.selected();
+            self.emit_change(selected);
+        }
+    }
 
+    /// Move the keyboard position to the given entry and scroll it into view.
+    fn set_current(&self, i: u32) {
         if let Some(entry) = self.entries.borrow().get(i as usize) {
-            entry.highlighted.set(true);
-            self.selected.set(Some(i));
+            self.current.set(Some(i));
 
             let mut y = 0;
 
@@ -234,6 +570,64 @@ impl List {
             }
         }
     }
+
+    /// Update the selection for a click or a key press on the given entry
+    /// according to the selection mode and the held down modifier keys.
+    fn change_selection(&self, i: u32, modifiers: Modifiers, toggle: bool) {
+        let mode = self.selection_mode.get();
+        let mut selection = self.selection.borrow().clone();
+
+        match mode {
+            SelectionMode::None => (),
+            SelectionMode::Single => {
+                selection.clear();
+                selection.insert(i);
+                self.anchor.set(Some(i));
+            }
+            SelectionMode::Multiple | SelectionMode::Extended => {
+                let anchor = self.anchor.get();
+                if mode == SelectionMode::Extended && modifiers.shift && anchor.is_some() {
+                    let anchor = anchor.unwrap();
+                    if !modifiers.ctrl {
+                        selection.clear();
+                    }
+                    for j in cmp::min(anchor, i)..cmp::max(anchor, i) + 1 {
+                        selection.insert(j);
+                    }
+                } else if toggle {
+                    if !selection.remove(&i) {
+                        selection.insert(i);
+                    }
+                    self.anchor.set(Some(i));
+                } else if !modifiers.ctrl {
+                    selection.clear();
+                    selection.insert(i);
+                    self.anchor.set(Some(i));
+                }
+            }
+        }
+
+        self.set_current(i);
+        if self.set_selection(selection) {
+            let selected = self.selected();
+            self.emit_change(selected);
+        }
+    }
+}
+
+impl Change for List {
+    type Value = Vec<u32>;
+
+    fn emit_change(&self, selected: Vec<u32>) {
+        if let Some(ref change_callback) = *self.change_callback.borrow() {
+            change_callback(self, selected);
+        }
+    }
+
+    fn on_change<T: Fn(&Self, Vec<u32>) + 'static>(&self, func: T) -> &Self {
+        *self.change_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
 }
 
 impl Style for List {
@@ -270,7 +664,7 @@ impl Widget for List {
     fn event(&self, event: Event, focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
         match event {
             Event::Mouse {
-                point, left_button, ..
+                point, left_button, modifiers, ..
             } => {
                 let mut click = false;
 
@@ -296,65 +690,79 @@ impl Widget for List {
                     }
                 }
 
-                if let Some(i) = self.get_entry_index(point) {
+                let index = self.get_entry_index(point);
+                let hover = self.hover.get();
+                if self.hover.check_set(index) {
+                    if let Some(entry) = hover.and_then(|i| self.entries.borrow().get(i as usize).cloned()) {
+                        entry.hover.set(false);
+                    }
+                    if let Some(entry) = index.and_then(|i| self.entries.borrow().get(i as usize).cloned()) {
+                        entry.hover.set(true);
+                    }
+                    *redraw = true;
+                }
+
+                if let Some(i) = index {
                     if click {
+                        self.change_selection(i, modifiers, modifiers.ctrl && !modifiers.shift);
+
                         if let Some(entry) = self.entries.borrow().get(i as usize) {
                             entry.emit_click(point);
                         }
                     }
+                }
+            }
+            Event::KeyPressed(key_event) if focused => {
+                let len = self.entries.borrow().len() as u32;
+                if len == 0 {
+                    return focused;
+                }
 
-                    match self.selected.get() {
-                        None => {
-                            self.change_selection(i);
-                            *redraw = true;
+                let modifiers = key_event.modifiers;
+                let current = self.current.get();
+                let i = match key_event.scancode {
+                    orbclient::K_UP => match current {
+                        None => 0,
+                        Some(i) => if i > 0 { i - 1 } else { i },
+                    },
+                    orbclient::K_DOWN => match current {
+                        None => 0,
+                        Some(i) => if i < len - 1 { i + 1 } else { i },
+                    },
+                    orbclient::K_HOME => 0,
+                    orbclient::K_END => len - 1,
+                    orbclient::K_SPACE => {
+                        if let Some(i) = current {
+                            match self.selection_mode.get() {
+                                SelectionMode::Multiple | SelectionMode::Extended => {
+                                    self.change_selection(i, Modifiers::default(), true);
+                                    *redraw = true;
+                                }
+                                _ => (),
+                            }
                         }
-                        Some(selected) => if selected != i {
-                            self.change_selection(i);
-                            *redraw = true;
-                        },
+                        return focused;
                     }
-                }
-            }
-            Event::KeyPressed(key_event) => match key_event.scancode {
-                orbclient::K_UP => match self.selected.get() {
-                    None => {
-                        self.change_selection(0);
-                        *redraw = true;
+                    orbclient::K_ENTER => {
+                        if let Some(i) = current {
+                            if let Some(entry) = self.entries.borrow().get(i as usize) {
+                                entry.emit_click(Point { x: 0, y: 0 });
+                            }
+                        }
+                        return focused;
                     }
-                    Some(i) => if i > 0 {
-                        self.change_selection(i - 1);
-                        *redraw = true;
-                    },
-                },
-                orbclient::K_DOWN => match self.selected.get() {
-                    None => {
-                        self.change_selection(0);
-                        *redraw = true;
+                    _ => return focused,
+                };
+
+                // Multiple selection only moves the keyboard position, Space selects
+                match self.selection_mode.get() {
+                    SelectionMode::Single | SelectionMode::Extended if !modifiers.ctrl => {
+                        self.change_selection(i, modifiers, false);
                     }
-                    Some(i) => if i < self.entries.borrow().len() as u32 - 1 {
-                        self.change_selection(i + 1);
-                        *redraw = true;
-                    },
-                },
-                orbclient::K_HOME => {
-                    self.change_selection(0);
-                    *redraw = true
+                    _ => self.set_current(i),
                 }
-                orbclient::K_END => {
-                    self.change_selection(self.entries.borrow().len() as u32 - 1);
-                    *redraw = true
-                }
-                orbclient::K_ENTER => match self.selected.get() {
-                    Some(i) => match self.entries.borrow().get(i as usize) {
-                        Some(entry) => {
-                            entry.emit_click(Point { x: 0, y: 0 });
-                        }
-                        None => {}
-                    },
-                    _ => {}
-                },
-                _ => {}
-            },
+                *redraw = true;
+            }
             Event::Scroll { y, .. } => {
                 self.scroll(y * -96);
                 *redraw = true;
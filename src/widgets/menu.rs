@@ -1,18 +1,36 @@
 use orbclient::Renderer;
+use orbclient;
 use std::cell::{Cell, RefCell};
 use std::cmp::max;
-use std::sync::Arc;
+use std::ptr;
+use std::sync::{Arc, Weak};
 
 use cell::{CloneCell, CheckSet};
 use draw::draw_box;
 use event::Event;
+use icons::{self, IconPlacement, ICON_SPACING};
 use point::Point;
+use primitives::Image;
 use rect::Rect;
+use shortcut::KeyChord;
 use thickness::Thickness;
 use theme::{Theme, Selector};
 use traits::{Click, Place, Text, Style};
 use widgets::{Widget, VerticalPlacement, HorizontalPlacement};
 
+const ENTRY_HEIGHT: u32 = 24;
+const SEPARATOR_HEIGHT: u32 = 9;
+const PADDING: i32 = 4;
+/// The column left of the entry text that shows the icon or the check mark.
+const ICON_SIZE: u32 = 16;
+/// The column right of the entry text that shows the arrow of a submenu.
+const ARROW_WIDTH: u32 = 16;
+/// The space between the entry text and the shortcut text.
+const SHORTCUT_SPACING: u32 = 24;
+
+/// A menu that shows its entries in a flyout below its button. The text of a menu and
+/// of its actions could contain a mnemonic, `&File` is opened with Alt+F. An open menu
+/// is navigated with the arrow keys, Enter activates the highlighted entry.
 pub struct Menu {
     pub rect: Cell<Rect>,
     local_position: Cell<Point>,
@@ -23,12 +41,42 @@ pub struct Menu {
     selector: CloneCell<Selector>,
     text: CloneCell<String>,
     text_offset: Cell<Point>,
+    flyout: Arc<Flyout>,
     entries: RefCell<Vec<Arc<dyn Entry>>>,
+    highlighted: Cell<Option<usize>>,
+    entry_pressed: Cell<bool>,
+    /// The menu that shows this menu as a submenu.
+    parent: RefCell<Weak<Menu>>,
+    navigate_callback: RefCell<Option<Arc<dyn Fn(&Menu, isize)>>>,
     click_callback: RefCell<Option<Arc<dyn Fn(&Menu, Point)>>>,
     pressed: Cell<bool>,
     activated: Cell<bool>,
 }
 
+/// The entries that are shown by an activated menu.
+struct Flyout {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    selector: CloneCell<Selector>,
+    menu: RefCell<Weak<Menu>>,
+}
+
+/// Shows menus side by side and switches between them while one of them is open.
+pub struct MenuBar {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    selector: CloneCell<Selector>,
+    menus: RefCell<Vec<Arc<Menu>>>,
+}
+
 pub struct Separator {
     pub rect: Cell<Rect>,
     vertical_placement: Cell<VerticalPlacement>,
@@ -41,11 +89,115 @@ pub struct Separator {
 
 pub trait Entry: Widget {
     fn entry_text(&self) -> String;
+
+    /// Text of the keyboard shortcut shown next to the entry text.
+    fn entry_shortcut_text(&self) -> String {
+        String::new()
+    }
+
+    /// The height of the entry in the flyout.
+    fn entry_height(&self) -> u32 {
+        ENTRY_HEIGHT
+    }
+
+    /// Entries that could not be highlighted and activated, like separators, return false.
+    fn entry_enabled(&self) -> bool {
+        true
+    }
+
+    /// The menu that is opened next to the entry when it is highlighted.
+    fn entry_submenu(&self) -> Option<Arc<Menu>> {
+        None
+    }
+
+    /// Where the icon of the entry is placed relative to its text.
+    fn entry_icon_placement(&self) -> IconPlacement {
+        IconPlacement::Left
+    }
+
+    /// Called when the entry is highlighted by the mouse or the keyboard.
+    fn entry_highlight(&self, _highlighted: bool) {}
+
+    /// Called when the entry is clicked or activated by the keyboard.
+    fn entry_activate(&self) {}
+}
+
+/// Split the text of a menu or an entry into the shown text and the position of its
+/// mnemonic. The mnemonic is the character after a `&`, `&&` shows a single `&`.
+fn mnemonic(text: &str) -> (String, Option<usize>) {
+    let mut shown = String::new();
+    let mut index = None;
+    let mut count = 0;
+
+    let mut chars = text.chars();
+    while let Some(mut c) = chars.next() {
+        if c == '&' {
+            match chars.next() {
+                Some('&') => (),
+                Some(next) => {
+                    if index.is_none() {
+                        index = Some(count);
+                    }
+                    c = next;
+                }
+                None => break,
+            }
+        }
+
+        shown.push(c);
+        count += 1;
+    }
+
+    (shown, index)
+}
+
+/// Check if the character is the mnemonic of the text, ignoring the case.
+fn is_mnemonic(text: &str, character: char) -> bool {
+    let (shown, index) = mnemonic(text);
+    match index.and_then(|index| shown.chars().nth(index)) {
+        Some(c) => c.to_lowercase().eq(character.to_lowercase()),
+        None => false,
+    }
+}
+
+/// Draw a text with an underlined mnemonic, characters that don't fit into the rect are skipped.
+fn draw_mnemonic_text(renderer: &mut dyn Renderer, text: &str, x: i32, y: i32, rect: Rect, color: orbclient::Color) {
+    let (shown, index) = mnemonic(text);
+    let mut x = x;
+    for (i, c) in shown.chars().enumerate() {
+        if x >= rect.x && x + 8 <= rect.x + rect.width as i32 && y + 16 <= rect.y + rect.height as i32 {
+            renderer.char(x, y, c, color);
+            if index == Some(i) {
+                renderer.rect(x, y + 15, 8, 1, color);
+            }
+        }
+        x += 8;
+    }
+}
+
+/// The width an entry needs to show its icon, text, shortcut and submenu arrow.
+fn entry_width(entry: &dyn Entry) -> u32 {
+    let text_width = mnemonic(&entry.entry_text()).0.chars().count() as u32 * 8;
+    let shortcut_width = entry.entry_shortcut_text().chars().count() as u32 * 8;
+
+    let mut width = 2 * PADDING as u32 + ICON_SIZE + PADDING as u32 + ARROW_WIDTH;
+    width += match entry.entry_icon_placement() {
+        IconPlacement::Left => text_width,
+        IconPlacement::Right => text_width + ICON_SPACING + ICON_SIZE,
+        IconPlacement::Top => max(text_width, ICON_SIZE),
+        IconPlacement::IconOnly => ICON_SIZE,
+    };
+    if shortcut_width > 0 {
+        width += SHORTCUT_SPACING + shortcut_width;
+    }
+    width
 }
 
 impl Menu {
     pub fn new<S: Into<String>>(name: S) -> Arc<Self> {
-        Arc::new(Menu {
+        let selector = CloneCell::new(Selector::new(Some("menu")));
+
+        let flyout = Arc::new(Flyout {
             rect: Cell::new(Rect::default()),
             local_position: Cell::new(Point::new(0, 0)),
             vertical_placement: Cell::new(VerticalPlacement::Absolute),
@@ -53,37 +205,282 @@ impl Menu {
             margin: Cell::new(Thickness::default()),
             children: RefCell::new(vec![]),
             selector: CloneCell::new(Selector::new(Some("menu"))),
+            menu: RefCell::new(Weak::new()),
+        });
+        flyout.selector.bind(&selector);
+
+        let menu = Arc::new(Menu {
+            rect: Cell::new(Rect::default()),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector,
             text: CloneCell::new(name.into()),
             text_offset: Cell::new(Point::default()),
+            flyout,
             entries: RefCell::new(Vec::new()),
+            highlighted: Cell::new(None),
+            entry_pressed: Cell::new(false),
+            parent: RefCell::new(Weak::new()),
+            navigate_callback: RefCell::new(None),
             click_callback: RefCell::new(None),
             pressed: Cell::new(false),
             activated: Cell::new(false),
-        })
+        });
+
+        *menu.flyout.menu.borrow_mut() = Arc::downgrade(&menu);
+
+        menu
     }
 
     pub fn add<T: Entry>(&self, new_entry: &Arc<T>) {
-        let mut rect = self.rect.get();
-        let text_width = new_entry.entry_text().len() as u32 * 8;
-        if rect.width < text_width {
-            rect.width = text_width;
+        self.entries.borrow_mut().push(new_entry.clone());
+
+        if self.activated.get() {
+            let flyout_rect = self.flyout.rect.get();
+            self.arrange_flyout(flyout_rect.x, flyout_rect.y);
         }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.activated.get()
+    }
 
-        let mut y = rect.y + rect.height as i32;
+    /// Open the flyout below the menu.
+    pub fn open(&self) {
+        let rect = self.rect.get();
+        self.open_at(Point::new(rect.x - 1, rect.y + rect.height as i32 - 1));
+    }
+
+    /// Open the flyout with its top left corner at a point, e.g. as a context menu.
+    pub fn open_at(&self, point: Point) {
+        self.close_submenus();
+        self.arrange_flyout(point.x, point.y);
+        self.set_highlight(None);
+        self.activated.set(true);
+    }
+
+    /// Highlight the first entry of the open flyout, used when a menu is opened by the keyboard.
+    pub fn highlight_first(&self) {
+        self.set_highlight(None);
+        self.move_highlight(1);
+    }
+
+    /// Close the flyout and all open submenus.
+    pub fn close(&self) {
+        self.close_popup();
+    }
+
+    /// Place the flyout with its top left corner at the given point and stack the entries in it.
+    fn arrange_flyout(&self, x: i32, y: i32) {
+        let entries = self.entries.borrow();
+
+        let mut width = entries.iter().map(|entry| entry_width(&**entry)).max().unwrap_or(0);
+        if self.parent.borrow().upgrade().is_none() {
+            width = max(width, self.rect.get().width);
+        }
+
+        let mut entry_y = y + 1;
+        for entry in entries.iter() {
+            let height = entry.entry_height();
+            entry.rect().set(Rect::new(x + 1, entry_y, width, height));
+            entry_y += height as i32;
+        }
+
+        self.flyout.rect.set(Rect::new(x, y, width + 2, (entry_y + 1 - y) as u32));
+    }
+
+    fn set_highlight(&self, index: Option<usize>) {
+        for (i, entry) in self.entries.borrow().iter().enumerate() {
+            entry.entry_highlight(Some(i) == index);
+        }
+        self.highlighted.set(index);
+    }
+
+    /// Highlight the next enabled entry in the given direction, wrapping around at the ends.
+    fn move_highlight(&self, delta: isize) {
+        let count = self.entries.borrow().len() as isize;
+        let mut index = match self.highlighted.get() {
+            Some(index) => index as isize,
+            None if delta > 0 => -1,
+            None => count,
+        };
+
+        for _ in 0..count {
+            index = (index + delta + count) % count;
+            if self.entries.borrow()[index as usize].entry_enabled() {
+                self.close_submenus();
+                self.set_highlight(Some(index as usize));
+                return;
+            }
+        }
+    }
+
+    /// Get the open submenu of the menu.
+    fn open_submenu(&self) -> Option<Arc<Menu>> {
+        self.entries.borrow().iter()
+            .filter_map(|entry| entry.entry_submenu())
+            .find(|submenu| submenu.is_open())
+    }
+
+    /// Open the submenu of an entry to the right of it.
+    fn show_submenu(&self, index: usize) -> Option<Arc<Menu>> {
+        let entry = self.entries.borrow().get(index).cloned()?;
+        let submenu = entry.entry_submenu()?;
+
+        if !submenu.is_open() {
+            self.close_submenus();
+            *submenu.parent.borrow_mut() = self.flyout.menu.borrow().clone();
+
+            let rect = entry.rect().get();
+            submenu.arrange_flyout(rect.x + rect.width as i32, rect.y - 1);
+            submenu.set_highlight(None);
+            submenu.activated.set(true);
+        }
+
+        Some(submenu)
+    }
+
+    fn close_submenus(&self) {
         for entry in self.entries.borrow().iter() {
-            let mut entry_rect = entry.rect().get();
-            y += entry_rect.height as i32;
-
-            if entry_rect.width < rect.width {
-                entry_rect.width = rect.width;
-                entry.rect().set(entry_rect);
-            } else {
-                rect.width = entry_rect.width;
+            if let Some(submenu) = entry.entry_submenu() {
+                submenu.close_popup();
             }
         }
-        rect.y = y;
-        new_entry.rect().set(rect);
-        self.entries.borrow_mut().push(new_entry.clone());
+    }
+
+    /// Activate an entry. Entries with a submenu open it, other entries close the
+    /// whole menu before they are activated.
+    fn activate(&self, index: usize) {
+        let entry = match self.entries.borrow().get(index) {
+            Some(entry) => entry.clone(),
+            None => return,
+        };
+
+        if !entry.entry_enabled() {
+            return;
+        }
+
+        if let Some(submenu) = self.show_submenu(index) {
+            submenu.move_highlight(1);
+            return;
+        }
+
+        let mut root = self.flyout.menu.borrow().upgrade();
+        while let Some(parent) = root.as_ref().and_then(|menu| menu.parent.borrow().upgrade()) {
+            root = Some(parent);
+        }
+        if let Some(root) = root {
+            root.close_popup();
+        }
+
+        entry.entry_activate();
+    }
+
+    /// Called with -1 or 1 when Left or Right is pressed in the flyout and there is
+    /// no submenu to close or open. Used by `MenuBar` to switch to the next menu.
+    pub fn on_navigate<F: Fn(&Menu, isize) + 'static>(&self, func: F) -> &Self {
+        *self.navigate_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+
+    fn navigate(&self, delta: isize) {
+        let navigate_callback = self.navigate_callback.borrow().clone();
+        if let Some(navigate_callback) = navigate_callback {
+            navigate_callback(self, delta);
+        }
+    }
+
+    fn draw_flyout(&self, renderer: &mut dyn Renderer, theme: &Theme) {
+        draw_box(renderer, self.flyout.rect.get(), theme, &self.flyout.selector.get().with_pseudo_class("active"));
+
+        for entry in self.entries.borrow().iter() {
+            entry.draw(renderer, false, theme);
+        }
+    }
+
+    fn flyout_event(&self, event: Event, redraw: &mut bool, caught: &mut bool) {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                if !self.flyout.rect.get().contains(point) {
+                    if !left_button {
+                        self.entry_pressed.set(false);
+                    }
+
+                    // the entry of an open submenu stays highlighted
+                    if self.open_submenu().is_none() && self.highlighted.get().is_some() {
+                        self.set_highlight(None);
+                        *redraw = true;
+                    }
+                    return;
+                }
+
+                let index = {
+                    let entries = self.entries.borrow();
+                    entries.iter().position(|entry| entry.rect().get().contains(point))
+                        .filter(|&index| entries[index].entry_enabled())
+                };
+
+                if self.highlighted.get() != index {
+                    self.close_submenus();
+                    self.set_highlight(index);
+                    if let Some(index) = index {
+                        self.show_submenu(index);
+                    }
+                    *redraw = true;
+                }
+
+                if left_button {
+                    self.entry_pressed.set(index.is_some());
+                } else if self.entry_pressed.check_set(false) {
+                    if let Some(index) = index {
+                        self.activate(index);
+                        *redraw = true;
+                    }
+                }
+
+                *caught = true;
+            }
+            // Alt is left to the menu bar to switch to another menu by its mnemonic
+            Event::KeyPressed(key_event) if !key_event.modifiers.alt => {
+                match key_event.scancode {
+                    orbclient::K_DOWN => self.move_highlight(1),
+                    orbclient::K_UP => self.move_highlight(-1),
+                    orbclient::K_RIGHT => {
+                        let submenu = self.highlighted.get().and_then(|index| self.show_submenu(index));
+                        match submenu {
+                            Some(submenu) => submenu.move_highlight(1),
+                            None => self.navigate(1),
+                        }
+                    }
+                    orbclient::K_LEFT => {
+                        if self.parent.borrow().upgrade().is_some() {
+                            self.close_popup();
+                        } else {
+                            self.navigate(-1);
+                        }
+                    }
+                    orbclient::K_ENTER | orbclient::K_SPACE => if let Some(index) = self.highlighted.get() {
+                        self.activate(index);
+                    },
+                    _ => if let Some(character) = key_event.character {
+                        let index = self.entries.borrow().iter()
+                            .position(|entry| entry.entry_enabled() && is_mnemonic(&entry.entry_text(), character));
+                        if let Some(index) = index {
+                            self.set_highlight(Some(index));
+                            self.activate(index);
+                        }
+                    },
+                }
+
+                // the open menu gets all keys
+                *redraw = true;
+                *caught = true;
+            }
+            _ => (),
+        }
     }
 }
 
@@ -145,113 +542,287 @@ impl Widget for Menu {
         &self.margin
     }
 
+    /// Measure the entries of the open flyout again, e.g. after a shortcut was set.
+    fn arrange(&self) {
+        if self.activated.get() {
+            let flyout_rect = self.flyout.rect.get();
+            self.arrange_flyout(flyout_rect.x, flyout_rect.y);
+        }
+    }
+
     fn draw(&self, renderer: &mut dyn Renderer, _focused: bool, theme: &Theme) {
         let rect = self.rect.get();
 
-        if self.activated.get() {
-            //TODO: set this selector as the child of self.selector
-            draw_box(renderer, rect, theme, &Selector::new(Some("menu-button")).with_pseudo_class("active"));
+        //TODO: set this selector as the child of self.selector
+        let pseudo_class = if self.activated.get() { "active" } else { "inactive" };
+        draw_box(renderer, rect, theme, &Selector::new(Some("menu-button")).with_pseudo_class(pseudo_class));
+
+        let text_offset = self.text_offset.get();
+        draw_mnemonic_text(
+            renderer,
+            &self.text.get(),
+            rect.x + text_offset.x,
+            rect.y + text_offset.y,
+            rect,
+            theme.color("color", &"button".into()),
+        );
+    }
 
-            let mut max_width = 0;
-            let mut max_height = 0;
+    fn event(&self, event: Event, focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                let rect = self.rect.get();
+                if rect.contains(point) {
+                    if left_button {
+                        if self.pressed.check_set(true) {
+                            if self.activated.get() {
+                                self.close_popup();
+                            } else {
+                                self.open();
+                                self.emit_click(point - rect.point());
+                            }
+                            *redraw = true;
+                        }
+                    } else {
+                        self.pressed.set(false);
+                    }
 
-            for entry in self.entries.borrow().iter() {
-                let r = entry.rect().get();
-                max_width = max(max_width, r.x + r.width as i32 - rect.x);
-                max_height = max(max_height, r.y + r.height as i32 - rect.y - rect.height as i32);
+                    *caught = true;
+                } else if !left_button {
+                    self.pressed.set(false);
+                }
             }
-
-            let entries_rect = Rect::new(
-                rect.x - 1, rect.y + rect.height as i32 - 1,
-                max_width as u32 + 2, max_height as u32 + 2,
-            );
-
-            draw_box(renderer, entries_rect, theme, &self.selector.get().with_pseudo_class("active"));
-        } else {
-            //TODO: set this selector as the child of self.selector
-            draw_box(renderer, rect, theme, &Selector::new(Some("menu-button")).with_pseudo_class("inactive"));
-        }
-
-        let text = self.text.borrow();
-        let mut point = self.text_offset.get();
-        for c in text.chars() {
-            if c == '\n' {
-                point.x = self.text_offset.get().x;
-                point.y += 16;
-            } else {
-                if point.x + 8 <= rect.width as i32 && point.y + 16 <= rect.height as i32 {
-                    renderer.char(point.x + rect.x, point.y + rect.y, c, theme.color("color", &"button".into()));
+            Event::KeyPressed(key_event) if key_event.modifiers.alt => {
+                if let Some(character) = key_event.character {
+                    if is_mnemonic(&self.text.get(), character) {
+                        self.open();
+                        self.highlight_first();
+                        *redraw = true;
+                        *caught = true;
+                    }
                 }
-                point.x += 8;
             }
+            _ => (),
         }
+        focused
+    }
 
+    fn popup(&self) -> Option<Arc<dyn Widget>> {
         if self.activated.get() {
-            for entry in self.entries.borrow().iter() {
-                entry.draw(renderer, _focused, theme);
-            }
+            Some(self.flyout.clone())
+        } else {
+            None
+        }
+    }
+
+    fn close_popup(&self) {
+        self.close_submenus();
+        self.set_highlight(None);
+        self.activated.set(false);
+        self.pressed.set(false);
+        self.entry_pressed.set(false);
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
+
+impl Widget for Flyout {
+    fn name(&self) -> &str {
+        "MenuFlyout"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    /// The entries could have changed since the flyout was opened.
+    fn update(&self) {
+        if let Some(menu) = self.menu.borrow().upgrade() {
+            Widget::arrange(&*menu);
+        }
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, _focused: bool, theme: &Theme) {
+        if let Some(menu) = self.menu.borrow().upgrade() {
+            menu.draw_flyout(renderer, theme);
         }
     }
 
     fn event(&self, event: Event, focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
-        let mut ignore_event = false;
-        if self.activated.get() {
-            for entry in self.entries.borrow().iter() {
-                if entry.event(event, focused, redraw, caught) {
-                    ignore_event = true;
-                    self.pressed.set(true);
-                }
+        let menu = self.menu.borrow().upgrade();
+        if let Some(menu) = menu {
+            menu.flyout_event(event, redraw, caught);
+        }
+        focused
+    }
 
-                if *caught {
-                    break;
-                }
+    /// The open submenu is shown as a popup of the flyout.
+    fn popup(&self) -> Option<Arc<dyn Widget>> {
+        let submenu = self.menu.borrow().upgrade()?.open_submenu()?;
+        Some(submenu.flyout.clone())
+    }
+
+    fn close_popup(&self) {
+        if let Some(menu) = self.menu.borrow().upgrade() {
+            menu.close_submenus();
+        }
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
+
+impl MenuBar {
+    pub fn new() -> Arc<Self> {
+        Arc::new(MenuBar {
+            rect: Cell::new(Rect::default()),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector: CloneCell::new(Selector::new(Some("menu-bar"))),
+            menus: RefCell::new(vec![]),
+        })
+    }
+
+    /// Add a menu to the right of the other menus, it is sized to fit its text.
+    pub fn add(self: &Arc<Self>, menu: &Arc<Menu>) {
+        let weak_bar = Arc::downgrade(self);
+        menu.on_navigate(move |menu, delta| {
+            if let Some(bar) = weak_bar.upgrade() {
+                bar.navigate(menu, delta);
             }
+        });
+
+        self.menus.borrow_mut().push(menu.clone());
+        self.children.borrow_mut().push(menu.clone());
+        self.arrange();
+    }
+
+    /// Close a menu and open the next one in the given direction, wrapping around at the ends.
+    fn navigate(&self, menu: &Menu, delta: isize) {
+        let menus = self.menus.borrow().clone();
+        let count = menus.len() as isize;
+
+        if let Some(index) = menus.iter().position(|other| ptr::eq(&**other, menu)) {
+            let next = &menus[((index as isize + delta + count) % count) as usize];
+            menu.close();
+            next.open();
+            next.highlight_first();
         }
+    }
+}
 
-        match event {
-            Event::Mouse { point, left_button, .. } => {
-                let mut click = false;
+impl Place for MenuBar {}
 
-                let rect = self.rect.get();
-                if rect.contains(point) {
-                    if left_button {
-                        self.pressed.set(!self.pressed.get());
+impl Style for MenuBar {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.selector
+    }
+}
 
-                        if self.activated.check_set(true) {
-                            click = true;
-                            *redraw = true;
-                        }
-                    } else {
-                        if !self.pressed.get() {
-                            if self.activated.check_set(false) {
-                                click = true;
-                                *redraw = true;
-                            }
-                        }
+impl Widget for MenuBar {
+    fn name(&self) -> &str {
+        "MenuBar"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn arrange(&self) {
+        let rect = self.rect.get();
+
+        let mut x = rect.x;
+        for menu in self.menus.borrow().iter() {
+            let width = mnemonic(&menu.text.get()).0.chars().count() as u32 * 8 + 16;
+            menu.rect.set(Rect::new(x, rect.y, width, rect.height));
+            menu.text_offset(8, (rect.height as i32 - 16) / 2);
+            x += width as i32;
+        }
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, _focused: bool, theme: &Theme) {
+        draw_box(renderer, self.rect.get(), theme, &self.selector.get());
+    }
+
+    fn event(&self, event: Event, focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        let menus = self.menus.borrow().clone();
+
+        // while a menu is open, hovering another menu opens that one instead
+        if let Event::Mouse { point, .. } = event {
+            if let Some(open) = menus.iter().position(|menu| menu.is_open()) {
+                let hovered = menus.iter().position(|menu| menu.rect.get().contains(point));
+                if let Some(hovered) = hovered {
+                    if hovered != open {
+                        let pressed = menus[open].pressed.get();
+                        menus[open].close();
+                        menus[hovered].open();
+                        menus[hovered].pressed.set(pressed);
+                        *redraw = true;
                     }
+                }
+            }
+        }
 
-                    *caught = true;
-                } else {
-                    if !ignore_event {
-                        if left_button {
-                            self.pressed.set(false);
-                        } else {
-                            if !self.pressed.get() {
-                                if self.activated.check_set(false) {
-                                    *redraw = true;
-                                }
-                            }
+        for (index, menu) in menus.iter().enumerate() {
+            menu.event(event, false, redraw, caught);
+
+            if *caught {
+                // a mnemonic opened this menu, only one menu is open at a time
+                if let Event::KeyPressed(_) = event {
+                    for (other_index, other) in menus.iter().enumerate() {
+                        if other_index != index {
+                            other.close();
                         }
                     }
                 }
+                break;
+            }
+        }
 
-                if click {
-                    let click_point: Point = point - rect.point();
-                    self.emit_click(click_point);
-                }
+        if let Event::Mouse { point, .. } = event {
+            if self.rect.get().contains(point) {
+                *caught = true;
             }
-            _ => (),
         }
+
         focused
     }
 
@@ -270,9 +841,18 @@ pub struct Action {
     selector: CloneCell<Selector>,
     text: CloneCell<String>,
     text_offset: Cell<Point>,
+    shortcut: Cell<Option<KeyChord>>,
+    icon: RefCell<Option<Arc<Image>>>,
+    /// The icon that is set by the `icon` property of the theme, with its name.
+    theme_icon: RefCell<Option<(String, Arc<Image>)>>,
+    icon_placement: Cell<IconPlacement>,
+    submenu: RefCell<Option<Arc<Menu>>>,
+    checkable: Cell<bool>,
+    /// Radio actions are checked by a click and unchecked by their `ActionGroup`.
+    radio: Cell<bool>,
+    pub checked: CloneCell<bool>,
     click_callback: RefCell<Option<Arc<dyn Fn(&Action, Point)>>>,
-    pressed: Cell<bool>,
-    hover: Cell<bool>,
+    highlighted: Cell<bool>,
 }
 
 impl Action {
@@ -287,11 +867,72 @@ impl Action {
             selector: CloneCell::new(Selector::new(Some("action"))),
             text: CloneCell::new(text.into()),
             text_offset: Cell::new(Point::default()),
+            shortcut: Cell::new(None),
+            icon: RefCell::new(None),
+            theme_icon: RefCell::new(None),
+            icon_placement: Cell::new(IconPlacement::Left),
+            submenu: RefCell::new(None),
+            checkable: Cell::new(false),
+            radio: Cell::new(false),
+            checked: CloneCell::new(false),
             click_callback: RefCell::new(None),
-            pressed: Cell::new(false),
-            hover: Cell::new(false),
+            highlighted: Cell::new(false),
         })
     }
+
+    /// Show the key chord of the shortcut that triggers this action.
+    pub fn shortcut(&self, shortcut: Option<KeyChord>) -> &Self {
+        self.shortcut.set(shortcut);
+        self
+    }
+
+    /// Show an icon next to the text, it replaces the icon that is set by the theme.
+    pub fn icon(&self, icon: &Arc<Image>) -> &Self {
+        *self.icon.borrow_mut() = Some(icon.clone());
+        self
+    }
+
+    /// Set where the icon is placed relative to the text. Left places it in the column
+    /// of the check marks. Must be set before the action is added to a menu.
+    pub fn icon_placement(&self, icon_placement: IconPlacement) -> &Self {
+        self.icon_placement.set(icon_placement);
+        self
+    }
+
+    /// Get the icon of the action, the theme is asked if none was set.
+    fn current_icon(&self, theme: &Theme, selector: &Selector) -> Option<Arc<Image>> {
+        if let Some(ref icon) = *self.icon.borrow() {
+            return Some(icon.clone());
+        }
+
+        let name = theme.string("icon", selector);
+        let changed = match *self.theme_icon.borrow() {
+            Some((ref current, _)) => *current != name,
+            None => !name.is_empty(),
+        };
+        if changed {
+            *self.theme_icon.borrow_mut() = icons::get(&name).map(|icon| (name, icon));
+        }
+
+        self.theme_icon.borrow().as_ref().map(|&(_, ref icon)| icon.clone())
+    }
+
+    /// Open a submenu next to the action when it is highlighted. The action is not
+    /// clicked any more.
+    pub fn submenu(&self, menu: &Arc<Menu>) -> &Self {
+        *self.submenu.borrow_mut() = Some(menu.clone());
+        self
+    }
+
+    /// A checkable action toggles its check mark when it is clicked.
+    pub fn checkable(&self, checkable: bool) -> &Self {
+        self.checkable.set(checkable);
+        self
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.checked.get()
+    }
 }
 
 impl Click for Action {
@@ -353,71 +994,74 @@ impl Widget for Action {
     fn draw(&self, renderer: &mut dyn Renderer, _focused: bool, theme: &Theme) {
         let rect = self.rect.get();
 
-        let pseudo_class = if self.hover.get() { "active" } else { "inactive" };
+        let pseudo_class = if self.highlighted.get() { "active" } else { "inactive" };
         let selector = &self.selector.get().with_pseudo_class(pseudo_class);
 
         draw_box(renderer, rect, theme, selector);
-
-        let text = self.text.borrow();
-        let mut point = self.text_offset.get();
-        for c in text.chars() {
-            if c == '\n' {
-                point.x = self.text_offset.get().x;
-                point.y += 16;
-            } else {
-                if point.x + 8 <= rect.width as i32 && point.y + 16 <= rect.height as i32 {
-                    renderer.char(point.x + rect.x, point.y + rect.y, c, theme.color("color", selector));
-                }
-                point.x += 8;
-            }
+        let color = theme.color("color", selector);
+
+        fn draw_icon(icon: &Arc<Image>, x: i32, y: i32, renderer: &mut dyn Renderer, theme: &Theme) {
+            let icon_rect = icon.rect.get();
+            icon.rect.set(Rect::new(
+                x + (ICON_SIZE as i32 - icon_rect.width as i32) / 2,
+                y + (ICON_SIZE as i32 - icon_rect.height as i32) / 2,
+                icon_rect.width,
+                icon_rect.height,
+            ));
+            icon.draw(renderer, false, theme);
         }
-    }
 
-    fn event(&self, event: Event, _focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
-        match event {
-            Event::Mouse { point, left_button, .. } => {
-                let mut click = false;
-                let rect = self.rect.get();
+        let icon = self.current_icon(theme, selector);
+        let icon_placement = self.icon_placement.get();
 
-                if rect.contains(point) {
-                    if self.hover.check_set(true) {
-                        *redraw = true;
-                    }
-
-                    if left_button {
-                        if self.pressed.check_set(true) {
-                            *redraw = true;
-                        }
-                    } else {
-                        if self.pressed.check_set(false) {
-                            click = true;
-                            self.hover.set(false);
-                            *redraw = true;
-                        }
-                    }
-
-                    *caught = true;
-                } else {
-                    if self.hover.check_set(false) {
-                        *redraw = true;
-                    }
+        // the check mark takes the place of an icon left of the text
+        let icon_x = rect.x + PADDING;
+        let icon_y = rect.y + (rect.height as i32 - ICON_SIZE as i32) / 2;
+        if self.checked.get() {
+            //TODO: set this selector as the child of self.selector
+            let mark_name = if self.radio.get() { "action-radio" } else { "action-check" };
+            let mark_selector = Selector::new(Some(mark_name)).with_pseudo_class(pseudo_class);
+            draw_box(renderer, Rect::new(icon_x + 4, icon_y + 4, 8, 8), theme, &mark_selector);
+        } else if let (Some(icon), IconPlacement::Left) = (icon.as_ref(), icon_placement) {
+            draw_icon(icon, icon_x, icon_y, renderer, theme);
+        }
 
-                    if !left_button {
-                        if self.pressed.check_set(false) {
-                            *redraw = true;
-                        }
-                    }
+        let text_offset = self.text_offset.get();
+        let mut text_x = icon_x + ICON_SIZE as i32 + PADDING + text_offset.x;
+        let mut text_y = rect.y + (rect.height as i32 - 16) / 2 + text_offset.y;
+        let mut show_text = true;
+        if let Some(ref icon) = icon {
+            if icon_placement != IconPlacement::Left {
+                let text_width = mnemonic(&self.text.get()).0.chars().count() as u32 * 8;
+                let (width, height) = icon_placement.size((ICON_SIZE, ICON_SIZE), (text_width, 16));
+                let content_rect = Rect::new(text_x, rect.y + (rect.height as i32 - height as i32) / 2, width, height);
+                let (icon_rect, text_rect) = icon_placement.layout(content_rect, ICON_SIZE, ICON_SIZE);
+                draw_icon(icon, icon_rect.x, icon_rect.y, renderer, theme);
+
+                text_x = text_rect.x;
+                if icon_placement == IconPlacement::Top {
+                    text_y = text_rect.y + text_offset.y;
                 }
+                show_text = icon_placement != IconPlacement::IconOnly;
+            }
+        }
 
-                if click {
-                    let click_point: Point = point - rect.point();
-                    self.emit_click(click_point);
-                }
+        if show_text {
+            draw_mnemonic_text(renderer, &self.text.get(), text_x, text_y, rect, color);
+        }
+        // draw the shortcut right aligned, left of the submenu arrow
+        let shortcut_text = self.entry_shortcut_text();
+        let mut x = rect.x + rect.width as i32 - PADDING - ARROW_WIDTH as i32 - shortcut_text.len() as i32 * 8;
+        for c in shortcut_text.chars() {
+            if x >= rect.x {
+                renderer.char(x, text_y, c, color);
             }
-            _ => (),
+            x += 8;
         }
 
-        false
+        if self.submenu.borrow().is_some() {
+            renderer.char(rect.x + rect.width as i32 - PADDING - 12, text_y, '>', color);
+        }
     }
 
     fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
@@ -429,6 +1073,94 @@ impl Entry for Action {
     fn entry_text(&self) -> String {
         self.text.get()
     }
+
+    fn entry_shortcut_text(&self) -> String {
+        self.shortcut.get().map(|chord| chord.to_string()).unwrap_or_default()
+    }
+
+    fn entry_height(&self) -> u32 {
+        if self.icon_placement.get() == IconPlacement::Top {
+            ENTRY_HEIGHT + ICON_SIZE + ICON_SPACING
+        } else {
+            ENTRY_HEIGHT
+        }
+    }
+
+    fn entry_submenu(&self) -> Option<Arc<Menu>> {
+        self.submenu.borrow().clone()
+    }
+
+    fn entry_icon_placement(&self) -> IconPlacement {
+        self.icon_placement.get()
+    }
+
+    fn entry_highlight(&self, highlighted: bool) {
+        self.highlighted.set(highlighted);
+    }
+
+    fn entry_activate(&self) {
+        if self.radio.get() {
+            self.checked.set(true);
+        } else if self.checkable.get() {
+            self.checked.set(!self.checked.get());
+        }
+
+        self.emit_click(Point::new(0, 0));
+    }
+}
+
+/// Makes the actions that are added to the group radio actions, only one of them
+/// is checked at a time.
+pub struct ActionGroup {
+    actions: Arc<RefCell<Vec<Weak<Action>>>>,
+}
+
+impl ActionGroup {
+    pub fn new() -> Self {
+        ActionGroup {
+            actions: Arc::new(RefCell::new(vec![])),
+        }
+    }
+
+    pub fn add(&self, action: &Arc<Action>) -> &Self {
+        action.checkable.set(true);
+        action.radio.set(true);
+        self.actions.borrow_mut().push(Arc::downgrade(action));
+
+        let actions = self.actions.clone();
+        let checked_action = Arc::downgrade(action);
+        action.checked.on_changed(move |checked: bool| {
+            if !checked {
+                return;
+            }
+
+            for other in actions.borrow().iter() {
+                if other.ptr_eq(&checked_action) {
+                    continue;
+                }
+
+                if let Some(other) = other.upgrade() {
+                    if other.checked.get() {
+                        other.checked.set(false);
+                    }
+                }
+            }
+        });
+
+        // keep the group consistent if the new action is already checked
+        if action.checked.get() {
+            action.checked.set(true);
+        }
+
+        self
+    }
+
+    /// Get the checked action of the group.
+    pub fn checked(&self) -> Option<Arc<Action>> {
+        self.actions.borrow().iter()
+            .filter_map(|action| action.upgrade())
+            .find(|action| action.checked.get())
+    }
 }
 
 impl Separator {
@@ -482,22 +1214,7 @@ impl Widget for Separator {
         draw_box(renderer, rect, theme, selector);
 
         let line_y = rect.y + rect.height as i32 / 2;
-        renderer.rect(rect.x, line_y, rect.width, 1, theme.color("color", selector));
-    }
-
-    fn event(&self, event: Event, _focused: bool, _redraw: &mut bool, caught: &mut bool) -> bool {
-        let mut ignore_event = false;
-        match event {
-            Event::Mouse { point, .. } => {
-                let rect = self.rect.get();
-                if rect.contains(point) {
-                    ignore_event = true;
-                    *caught = true;
-                }
-            }
-            _ => (),
-        }
-        ignore_event
+        renderer.rect(rect.x + PADDING, line_y, rect.width.saturating_sub(2 * PADDING as u32), 1, theme.color("color", selector));
     }
 
     fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
@@ -509,4 +1226,12 @@ impl Entry for Separator {
     fn entry_text(&self) -> String {
         String::new()
     }
+
+    fn entry_height(&self) -> u32 {
+        SEPARATOR_HEIGHT
+    }
+
+    fn entry_enabled(&self) -> bool {
+        false
+    }
 }
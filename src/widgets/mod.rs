@@ -5,8 +5,10 @@
 
 use orbclient::Renderer;
 use std::any::Any;
+use std::cmp::max;
 use std::cell::{Cell, RefCell};
 use std::sync::Arc;
+use std::time::Instant;
 
 use event::Event;
 use rect::Rect;
@@ -15,19 +17,39 @@ use theme::Theme;
 use thickness::Thickness;
 
 pub use self::button::Button;
-pub use self::combo_box::ComboBox;
+pub use self::canvas::{Canvas, Painter};
+pub use self::check_box::CheckBox;
+pub use self::combo_box::{ComboBox, ComboBoxFilter};
+pub use self::data_grid::DataGrid;
 pub use self::label::Label;
-pub use self::menu::{Action, Menu, Separator};
+pub use self::menu::{Action, ActionGroup, Menu, MenuBar, Separator};
 pub use self::progress_bar::ProgressBar;
+pub use self::radio_button::{RadioButton, RadioGroup};
+pub use self::scroll_viewer::ScrollViewer;
+pub use self::slider::Slider;
+pub use self::spin_box::SpinBox;
+pub use self::tab_control::TabControl;
 pub use self::text_box::TextBox;
-pub use self::list::{Entry, List};
+pub use self::tree_view::{TreeNode, TreeView};
+pub use self::virtual_list::VirtualList;
+pub use self::list::{Entry, List, SelectionMode};
 
 mod button;
+mod canvas;
+mod check_box;
 mod combo_box;
+mod data_grid;
 mod label;
 mod menu;
 mod progress_bar;
+mod radio_button;
+mod scroll_viewer;
+mod slider;
+mod spin_box;
+mod tab_control;
 mod text_box;
+mod tree_view;
+mod virtual_list;
 mod list;
 
 /// Describes the vertical placement of a widget.
@@ -88,70 +110,186 @@ pub trait Widget: Any {
     /// Borrow the children of the widget.
     fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>>;
 
+    /// Get the widgets that are shown inside of the widget. Widgets that keep them outside
+    /// of `children`, e.g. the entries of a grid or the content of a scroll viewer, return
+    /// them here, so that the window finds them, e.g. for popups, ticks, tooltips and
+    /// scoped shortcuts.
+    fn visible_children(&self) -> Vec<Arc<dyn Widget>> {
+        self.children().borrow().clone()
+    }
+
     /// Add a child to the widget.
     fn add(&self, widget: Arc<dyn Widget>) {
         (*self.children().borrow_mut()).push(widget);
         self.arrange();
     }
 
+    /// Get the size the widget needs to show all of its content, e.g. the size of the cells
+    /// of a grid. Containers like a scroll viewer use it, by default it is the size of the rect.
+    fn content_size(&self) -> (u32, u32) {
+        let rect = self.rect().get();
+        (rect.width, rect.height)
+    }
+
     /// Used to update the state of the widget. Could be used to update the selector.
     fn update(&self) {}
 
+    /// Get the open popup of the widget, e.g. the flyout of a combo box. The window draws
+    /// popups above all other widgets and offers them the events first.
+    fn popup(&self) -> Option<Arc<dyn Widget>> {
+        None
+    }
+
+    /// Close the popup of the widget. Called by the window on a click outside of the
+    /// widget and its popup and on Escape.
+    fn close_popup(&self) {}
+
+    /// Advance the animation of the widget. The window calls it about every frame,
+    /// returns true if the widget has to be redrawn.
+    fn tick(&self, _now: Instant) -> bool {
+        false
+    }
+
+    /// Returns true while the widget needs a `tick` every frame, e.g. while it shows an
+    /// animation. The window wakes up for the next frame then.
+    fn needs_tick(&self) -> bool {
+        false
+    }
+
+    /// Get the default context menu of the widget. It is shown if no other context menu
+    /// was set with `Window::context_menus`.
+    fn context_menu(&self) -> Option<Arc<Menu>> {
+        None
+    }
+
     /// Arrange the children of the widget. Could be override to create a custom layout.
     fn arrange(&self) {
         let parent_rect = self.rect().get();
 
         for child in &*self.children().borrow_mut() {
-            let mut child_rect = child.rect().get();
-            let child_position = child.local_position().get();
-            let margin = child.margin().get();
-
-            match child.vertical_placement().get() {
-                VerticalPlacement::Absolute => {
-                    child_rect.y = parent_rect.y + child_position.y;
-                }
-                VerticalPlacement::Stretch => {
-                    child_rect.height =
-                        parent_rect.height - margin.top as u32 - margin.bottom as u32;
-                    child_rect.y = parent_rect.y + margin.top;
-                }
-                VerticalPlacement::Top => {
-                    child_rect.y = parent_rect.y + margin.top;
-                }
-                VerticalPlacement::Center => {
-                    child_rect.y = parent_rect.y + parent_rect.height as i32 / 2
-                        - child_rect.height as i32 / 2;
-                }
-                VerticalPlacement::Bottom => {
-                    child_rect.y = parent_rect.y + parent_rect.height as i32 - margin.bottom
-                        - child_rect.height as i32;
-                }
-            }
+            arrange_child(parent_rect, child);
+        }
+    }
+}
 
-            match child.horizontal_placement().get() {
-                HorizontalPlacement::Absolute => {
-                    child_rect.x = parent_rect.x + child_position.x;
-                }
-                HorizontalPlacement::Stretch => {
-                    child_rect.width =
-                        parent_rect.width - margin.left as u32 - margin.right as u32;
-                    child_rect.x = parent_rect.x + margin.left;
-                }
-                HorizontalPlacement::Left => {
-                    child_rect.x = parent_rect.x + margin.left;
-                }
-                HorizontalPlacement::Center => {
-                    child_rect.x = parent_rect.x + parent_rect.width as i32 / 2
-                        - child_rect.width as i32 / 2;
-                }
-                HorizontalPlacement::Right => {
-                    child_rect.x = parent_rect.x + parent_rect.width as i32 - margin.right
-                        - child_rect.width as i32;
-                }
+/// Place a child inside of a rect according to its placement, local position and margin.
+pub fn arrange_child(parent_rect: Rect, child: &Arc<dyn Widget>) {
+    let mut child_rect = child.rect().get();
+    let child_position = child.local_position().get();
+    let margin = child.margin().get();
+
+    match child.vertical_placement().get() {
+        VerticalPlacement::Absolute => {
+            child_rect.y = parent_rect.y + child_position.y;
+        }
+        VerticalPlacement::Stretch => {
+            child_rect.height =
+                max(0, parent_rect.height as i32 - margin.top - margin.bottom) as u32;
+            child_rect.y = parent_rect.y + margin.top;
+        }
+        VerticalPlacement::Top => {
+            child_rect.y = parent_rect.y + margin.top;
+        }
+        VerticalPlacement::Center => {
+            child_rect.y = parent_rect.y + parent_rect.height as i32 / 2
+                - child_rect.height as i32 / 2;
+        }
+        VerticalPlacement::Bottom => {
+            child_rect.y = parent_rect.y + parent_rect.height as i32 - margin.bottom
+                - child_rect.height as i32;
+        }
+    }
+
+    match child.horizontal_placement().get() {
+        HorizontalPlacement::Absolute => {
+            child_rect.x = parent_rect.x + child_position.x;
+        }
+        HorizontalPlacement::Stretch => {
+            child_rect.width =
+                max(0, parent_rect.width as i32 - margin.left - margin.right) as u32;
+            child_rect.x = parent_rect.x + margin.left;
+        }
+        HorizontalPlacement::Left => {
+            child_rect.x = parent_rect.x + margin.left;
+        }
+        HorizontalPlacement::Center => {
+            child_rect.x = parent_rect.x + parent_rect.width as i32 / 2
+                - child_rect.width as i32 / 2;
+        }
+        HorizontalPlacement::Right => {
+            child_rect.x = parent_rect.x + parent_rect.width as i32 - margin.right
+                - child_rect.width as i32;
+        }
+    }
+
+    child.rect().set(child_rect);
+    child.arrange();
+}
+
+/// Collect the open popups of a widget tree together with the widgets they belong to.
+pub fn collect_popups(widget: &Arc<dyn Widget>, popups: &mut Vec<(Arc<dyn Widget>, Arc<dyn Widget>)>) {
+    if let Some(popup) = widget.popup() {
+        popups.push((widget.clone(), popup.clone()));
+
+        // a popup could have popups of its own, e.g. the submenus of a menu
+        collect_popups(&popup, popups);
+    }
+
+    for child in widget.visible_children().iter() {
+        collect_popups(child, popups);
+    }
+}
+
+/// Advance the animations of a widget tree. Returns true if a widget has to be redrawn.
+pub fn tick_widgets(widget: &Arc<dyn Widget>, now: Instant) -> bool {
+    let mut redraw = widget.tick(now);
+
+    for child in widget.visible_children().iter() {
+        redraw |= tick_widgets(child, now);
+    }
+
+    redraw
+}
+
+/// Returns true if a widget of the tree needs a tick every frame.
+pub fn tree_needs_tick(widget: &Arc<dyn Widget>) -> bool {
+    widget.needs_tick() || widget.visible_children().iter().any(tree_needs_tick)
+}
+
+/// Find the innermost widget at a point that matches the predicate, searching the widgets
+/// from the top.
+pub fn find_widget_at<P: Fn(&Arc<dyn Widget>) -> bool>(
+    widgets: &[Arc<dyn Widget>], point: Point, predicate: &P
+) -> Option<Arc<dyn Widget>> {
+    for widget in widgets.iter().rev() {
+        if widget.rect().get().contains(point) {
+            if let Some(child) = find_widget_at(&widget.visible_children(), point, predicate) {
+                return Some(child);
             }
 
-            child.rect().set(child_rect);
-            child.arrange();
+            if predicate(widget) {
+                return Some(widget.clone());
+            }
         }
     }
+
+    None
+}
+
+/// Returns true if the widget is part of the widget tree.
+pub fn contains_widget(tree: &Arc<dyn Widget>, widget: &Arc<dyn Widget>) -> bool {
+    if Arc::ptr_eq(tree, widget) {
+        return true;
+    }
+
+    tree.visible_children().iter().any(|child| contains_widget(child, widget))
+}
+
+/// Close all popups of a widget tree.
+pub fn close_popups(widget: &Arc<dyn Widget>) {
+    widget.close_popup();
+
+    for child in widget.visible_children().iter() {
+        close_popups(child);
+    }
 }
@@ -2,10 +2,12 @@ use orbclient::Renderer;
 use std::cell::{Cell, RefCell};
 use std::cmp::{min, max};
 use std::sync::Arc;
+use std::time::Instant;
 
 use cell::{CloneCell, CheckSet};
 use draw::draw_box;
 use event::Event;
+use layouts::Orientation;
 use point::Point;
 use rect::Rect;
 use thickness::Thickness;
@@ -13,6 +15,14 @@ use theme::{Theme, Selector};
 use traits::{Click, Place, Style};
 use widgets::{Widget, VerticalPlacement, HorizontalPlacement};
 
+/// The time the chunk of an indeterminate progress bar needs to cross the bar.
+const INDETERMINATE_PERIOD: u64 = 1500;
+/// The time between two frames of the indeterminate animation, in milliseconds.
+const FRAME_TIME: u64 = 33;
+
+/// Shows the progress of a task as a bar that is filled from the left, or from the
+/// bottom if it is vertical. If the progress is unknown, an indeterminate progress bar
+/// shows a chunk that moves along the bar.
 pub struct ProgressBar {
     pub rect: Cell<Rect>,
     children: RefCell<Vec<Arc<dyn Widget>>>,
@@ -22,8 +32,14 @@ pub struct ProgressBar {
     margin: Cell<Thickness>,
     pub selector: CloneCell<Selector>,
     pub value: Cell<i32>,
-    pub minimum: i32,
-    pub maximum: i32,
+    pub minimum: Cell<i32>,
+    pub maximum: Cell<i32>,
+    pub orientation: Cell<Orientation>,
+    /// The text that is centered on the bar, see `text_format`
+    pub text_format: CloneCell<Option<String>>,
+    pub indeterminate: Cell<bool>,
+    /// The start of the indeterminate animation and its current frame
+    animation: Cell<Option<(Instant, u64)>>,
     click_callback: RefCell<Option<Arc<dyn Fn(&ProgressBar, Point)>>>,
     pressed: Cell<bool>,
 }
@@ -39,17 +55,121 @@ impl ProgressBar {
             children: RefCell::new(vec![]),
             selector: CloneCell::new(Selector::new(Some("progress-bar"))),
             value: Cell::new(0),
-            minimum: 0,
-            maximum: 100,
+            minimum: Cell::new(0),
+            maximum: Cell::new(100),
+            orientation: Cell::new(Orientation::Horizontal),
+            text_format: CloneCell::new(None),
+            indeterminate: Cell::new(false),
+            animation: Cell::new(None),
             click_callback: RefCell::new(None),
             pressed: Cell::new(false),
         })
     }
 
     pub fn value(&self, value: i32) -> &Self {
-        self.value.set(value);
+        self.value.set(self.clamp(value));
+        self
+    }
+
+    pub fn range(&self, minimum: i32, maximum: i32) -> &Self {
+        self.minimum.set(minimum);
+        self.maximum.set(max(minimum, maximum));
+        self.value.set(self.clamp(self.value.get()));
+        self
+    }
+
+    pub fn orientation(&self, orientation: Orientation) -> &Self {
+        self.orientation.set(orientation);
+        self
+    }
+
+    /// Show a text centered on the bar. `{percent}`, `{value}`, `{minimum}` and `{maximum}`
+    /// are replaced, e.g. `{percent}%`. Indeterminate progress bars show the text as it is.
+    pub fn text_format<S: Into<String>>(&self, text_format: Option<S>) -> &Self {
+        self.text_format.set(text_format.map(|text_format| text_format.into()));
+        self
+    }
+
+    /// An indeterminate progress bar shows that a task is running when its progress is unknown.
+    pub fn indeterminate(&self, indeterminate: bool) -> &Self {
+        self.indeterminate.set(indeterminate);
+        self.animation.set(None);
         self
     }
+
+    fn clamp(&self, value: i32) -> i32 {
+        max(self.minimum.get(), min(self.maximum.get(), value))
+    }
+
+    /// Get the progress and the range, in i64 to not overflow with wide ranges.
+    fn progress(&self) -> (i64, i64) {
+        let minimum = self.minimum.get() as i64;
+        let range = max(1, self.maximum.get() as i64 - minimum);
+        (self.clamp(self.value.get()) as i64 - minimum, range)
+    }
+
+    /// Get the progress in percent.
+    pub fn percent(&self) -> i32 {
+        let (progress, range) = self.progress();
+        (progress * 100 / range) as i32
+    }
+
+    fn text(&self) -> Option<String> {
+        self.text_format.get().map(|text_format| {
+            if self.indeterminate.get() {
+                return text_format;
+            }
+
+            text_format
+                .replace("{percent}", &self.percent().to_string())
+                .replace("{value}", &self.clamp(self.value.get()).to_string())
+                .replace("{minimum}", &self.minimum.get().to_string())
+                .replace("{maximum}", &self.maximum.get().to_string())
+        })
+    }
+
+    /// Get the filled part of the bar.
+    fn progress_rect(&self) -> Rect {
+        let rect = self.rect.get();
+        let (progress, range) = self.progress();
+
+        match self.orientation.get() {
+            Orientation::Horizontal => Rect {
+                width: (rect.width as i64 * progress / range) as u32,
+                ..rect
+            },
+            Orientation::Vertical => {
+                let height = (rect.height as i64 * progress / range) as u32;
+                Rect::new(rect.x, rect.y + (rect.height - height) as i32, rect.width, height)
+            }
+        }
+    }
+
+    /// Get the moving chunk of an indeterminate progress bar.
+    fn chunk_rect(&self) -> Rect {
+        let rect = self.rect.get();
+        let frame = self.animation.get().map(|(_, frame)| frame).unwrap_or(0);
+        let frames = INDETERMINATE_PERIOD / FRAME_TIME;
+        let position = (frame % frames) as i64;
+
+        // the chunk is a quarter of the bar, it enters at the start and leaves at the end
+        let (length, chunk) = match self.orientation.get() {
+            Orientation::Horizontal => (rect.width as i64, rect.width as i64 / 4),
+            Orientation::Vertical => (rect.height as i64, rect.height as i64 / 4),
+        };
+        let offset = -chunk + (length + chunk) * position / frames as i64;
+
+        let chunk_rect = match self.orientation.get() {
+            Orientation::Horizontal => Rect::new(rect.x + offset as i32, rect.y, chunk as u32, rect.height),
+            Orientation::Vertical => Rect::new(
+                rect.x,
+                rect.y + (length - offset - chunk) as i32,
+                rect.width,
+                chunk as u32,
+            ),
+        };
+        chunk_rect.intersection(&rect)
+    }
 }
 
 impl Click for ProgressBar {
@@ -100,12 +220,11 @@ impl Widget for ProgressBar {
 
     fn draw(&self, renderer: &mut dyn Renderer, _focused: bool, theme: &Theme) {
         let rect = self.rect.get();
-        let progress_rect = Rect{
-                                width: (rect.width as i32 *
-                                        max(0, min(self.maximum, self.value.get() - self.minimum)) /
-                                        max(1, self.maximum - self.minimum)) as u32,
-                                ..self.rect.get()
-                            };
+        let progress_rect = if self.indeterminate.get() {
+            self.chunk_rect()
+        } else {
+            self.progress_rect()
+        };
 
         let selector = &self.selector.get();
 
@@ -114,11 +233,51 @@ impl Widget for ProgressBar {
         let b_r = theme.get("border-radius", selector).map(|v| v.uint().unwrap()).unwrap_or(1);
         let b_t = theme.get("border-width", selector).map(|v| v.uint().unwrap()).unwrap_or(0);
 
-
-        if progress_rect.width >=  b_t + b_r * 2 {
+        let length = match self.orientation.get() {
+            Orientation::Horizontal => progress_rect.width,
+            Orientation::Vertical => progress_rect.height,
+        };
+        if length >= b_t + b_r * 2 {
             //TODO: set this selector as the child of self.selector
             draw_box(renderer, progress_rect, theme, &Selector::new(Some("progress")));
         }
+
+        if let Some(text) = self.text() {
+            let color = theme.color("color", selector);
+            let text_width = text.chars().count() as i32 * 8;
+            let mut x = rect.x + (rect.width as i32 - text_width) / 2;
+            let y = rect.y + (rect.height as i32 - 16) / 2;
+            for c in text.chars() {
+                if x >= rect.x && x + 8 <= rect.x + rect.width as i32 {
+                    renderer.char(x, y, c, color);
+                }
+                x += 8;
+            }
+        }
+    }
+
+    fn tick(&self, now: Instant) -> bool {
+        if !self.indeterminate.get() {
+            return false;
+        }
+
+        match self.animation.get() {
+            Some((start, frame)) => {
+                let elapsed = now.duration_since(start);
+                let elapsed = elapsed.as_secs() * 1000 + elapsed.subsec_nanos() as u64 / 1_000_000;
+                let next_frame = elapsed / FRAME_TIME;
+                self.animation.set(Some((start, next_frame)));
+                next_frame != frame
+            }
+            None => {
+                self.animation.set(Some((now, 0)));
+                true
+            }
+        }
+    }
+
+    fn needs_tick(&self) -> bool {
+        self.indeterminate.get()
     }
 
     fn event(&self, event: Event, focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
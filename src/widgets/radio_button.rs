@@ -0,0 +1,291 @@
+use orbclient::Renderer;
+use std::cell::{Cell, RefCell};
+use std::sync::{Arc, Weak};
+use orbclient;
+
+use cell::{CheckSet, CloneCell};
+use draw::draw_box;
+use event::Event;
+use point::Point;
+use rect::Rect;
+use thickness::Thickness;
+use theme::{Selector, Theme};
+use traits::{Click, Place, Style, Text};
+use widgets::{HorizontalPlacement, VerticalPlacement, Widget};
+
+/// A button that is checked by clicking it or by pressing space. Radio buttons
+/// added to the same `RadioGroup` are mutually exclusive.
+pub struct RadioButton {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub selector: CloneCell<Selector>,
+    pub text: CloneCell<String>,
+    pub text_offset: Cell<Point>,
+    pub checked: CloneCell<bool>,
+    click_callback: RefCell<Option<Arc<dyn Fn(&RadioButton, Point)>>>,
+    hover: Cell<bool>,
+    pressed: Cell<bool>,
+}
+
+impl RadioButton {
+    pub fn new() -> Arc<Self> {
+        Arc::new(RadioButton {
+            rect: Cell::new(Rect::new(0, 0, 16, 16)),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector: CloneCell::new(Selector::new(Some("radio-button"))),
+            text: CloneCell::new(String::new()),
+            text_offset: Cell::new(Point::new(8, 0)),
+            checked: CloneCell::new(false),
+            click_callback: RefCell::new(None),
+            hover: Cell::new(false),
+            pressed: Cell::new(false),
+        })
+    }
+
+    pub fn checked(&self, checked: bool) -> &Self {
+        self.checked.set(checked);
+        self
+    }
+
+    fn adjust_size(&self) {
+        let text_offset = self.text_offset.get();
+        let text_width = self.text.borrow().len() as u32 * 8;
+        self.size(
+            16 + if text_width > 0 { text_offset.x as u32 + text_width } else { 0 },
+            16 + 2 * text_offset.y as u32,
+        );
+    }
+}
+
+impl Click for RadioButton {
+    fn emit_click(&self, point: Point) {
+        if let Some(ref click_callback) = *self.click_callback.borrow() {
+            click_callback(self, point);
+        }
+    }
+
+    fn on_click<T: Fn(&Self, Point) + 'static>(&self, func: T) -> &Self {
+        *self.click_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+}
+
+impl Place for RadioButton {}
+
+impl Text for RadioButton {
+    fn text<S: Into<String>>(&self, text: S) -> &Self {
+        self.text.set(text.into());
+        self.adjust_size();
+        self
+    }
+
+    fn text_offset(&self, x: i32, y: i32) -> &Self {
+        self.text_offset.set(Point::new(x, y));
+        self.adjust_size();
+        self
+    }
+}
+
+impl Style for RadioButton {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.selector
+    }
+}
+
+impl Widget for RadioButton {
+    fn name(&self) -> &str {
+        "RadioButton"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn update(&self) {
+        let mut selector = self.selector.get()
+            .without_pseudo_class("checked")
+            .without_pseudo_class("hover");
+
+        if self.checked.get() {
+            selector = selector.with_pseudo_class("checked");
+        }
+
+        if self.hover.get() {
+            selector = selector.with_pseudo_class("hover");
+        }
+
+        self.selector.set(selector);
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+        let rect = self.rect.get();
+        let mut selector = self.selector.get();
+
+        if focused {
+            selector = selector.with_pseudo_class("focus");
+        }
+
+        let box_rect = Rect::new(rect.x, rect.y + rect.height as i32 / 2 - 8, 16, 16);
+        draw_box(renderer, box_rect, theme, &selector);
+
+        if self.checked.get() {
+            //TODO: set this selector as the child of self.selector
+            draw_box(
+                renderer,
+                Rect::new(box_rect.x + 4, box_rect.y + 4, 8, 8),
+                theme,
+                &Selector::new(Some("radio-button-mark")),
+            );
+        }
+
+        let mut x = box_rect.x + 16 + self.text_offset.get().x;
+        for c in self.text.borrow().chars() {
+            if x + 8 <= rect.x + rect.width as i32 {
+                renderer.char(x, box_rect.y, c, theme.color("color", &selector));
+            }
+            x += 8;
+        }
+    }
+
+    fn event(&self, event: Event, mut focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                let mut click = false;
+
+                let rect = self.rect.get();
+                if rect.contains(point) {
+                    if self.hover.check_set(true) {
+                        *redraw = true;
+                    }
+
+                    if left_button {
+                        if self.pressed.check_set(true) {
+                            *redraw = true;
+                        }
+                    } else {
+                        if self.pressed.check_set(false) {
+                            click = true;
+                            *redraw = true;
+                        }
+                    }
+
+                    *caught = true;
+                } else {
+                    if self.hover.check_set(false) {
+                        *redraw = true;
+                    }
+
+                    if !left_button {
+                        if self.pressed.check_set(false) {
+                            *redraw = true;
+                        }
+                    }
+                }
+
+                if click {
+                    focused = true;
+                    self.checked(true);
+                    let click_point: Point = point - rect.point();
+                    self.emit_click(click_point);
+                }
+            }
+            Event::KeyPressed(key_event) if focused => if key_event.scancode == orbclient::K_SPACE {
+                self.checked(true);
+                self.emit_click(Point::new(0, 0));
+                *redraw = true;
+            },
+            _ => (),
+        }
+
+        focused
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
+
+/// Makes the radio buttons added to it mutually exclusive. Checking one of the
+/// buttons unchecks all others.
+pub struct RadioGroup {
+    buttons: Arc<RefCell<Vec<Weak<RadioButton>>>>,
+}
+
+impl RadioGroup {
+    pub fn new() -> Self {
+        RadioGroup {
+            buttons: Arc::new(RefCell::new(vec![])),
+        }
+    }
+
+    pub fn add(&self, button: &Arc<RadioButton>) -> &Self {
+        self.buttons.borrow_mut().push(Arc::downgrade(button));
+
+        let buttons = self.buttons.clone();
+        let checked_button = Arc::downgrade(button);
+        button.checked.on_changed(move |checked: bool| {
+            if !checked {
+                return;
+            }
+
+            for other in buttons.borrow().iter() {
+                if other.ptr_eq(&checked_button) {
+                    continue;
+                }
+
+                if let Some(other) = other.upgrade() {
+                    if other.checked.get() {
+                        other.checked.set(false);
+                    }
+                }
+            }
+        });
+
+        // keep the group consistent if the new button is already checked
+        if button.checked.get() {
+            button.checked.set(true);
+        }
+
+        self
+    }
+
+    /// Get the index of the checked button.
+    pub fn selected(&self) -> Option<usize> {
+        self.buttons
+            .borrow()
+            .iter()
+            .position(|button| button.upgrade().map(|b| b.checked.get()).unwrap_or(false))
+    }
+
+    /// Check the button with the given index.
+    pub fn select(&self, index: usize) {
+        let button = self.buttons.borrow().get(index).and_then(|button| button.upgrade());
+        if let Some(button) = button {
+            button.checked(true);
+        }
+    }
+}
@@ -0,0 +1,426 @@
+use orbclient::Renderer;
+use std::cell::{Cell, RefCell};
+use std::cmp::{max, min};
+use std::sync::Arc;
+
+use cell::{CheckSet, CloneCell};
+use draw::{draw_box, ClipRenderer};
+use event::Event;
+use layouts::Orientation;
+use point::Point;
+use rect::Rect;
+use thickness::Thickness;
+use theme::{Selector, Theme};
+use traits::{Place, Style};
+use widgets::{HorizontalPlacement, VerticalPlacement, Widget};
+
+const SCROLL_BAR_SIZE: u32 = 8;
+const MIN_THUMB_SIZE: i32 = 16;
+const SCROLL_STEP: i32 = 48;
+
+/// Hosts a single content widget that could be larger than the scroll viewer itself.
+/// The visible part of the content is moved with scroll bars or the mouse wheel.
+///
+/// The size of the content is taken from its rect, so layouts that size themselves
+/// like `Grid` could be scrolled. Content with a stretch placement is fitted to the
+/// viewport in that direction.
+pub struct ScrollViewer {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub selector: CloneCell<Selector>,
+    content: RefCell<Option<Arc<dyn Widget>>>,
+    pub scroll_offset: Cell<Point>,
+    /// The scroll bar that is dragged with the mouse position and scroll offset at the start of the drag
+    dragging: Cell<Option<(Orientation, i32, i32)>>,
+    pressed: Cell<bool>,
+    /// Scroll events have no position, so the wheel only scrolls while the mouse is over the scroll viewer
+    hover: Cell<bool>,
+}
+
+impl ScrollViewer {
+    pub fn new() -> Arc<Self> {
+        Arc::new(ScrollViewer {
+            rect: Cell::new(Rect::default()),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector: CloneCell::new(Selector::new(Some("scroll-viewer"))),
+            content: RefCell::new(None),
+            scroll_offset: Cell::new(Point::new(0, 0)),
+            dragging: Cell::new(None),
+            pressed: Cell::new(false),
+            hover: Cell::new(false),
+        })
+    }
+
+    /// Set the widget that is scrolled by the scroll viewer.
+    pub fn content<T: Widget>(&self, content: &Arc<T>) -> &Self {
+        *self.content.borrow_mut() = Some(content.clone());
+        self.scroll_offset.set(Point::new(0, 0));
+        self.arrange();
+        self
+    }
+
+    /// Scroll to the given offset of the content.
+    pub fn scroll_to(&self, x: i32, y: i32) {
+        let (max_x, max_y) = self.max_offset();
+        self.scroll_offset.set(Point::new(max(0, min(max_x, x)), max(0, min(max_y, y))));
+        self.arrange();
+    }
+
+    /// Scroll the content by the given distance.
+    pub fn scroll(&self, x: i32, y: i32) {
+        let offset = self.scroll_offset.get();
+        self.scroll_to(offset.x + x, offset.y + y);
+    }
+
+    /// Scroll as little as possible to make the given widget of the content visible.
+    pub fn scroll_into_view<T: Widget>(&self, widget: &Arc<T>) {
+        let content_rect = match *self.content.borrow() {
+            Some(ref content) => content.rect().get(),
+            None => return,
+        };
+
+        let rect = widget.rect().get();
+        let viewport = self.viewport();
+        let mut offset = self.scroll_offset.get();
+
+        let x = rect.x - content_rect.x;
+        if x < offset.x {
+            offset.x = x;
+        } else if x + rect.width as i32 > offset.x + viewport.width as i32 {
+            offset.x = x + rect.width as i32 - viewport.width as i32;
+        }
+
+        let y = rect.y - content_rect.y;
+        if y < offset.y {
+            offset.y = y;
+        } else if y + rect.height as i32 > offset.y + viewport.height as i32 {
+            offset.y = y + rect.height as i32 - viewport.height as i32;
+        }
+
+        self.scroll_to(offset.x, offset.y);
+    }
+
+    fn content_size(&self) -> (u32, u32) {
+        match *self.content.borrow() {
+            Some(ref content) => content.content_size(),
+            None => (0, 0),
+        }
+    }
+
+    /// Get which scroll bars are needed. A scroll bar takes space from the
+    /// viewport, which could make the other one necessary.
+    fn scroll_bars(&self) -> (bool, bool) {
+        let rect = self.rect.get();
+        let (width, height) = self.content_size();
+
+        let mut horizontal = width > rect.width;
+        let vertical = height > rect.height.saturating_sub(if horizontal { SCROLL_BAR_SIZE } else { 0 });
+        if vertical && !horizontal {
+            horizontal = width > rect.width.saturating_sub(SCROLL_BAR_SIZE);
+        }
+
+        (horizontal, vertical)
+    }
+
+    /// The part of the scroll viewer the content is shown in.
+    fn viewport(&self) -> Rect {
+        let mut rect = self.rect.get();
+        let (horizontal, vertical) = self.scroll_bars();
+
+        if horizontal {
+            rect.height = rect.height.saturating_sub(SCROLL_BAR_SIZE);
+        }
+        if vertical {
+            rect.width = rect.width.saturating_sub(SCROLL_BAR_SIZE);
+        }
+
+        rect
+    }
+
+    fn max_offset(&self) -> (i32, i32) {
+        let viewport = self.viewport();
+        let (width, height) = self.content_size();
+        (
+            max(0, width as i32 - viewport.width as i32),
+            max(0, height as i32 - viewport.height as i32),
+        )
+    }
+
+    fn track_rect(&self, orientation: Orientation) -> Rect {
+        let viewport = self.viewport();
+        match orientation {
+            Orientation::Horizontal => Rect::new(
+                viewport.x,
+                viewport.y + viewport.height as i32,
+                viewport.width,
+                SCROLL_BAR_SIZE,
+            ),
+            Orientation::Vertical => Rect::new(
+                viewport.x + viewport.width as i32,
+                viewport.y,
+                SCROLL_BAR_SIZE,
+                viewport.height,
+            ),
+        }
+    }
+
+    fn thumb_rect(&self, orientation: Orientation) -> Rect {
+        let track = self.track_rect(orientation);
+        let viewport = self.viewport();
+        let (content_width, content_height) = self.content_size();
+        let (max_x, max_y) = self.max_offset();
+        let offset = self.scroll_offset.get();
+
+        match orientation {
+            Orientation::Horizontal => {
+                let length = thumb_length(track.width, viewport.width, content_width);
+                let position = offset.x * (track.width as i32 - length) / max(1, max_x);
+                Rect::new(track.x + position, track.y, length as u32, track.height)
+            }
+            Orientation::Vertical => {
+                let length = thumb_length(track.height, viewport.height, content_height);
+                let position = offset.y * (track.height as i32 - length) / max(1, max_y);
+                Rect::new(track.x, track.y + position, track.width, length as u32)
+            }
+        }
+    }
+
+    /// Move the dragged thumb to follow the mouse.
+    fn drag(&self, orientation: Orientation, start: i32, start_offset: i32, point: Point) {
+        let track = self.track_rect(orientation);
+        let thumb = self.thumb_rect(orientation);
+        let (max_x, max_y) = self.max_offset();
+        let offset = self.scroll_offset.get();
+
+        match orientation {
+            Orientation::Horizontal => {
+                let free = max(1, track.width as i32 - thumb.width as i32);
+                self.scroll_to(start_offset + (point.x - start) * max_x / free, offset.y);
+            }
+            Orientation::Vertical => {
+                let free = max(1, track.height as i32 - thumb.height as i32);
+                self.scroll_to(offset.x, start_offset + (point.y - start) * max_y / free);
+            }
+        }
+    }
+
+    /// Handle a mouse press on a scroll bar. Pressing the thumb starts a drag,
+    /// pressing the track moves by one page.
+    fn press_scroll_bar(&self, orientation: Orientation, point: Point) {
+        let thumb = self.thumb_rect(orientation);
+        let viewport = self.viewport();
+        let offset = self.scroll_offset.get();
+
+        match orientation {
+            Orientation::Horizontal => if thumb.contains(point) {
+                self.dragging.set(Some((orientation, point.x, offset.x)));
+            } else if point.x < thumb.x {
+                self.scroll(-(viewport.width as i32), 0);
+            } else {
+                self.scroll(viewport.width as i32, 0);
+            },
+            Orientation::Vertical => if thumb.contains(point) {
+                self.dragging.set(Some((orientation, point.y, offset.y)));
+            } else if point.y < thumb.y {
+                self.scroll(0, -(viewport.height as i32));
+            } else {
+                self.scroll(0, viewport.height as i32);
+            },
+        }
+    }
+
+    fn draw_scroll_bar(&self, renderer: &mut dyn Renderer, orientation: Orientation, theme: &Theme) {
+        //TODO: set these selectors as the children of self.selector
+        draw_box(renderer, self.track_rect(orientation), theme, &Selector::new(Some("scroll-bar")));
+
+        let mut thumb_selector = Selector::new(Some("scroll-bar-thumb"));
+        if let Some((dragged, _, _)) = self.dragging.get() {
+            if dragged == orientation {
+                thumb_selector = thumb_selector.with_pseudo_class("active");
+            }
+        }
+        draw_box(renderer, self.thumb_rect(orientation), theme, &thumb_selector);
+    }
+}
+
+fn thumb_length(track_length: u32, viewport_length: u32, content_length: u32) -> i32 {
+    let length = track_length as i32 * viewport_length as i32 / max(1, content_length as i32);
+    min(track_length as i32, max(MIN_THUMB_SIZE, length))
+}
+
+impl Place for ScrollViewer {}
+
+impl Style for ScrollViewer {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.selector
+    }
+}
+
+impl Widget for ScrollViewer {
+    fn name(&self) -> &str {
+        "ScrollViewer"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn arrange(&self) {
+        if let Some(ref content) = *self.content.borrow() {
+            let viewport = self.viewport();
+            let offset = self.scroll_offset.get();
+            let mut content_rect = content.rect().get();
+
+            content_rect.x = viewport.x - offset.x;
+            content_rect.y = viewport.y - offset.y;
+            if content.horizontal_placement().get() == HorizontalPlacement::Stretch {
+                content_rect.width = viewport.width;
+            }
+            if content.vertical_placement().get() == VerticalPlacement::Stretch {
+                content_rect.height = viewport.height;
+            }
+
+            content.rect().set(content_rect);
+            content.arrange();
+        }
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+        fn draw_widget(widget: &Arc<dyn Widget>, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+            widget.update();
+            widget.draw(renderer, focused, theme);
+
+            for child in widget.children().borrow().iter() {
+                draw_widget(child, renderer, focused, theme);
+            }
+        }
+
+        draw_box(renderer, self.rect.get(), theme, &self.selector.get());
+
+        if let Some(ref content) = *self.content.borrow() {
+            let mut clip_renderer = ClipRenderer::new(renderer, self.viewport());
+            draw_widget(content, &mut clip_renderer, focused, theme);
+        }
+
+        let (horizontal, vertical) = self.scroll_bars();
+        if horizontal {
+            self.draw_scroll_bar(renderer, Orientation::Horizontal, theme);
+        }
+        if vertical {
+            self.draw_scroll_bar(renderer, Orientation::Vertical, theme);
+        }
+    }
+
+    fn event(&self, event: Event, mut focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                self.hover.set(self.rect.get().contains(point));
+
+                if let Some((orientation, start, start_offset)) = self.dragging.get() {
+                    if left_button {
+                        self.drag(orientation, start, start_offset, point);
+                    } else {
+                        self.dragging.set(None);
+                    }
+
+                    *redraw = true;
+                    *caught = true;
+                    return focused;
+                }
+
+                let (horizontal, vertical) = self.scroll_bars();
+                let scroll_bar = if horizontal && self.track_rect(Orientation::Horizontal).contains(point) {
+                    Some(Orientation::Horizontal)
+                } else if vertical && self.track_rect(Orientation::Vertical).contains(point) {
+                    Some(Orientation::Vertical)
+                } else {
+                    None
+                };
+
+                if let Some(orientation) = scroll_bar {
+                    if left_button && self.pressed.check_set(true) {
+                        self.press_scroll_bar(orientation, point);
+                        *redraw = true;
+                    } else if !left_button {
+                        self.pressed.set(false);
+                    }
+
+                    *caught = true;
+                    return focused;
+                }
+
+                self.pressed.set(left_button && self.rect.get().contains(point));
+            }
+            Event::Scroll { x, y } => {
+                if self.hover.get() {
+                    let (horizontal, vertical) = self.scroll_bars();
+                    if vertical || horizontal {
+                        // without a vertical scroll bar the wheel scrolls horizontally
+                        if vertical {
+                            self.scroll(-x * SCROLL_STEP, -y * SCROLL_STEP);
+                        } else {
+                            self.scroll(-(x + y) * SCROLL_STEP, 0);
+                        }
+
+                        *redraw = true;
+                        *caught = true;
+                        return focused;
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        if let Some(ref content) = *self.content.borrow() {
+            // parts of the content outside of the viewport are hidden and must not be clicked
+            let forward = match event {
+                Event::Mouse { point, .. } => {
+                    let content_rect = content.rect().get();
+                    let (width, height) = content.content_size();
+                    self.viewport().contains(point)
+                        || !Rect::new(content_rect.x, content_rect.y, width, height).contains(point)
+                }
+                _ => true,
+            };
+
+            if forward {
+                focused = content.event(event, focused, redraw, caught);
+            }
+        }
+
+        focused
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+    fn visible_children(&self) -> Vec<Arc<dyn Widget>> {
+        let mut children = self.children.borrow().clone();
+        children.extend(self.content.borrow().clone());
+        children
+    }
+}
@@ -0,0 +1,321 @@
+use orbclient::Renderer;
+use std::cell::{Cell, RefCell};
+use std::cmp::{max, min};
+use std::sync::Arc;
+use orbclient;
+
+use cell::{CheckSet, CloneCell};
+use draw::draw_box;
+use event::Event;
+use layouts::Orientation;
+use point::Point;
+use rect::Rect;
+use thickness::Thickness;
+use theme::{Selector, Theme};
+use traits::{Change, Place, Style};
+use widgets::{HorizontalPlacement, VerticalPlacement, Widget};
+
+const THUMB_SIZE: u32 = 16;
+const TRACK_SIZE: u32 = 4;
+
+/// A widget to select a value of a range by dragging a thumb or by using the
+/// arrow and page keys.
+pub struct Slider {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub selector: CloneCell<Selector>,
+    pub value: CloneCell<i32>,
+    pub minimum: Cell<i32>,
+    pub maximum: Cell<i32>,
+    pub step: Cell<i32>,
+    pub orientation: Cell<Orientation>,
+    change_callback: RefCell<Option<Arc<dyn Fn(&Slider, i32)>>>,
+    hover: Cell<bool>,
+    pressed: Cell<bool>,
+}
+
+impl Slider {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Slider {
+            rect: Cell::new(Rect::new(0, 0, 100, THUMB_SIZE)),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector: CloneCell::new(Selector::new(Some("slider"))),
+            value: CloneCell::new(0),
+            minimum: Cell::new(0),
+            maximum: Cell::new(100),
+            step: Cell::new(1),
+            orientation: Cell::new(Orientation::Horizontal),
+            change_callback: RefCell::new(None),
+            hover: Cell::new(false),
+            pressed: Cell::new(false),
+        })
+    }
+
+    pub fn value(&self, value: i32) -> &Self {
+        self.value.set(self.clamp(value));
+        self
+    }
+
+    pub fn range(&self, minimum: i32, maximum: i32) -> &Self {
+        self.minimum.set(minimum);
+        self.maximum.set(max(minimum, maximum));
+        self.value.set(self.clamp(self.value.get()));
+        self
+    }
+
+    /// Set the amount the value changes with the arrow keys. Values picked with the
+    /// mouse are rounded to a multiple of the step.
+    pub fn step(&self, step: i32) -> &Self {
+        self.step.set(max(1, step));
+        self
+    }
+
+    pub fn orientation(&self, orientation: Orientation) -> &Self {
+        self.orientation.set(orientation);
+        self
+    }
+
+    fn clamp(&self, value: i32) -> i32 {
+        max(self.minimum.get(), min(self.maximum.get(), value))
+    }
+
+    /// The amount the value changes with Page Up and Page Down.
+    fn page(&self) -> i32 {
+        let step = self.step.get() as i64;
+        let range = self.maximum.get() as i64 - self.minimum.get() as i64;
+        max(step, range / 10 / step * step) as i32
+    }
+
+    /// Set the value and emit a change if it differs from the current one.
+    fn change_value(&self, value: i32) -> bool {
+        let value = self.clamp(value);
+        if value == self.value.get() {
+            return false;
+        }
+
+        self.value.set(value);
+        self.emit_change(value);
+        true
+    }
+
+    /// The length the thumb could move along the track.
+    fn track_length(&self) -> i32 {
+        let rect = self.rect.get();
+        let length = match self.orientation.get() {
+            Orientation::Horizontal => rect.width,
+            Orientation::Vertical => rect.height,
+        };
+        max(0, length as i32 - THUMB_SIZE as i32)
+    }
+
+    /// Get the offset of the thumb along the track.
+    fn thumb_offset(&self) -> i32 {
+        // wide ranges overflow i32
+        let range = max(1, self.maximum.get() as i64 - self.minimum.get() as i64);
+        let value = self.value.get() as i64 - self.minimum.get() as i64;
+        let offset = (value * self.track_length() as i64 / range) as i32;
+        match self.orientation.get() {
+            Orientation::Horizontal => offset,
+            // vertical sliders have their minimum at the bottom
+            Orientation::Vertical => self.track_length() - offset,
+        }
+    }
+
+    fn value_at(&self, point: Point) -> i32 {
+        let rect = self.rect.get();
+        let length = max(1, self.track_length());
+        let offset = match self.orientation.get() {
+            Orientation::Horizontal => point.x - rect.x,
+            Orientation::Vertical => rect.y + rect.height as i32 - point.y,
+        } - THUMB_SIZE as i32 / 2;
+        let offset = max(0, min(length, offset));
+
+        // wide ranges overflow i32
+        let (offset, length) = (offset as i64, length as i64);
+        let range = self.maximum.get() as i64 - self.minimum.get() as i64;
+        let step = self.step.get() as i64;
+        let value = (offset * range + length / 2) / length;
+        let value = self.minimum.get() as i64 + (value + step / 2) / step * step;
+        min(self.maximum.get() as i64, value) as i32
+    }
+
+    fn thumb_rect(&self) -> Rect {
+        let rect = self.rect.get();
+        let offset = self.thumb_offset();
+        match self.orientation.get() {
+            Orientation::Horizontal => Rect::new(
+                rect.x + offset,
+                rect.y + (rect.height as i32 - THUMB_SIZE as i32) / 2,
+                THUMB_SIZE,
+                THUMB_SIZE,
+            ),
+            Orientation::Vertical => Rect::new(
+                rect.x + (rect.width as i32 - THUMB_SIZE as i32) / 2,
+                rect.y + offset,
+                THUMB_SIZE,
+                THUMB_SIZE,
+            ),
+        }
+    }
+}
+
+impl Change for Slider {
+    type Value = i32;
+
+    fn emit_change(&self, value: i32) {
+        if let Some(ref change_callback) = *self.change_callback.borrow() {
+            change_callback(self, value);
+        }
+    }
+
+    fn on_change<T: Fn(&Self, i32) + 'static>(&self, func: T) -> &Self {
+        *self.change_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+}
+
+impl Place for Slider {}
+
+impl Style for Slider {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.selector
+    }
+}
+
+impl Widget for Slider {
+    fn name(&self) -> &str {
+        "Slider"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn update(&self) {
+        let mut selector = self.selector.get().without_pseudo_class("hover");
+
+        if self.hover.get() {
+            selector = selector.with_pseudo_class("hover");
+        }
+
+        self.selector.set(selector);
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+        let rect = self.rect.get();
+        let mut selector = self.selector.get();
+
+        if focused {
+            selector = selector.with_pseudo_class("focus");
+        }
+
+        let half_thumb = THUMB_SIZE as i32 / 2;
+        let track_rect = match self.orientation.get() {
+            Orientation::Horizontal => Rect::new(
+                rect.x + half_thumb,
+                rect.y + (rect.height as i32 - TRACK_SIZE as i32) / 2,
+                self.track_length() as u32,
+                TRACK_SIZE,
+            ),
+            Orientation::Vertical => Rect::new(
+                rect.x + (rect.width as i32 - TRACK_SIZE as i32) / 2,
+                rect.y + half_thumb,
+                TRACK_SIZE,
+                self.track_length() as u32,
+            ),
+        };
+        draw_box(renderer, track_rect, theme, &selector);
+
+        //TODO: set this selector as the child of self.selector
+        let mut thumb_selector = Selector::new(Some("slider-thumb"));
+        if self.pressed.get() {
+            thumb_selector = thumb_selector.with_pseudo_class("active");
+        } else if self.hover.get() || focused {
+            thumb_selector = thumb_selector.with_pseudo_class("hover");
+        }
+        draw_box(renderer, self.thumb_rect(), theme, &thumb_selector);
+    }
+
+    fn event(&self, event: Event, mut focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                let rect = self.rect.get();
+                if rect.contains(point) {
+                    if self.hover.check_set(true) {
+                        *redraw = true;
+                    }
+
+                    if left_button && self.pressed.check_set(true) {
+                        focused = true;
+                        *redraw = true;
+                    }
+
+                    *caught = true;
+                } else if self.hover.check_set(false) {
+                    *redraw = true;
+                }
+
+                if !left_button {
+                    if self.pressed.check_set(false) {
+                        *redraw = true;
+                    }
+                } else if self.pressed.get() {
+                    // keep dragging the thumb while the mouse is outside of the slider
+                    if self.change_value(self.value_at(point)) {
+                        *redraw = true;
+                    }
+                    *caught = true;
+                }
+            }
+            Event::KeyPressed(key_event) if focused => {
+                let value = self.value.get();
+                let new_value = match key_event.scancode {
+                    orbclient::K_LEFT | orbclient::K_DOWN => Some(value.saturating_sub(self.step.get())),
+                    orbclient::K_RIGHT | orbclient::K_UP => Some(value.saturating_add(self.step.get())),
+                    orbclient::K_PGDN => Some(value.saturating_sub(self.page())),
+                    orbclient::K_PGUP => Some(value.saturating_add(self.page())),
+                    orbclient::K_HOME => Some(self.minimum.get()),
+                    orbclient::K_END => Some(self.maximum.get()),
+                    _ => None,
+                };
+
+                if let Some(new_value) = new_value {
+                    if self.change_value(new_value) {
+                        *redraw = true;
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        focused
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
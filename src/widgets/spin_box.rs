@@ -0,0 +1,302 @@
+use orbclient::Renderer;
+use std::cell::{Cell, RefCell};
+use std::cmp::{max, min};
+use std::sync::Arc;
+use orbclient;
+
+use cell::{CheckSet, CloneCell};
+use draw::draw_box;
+use event::Event;
+use point::Point;
+use rect::Rect;
+use thickness::Thickness;
+use theme::{Selector, Theme};
+use traits::{Change, Place, Style, Text};
+use validator::Validator;
+use widgets::{HorizontalPlacement, TextBox, VerticalPlacement, Widget};
+
+const BUTTON_WIDTH: u32 = 16;
+
+/// A text box for whole numbers with buttons to increment and decrement the value.
+/// The value could also be changed with the arrow and page keys.
+pub struct SpinBox {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub text_box: Arc<TextBox>,
+    pub value: CloneCell<i32>,
+    pub minimum: Cell<i32>,
+    pub maximum: Cell<i32>,
+    pub step: Cell<i32>,
+    change_callback: RefCell<Option<Arc<dyn Fn(&SpinBox, i32)>>>,
+    /// The direction of the pressed button, 1 for increment and -1 for decrement
+    pressed: Cell<i32>,
+}
+
+impl SpinBox {
+    pub fn new() -> Arc<Self> {
+        let text_box = TextBox::new();
+        text_box.selector.set(Selector::new(Some("spin-box")));
+        text_box.text_offset(6, 6);
+
+        let spin_box = Arc::new(SpinBox {
+            rect: Cell::new(Rect::new(0, 0, 96, 28)),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            text_box,
+            value: CloneCell::new(0),
+            minimum: Cell::new(0),
+            maximum: Cell::new(100),
+            step: Cell::new(1),
+            change_callback: RefCell::new(None),
+            pressed: Cell::new(0),
+        });
+
+        spin_box.update_validator();
+        spin_box.update_text();
+        spin_box.arrange();
+
+        spin_box
+    }
+
+    pub fn value(&self, value: i32) -> &Self {
+        self.value.set(self.clamp(value));
+        self.update_text();
+        self
+    }
+
+    pub fn range(&self, minimum: i32, maximum: i32) -> &Self {
+        self.minimum.set(minimum);
+        self.maximum.set(max(minimum, maximum));
+        self.value.set(self.clamp(self.value.get()));
+        self.update_validator();
+        self.update_text();
+        self
+    }
+
+    /// Set the amount the value changes with the buttons and the arrow keys.
+    pub fn step(&self, step: i32) -> &Self {
+        self.step.set(max(1, step));
+        self
+    }
+
+    fn clamp(&self, value: i32) -> i32 {
+        max(self.minimum.get(), min(self.maximum.get(), value))
+    }
+
+    fn update_validator(&self) {
+        self.text_box.validator(
+            Validator::new().integer(self.minimum.get() as i64, self.maximum.get() as i64),
+        );
+    }
+
+    /// Replace the text of the text box by the current value.
+    fn update_text(&self) {
+        let text = self.value.get().to_string();
+        self.text_box.text_i.set(text.len());
+        self.text_box.text.set(text);
+    }
+
+    /// Set the value and emit a change if it differs from the current one.
+    fn change_value(&self, value: i32) -> bool {
+        let value = self.clamp(value);
+        if value == self.value.get() {
+            return false;
+        }
+
+        self.value.set(value);
+        self.emit_change(value);
+        true
+    }
+
+    fn increment_rect(&self) -> Rect {
+        let rect = self.rect.get();
+        Rect::new(
+            rect.x + rect.width as i32 - BUTTON_WIDTH as i32,
+            rect.y,
+            BUTTON_WIDTH,
+            rect.height / 2,
+        )
+    }
+
+    fn decrement_rect(&self) -> Rect {
+        let rect = self.rect.get();
+        Rect::new(
+            rect.x + rect.width as i32 - BUTTON_WIDTH as i32,
+            rect.y + rect.height as i32 / 2,
+            BUTTON_WIDTH,
+            rect.height - rect.height / 2,
+        )
+    }
+
+    fn draw_button(&self, renderer: &mut dyn Renderer, rect: Rect, c: char, pressed: bool, theme: &Theme) {
+        //TODO: set this selector as the child of the text box selector
+        let mut selector = Selector::new(Some("spin-box-button"));
+        if pressed {
+            selector = selector.with_pseudo_class("active");
+        }
+
+        draw_box(renderer, rect, theme, &selector);
+        renderer.char(
+            rect.x + (rect.width as i32 - 8) / 2,
+            rect.y + (rect.height as i32 - 16) / 2,
+            c,
+            theme.color("color", &selector),
+        );
+    }
+}
+
+impl Change for SpinBox {
+    type Value = i32;
+
+    fn emit_change(&self, value: i32) {
+        if let Some(ref change_callback) = *self.change_callback.borrow() {
+            change_callback(self, value);
+        }
+    }
+
+    fn on_change<T: Fn(&Self, i32) + 'static>(&self, func: T) -> &Self {
+        *self.change_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+}
+
+impl Place for SpinBox {}
+
+impl Style for SpinBox {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.text_box.selector
+    }
+}
+
+impl Widget for SpinBox {
+    fn name(&self) -> &str {
+        "SpinBox"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn arrange(&self) {
+        let rect = self.rect.get();
+        self.text_box.rect.set(Rect::new(
+            rect.x,
+            rect.y,
+            rect.width.saturating_sub(BUTTON_WIDTH),
+            rect.height,
+        ));
+    }
+
+    fn update(&self) {
+        self.text_box.update();
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+        self.text_box.draw(renderer, focused, theme);
+
+        let pressed = self.pressed.get();
+        self.draw_button(renderer, self.increment_rect(), '+', pressed > 0, theme);
+        self.draw_button(renderer, self.decrement_rect(), '-', pressed < 0, theme);
+    }
+
+    fn event(&self, event: Event, mut focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                let direction = if self.increment_rect().contains(point) {
+                    1
+                } else if self.decrement_rect().contains(point) {
+                    -1
+                } else {
+                    0
+                };
+
+                if direction != 0 {
+                    if left_button {
+                        if self.pressed.check_set(direction) {
+                            *redraw = true;
+                        }
+                    } else if self.pressed.get() == direction {
+                        self.pressed.set(0);
+                        if self.change_value(self.value.get().saturating_add(direction * self.step.get())) {
+                            self.update_text();
+                        }
+                        focused = true;
+                        *redraw = true;
+                    }
+
+                    *caught = true;
+                    return focused;
+                }
+
+                if !left_button && self.pressed.check_set(0) {
+                    *redraw = true;
+                }
+            }
+            Event::KeyPressed(key_event) if focused => {
+                let step = self.step.get();
+                let delta = match key_event.scancode {
+                    orbclient::K_UP => Some(step),
+                    orbclient::K_DOWN => Some(-step),
+                    orbclient::K_PGUP => Some(step.saturating_mul(10)),
+                    orbclient::K_PGDN => Some(-step.saturating_mul(10)),
+                    _ => None,
+                };
+
+                if let Some(delta) = delta {
+                    if self.change_value(self.value.get().saturating_add(delta)) {
+                        self.update_text();
+                        *redraw = true;
+                    }
+                    return focused;
+                }
+
+                // finish the input by showing the value that is actually used
+                if key_event.scancode == orbclient::K_ENTER {
+                    self.update_text();
+                    *redraw = true;
+                    return focused;
+                }
+            }
+            _ => (),
+        }
+
+        focused = self.text_box.event(event, focused, redraw, caught);
+
+        // take over typed values as soon as they are complete and in range
+        let typed_value = self.text_box.text.borrow().parse::<i32>().ok();
+        if let Some(typed_value) = typed_value {
+            if self.clamp(typed_value) == typed_value {
+                self.change_value(typed_value);
+            }
+        }
+
+        focused
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
@@ -0,0 +1,384 @@
+use orbclient::Renderer;
+use std::cell::{Cell, RefCell};
+use std::cmp::max;
+use std::sync::Arc;
+use orbclient;
+
+use cell::{CheckSet, CloneCell};
+use draw::{draw_box, ClipRenderer};
+use event::Event;
+use point::Point;
+use rect::Rect;
+use thickness::Thickness;
+use theme::{Selector, Theme};
+use traits::{Change, Place, Style};
+use widgets::{HorizontalPlacement, VerticalPlacement, Widget};
+
+const HEADER_HEIGHT: u32 = 28;
+const HEADER_PADDING: u32 = 12;
+const CLOSE_BUTTON_WIDTH: u32 = 16;
+
+struct Tab {
+    header: String,
+    content: Arc<dyn Widget>,
+}
+
+/// Shows a row of tab headers and the content widget of the selected tab.
+/// Ctrl+Tab and Ctrl+Shift+Tab switch between the tabs.
+pub struct TabControl {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub selector: CloneCell<Selector>,
+    tabs: RefCell<Vec<Tab>>,
+    selected: Cell<Option<usize>>,
+    /// Show a close button on every tab header
+    pub closable: Cell<bool>,
+    change_callback: RefCell<Option<Arc<dyn Fn(&TabControl, Option<usize>)>>>,
+    hover: Cell<Option<usize>>,
+    pressed: Cell<Option<usize>>,
+}
+
+impl TabControl {
+    pub fn new() -> Arc<Self> {
+        Arc::new(TabControl {
+            rect: Cell::new(Rect::default()),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector: CloneCell::new(Selector::new(Some("tab-control"))),
+            tabs: RefCell::new(vec![]),
+            selected: Cell::new(None),
+            closable: Cell::new(false),
+            change_callback: RefCell::new(None),
+            hover: Cell::new(None),
+            pressed: Cell::new(None),
+        })
+    }
+
+    /// Add a tab with the given header. The first tab that is added gets selected.
+    /// Returns the index of the new tab.
+    pub fn add_tab<S: Into<String>, T: Widget>(&self, header: S, content: &Arc<T>) -> usize {
+        let index = {
+            let mut tabs = self.tabs.borrow_mut();
+            tabs.push(Tab {
+                header: header.into(),
+                content: content.clone(),
+            });
+            tabs.len() - 1
+        };
+
+        if self.selected.get().is_none() {
+            self.selected.set(Some(index));
+        }
+
+        self.arrange();
+        index
+    }
+
+    /// Remove the tab with the given index. If it was selected, its neighbour gets selected.
+    pub fn remove_tab(&self, index: usize) {
+        let len = {
+            let mut tabs = self.tabs.borrow_mut();
+            if index >= tabs.len() {
+                return;
+            }
+            tabs.remove(index);
+            tabs.len()
+        };
+
+        if let Some(selected) = self.selected.get() {
+            if len == 0 {
+                self.selected.set(None);
+            } else if selected > index || selected == len {
+                self.selected.set(Some(selected - 1));
+            }
+        }
+
+        self.arrange();
+    }
+
+    pub fn closable(&self, closable: bool) -> &Self {
+        self.closable.set(closable);
+        self
+    }
+
+    /// Select the tab with the given index without emitting a change.
+    pub fn select(&self, index: usize) -> &Self {
+        if index < self.len() {
+            self.selected.set(Some(index));
+            self.arrange();
+        }
+        self
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected.get()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.borrow().len()
+    }
+
+    fn change_selection(&self, index: usize) {
+        if self.selected.check_set(Some(index)) {
+            self.arrange();
+            self.emit_change(Some(index));
+        }
+    }
+
+    fn close_tab(&self, index: usize) {
+        let selected = self.selected.get();
+        self.remove_tab(index);
+        if self.selected.get() != selected || selected == Some(index) {
+            self.emit_change(self.selected.get());
+        }
+    }
+
+    fn header_rects(&self) -> Vec<Rect> {
+        let rect = self.rect.get();
+        let close_width = if self.closable.get() { CLOSE_BUTTON_WIDTH } else { 0 };
+
+        let mut x = rect.x;
+        self.tabs
+            .borrow()
+            .iter()
+            .map(|tab| {
+                let width = tab.header.chars().count() as u32 * 8 + 2 * HEADER_PADDING + close_width;
+                let header_rect = Rect::new(x, rect.y, width, HEADER_HEIGHT);
+                x += width as i32;
+                header_rect
+            })
+            .collect()
+    }
+
+    fn close_rect(header_rect: Rect) -> Rect {
+        Rect::new(
+            header_rect.x + header_rect.width as i32 - HEADER_PADDING as i32 - CLOSE_BUTTON_WIDTH as i32 / 2,
+            header_rect.y + (HEADER_HEIGHT as i32 - 16) / 2,
+            CLOSE_BUTTON_WIDTH,
+            16,
+        )
+    }
+
+    /// The area below the headers the content of the selected tab is shown in.
+    fn panel_rect(&self) -> Rect {
+        let rect = self.rect.get();
+        Rect::new(
+            rect.x,
+            rect.y + HEADER_HEIGHT as i32,
+            rect.width,
+            rect.height.saturating_sub(HEADER_HEIGHT),
+        )
+    }
+
+    fn selected_content(&self) -> Option<Arc<dyn Widget>> {
+        self.selected
+            .get()
+            .and_then(|index| self.tabs.borrow().get(index).map(|tab| tab.content.clone()))
+    }
+}
+
+impl Change for TabControl {
+    type Value = Option<usize>;
+
+    fn emit_change(&self, selected: Option<usize>) {
+        if let Some(ref change_callback) = *self.change_callback.borrow() {
+            change_callback(self, selected);
+        }
+    }
+
+    fn on_change<T: Fn(&Self, Option<usize>) + 'static>(&self, func: T) -> &Self {
+        *self.change_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+}
+
+impl Place for TabControl {}
+
+impl Style for TabControl {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.selector
+    }
+}
+
+impl Widget for TabControl {
+    fn name(&self) -> &str {
+        "TabControl"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn arrange(&self) {
+        let panel = self.panel_rect();
+
+        for tab in self.tabs.borrow().iter() {
+            let content = &tab.content;
+            let position = content.local_position().get();
+            let margin = content.margin().get();
+            let mut content_rect = content.rect().get();
+
+            content_rect.x = panel.x + position.x + margin.left;
+            content_rect.y = panel.y + position.y + margin.top;
+            if content.horizontal_placement().get() == HorizontalPlacement::Stretch {
+                content_rect.x = panel.x + margin.left;
+                content_rect.width = max(0, panel.width as i32 - margin.left - margin.right) as u32;
+            }
+            if content.vertical_placement().get() == VerticalPlacement::Stretch {
+                content_rect.y = panel.y + margin.top;
+                content_rect.height = max(0, panel.height as i32 - margin.top - margin.bottom) as u32;
+            }
+
+            content.rect().set(content_rect);
+            content.arrange();
+        }
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+        fn draw_widget(widget: &Arc<dyn Widget>, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+            widget.update();
+            widget.draw(renderer, focused, theme);
+
+            for child in widget.children().borrow().iter() {
+                draw_widget(child, renderer, focused, theme);
+            }
+        }
+
+        let panel = self.panel_rect();
+        draw_box(renderer, panel, theme, &self.selector.get());
+
+        let selected = self.selected.get();
+        let hover = self.hover.get();
+        let closable = self.closable.get();
+
+        for (i, (tab, header_rect)) in self.tabs.borrow().iter().zip(self.header_rects()).enumerate() {
+            //TODO: set this selector as the child of self.selector
+            let mut selector = Selector::new(Some("tab"));
+            if selected == Some(i) {
+                selector = selector.with_pseudo_class("active");
+            } else if hover == Some(i) {
+                selector = selector.with_pseudo_class("hover");
+            }
+
+            draw_box(renderer, header_rect, theme, &selector);
+
+            let color = theme.color("color", &selector);
+            let mut x = header_rect.x + HEADER_PADDING as i32;
+            let y = header_rect.y + (HEADER_HEIGHT as i32 - 16) / 2;
+            for c in tab.header.chars() {
+                renderer.char(x, y, c, color);
+                x += 8;
+            }
+
+            if closable {
+                let close_rect = TabControl::close_rect(header_rect);
+                renderer.char(close_rect.x + (close_rect.width as i32 - 8) / 2, close_rect.y, 'x', color);
+            }
+        }
+
+        if let Some(content) = self.selected_content() {
+            let mut clip_renderer = ClipRenderer::new(renderer, panel);
+            draw_widget(&content, &mut clip_renderer, focused, theme);
+        }
+    }
+
+    fn event(&self, event: Event, mut focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                let header_rects = self.header_rects();
+                let index = header_rects.iter().position(|rect| rect.contains(point));
+
+                if self.hover.check_set(index) {
+                    *redraw = true;
+                }
+
+                if let Some(index) = index {
+                    if left_button {
+                        self.pressed.set(Some(index));
+                    } else if self.pressed.get() == Some(index) {
+                        self.pressed.set(None);
+
+                        if self.closable.get() && TabControl::close_rect(header_rects[index]).contains(point) {
+                            self.close_tab(index);
+                        } else {
+                            self.change_selection(index);
+                        }
+
+                        focused = true;
+                        *redraw = true;
+                    }
+
+                    *caught = true;
+                    return focused;
+                }
+
+                if !left_button {
+                    self.pressed.set(None);
+                }
+            }
+            Event::KeyPressed(key_event) if focused => {
+                if key_event.scancode == orbclient::K_TAB && key_event.modifiers.ctrl {
+                    let len = self.len();
+                    if let Some(selected) = self.selected.get() {
+                        if key_event.modifiers.shift {
+                            self.change_selection((selected + len - 1) % len);
+                        } else {
+                            self.change_selection((selected + 1) % len);
+                        }
+                        *redraw = true;
+                    }
+                    return focused;
+                }
+            }
+            _ => (),
+        }
+
+        if let Some(content) = self.selected_content() {
+            // the content only sees the mouse while it is over the panel
+            let forward = match event {
+                Event::Mouse { point, .. } => {
+                    self.panel_rect().contains(point) || !content.rect().get().contains(point)
+                }
+                _ => true,
+            };
+
+            if forward {
+                focused = content.event(event, focused, redraw, caught);
+            }
+        }
+
+        focused
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+    fn visible_children(&self) -> Vec<Arc<dyn Widget>> {
+        let mut children = self.children.borrow().clone();
+        children.extend(self.selected_content());
+        children
+    }
+}
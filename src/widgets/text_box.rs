@@ -4,16 +4,19 @@ use std::cmp::{max, min};
 use std::ops::Deref;
 use std::sync::Arc;
 use orbclient;
+use unicode_segmentation::UnicodeSegmentation;
 
 use cell::{CheckSet, CloneCell};
+use clipboard;
 use draw::draw_box;
 use event::Event;
 use point::Point;
 use rect::Rect;
 use thickness::Thickness;
 use theme::{Selector, Theme};
-use traits::{Click, Enter, EventFilter, Place, Style, Text};
-use widgets::{Widget, VerticalPlacement, HorizontalPlacement};
+use traits::{Change, Click, Enter, EventFilter, Place, Style, Text};
+use validator::Validator;
+use widgets::{Action, Menu, Separator, Widget, VerticalPlacement, HorizontalPlacement};
 
 /// Find next character index
 fn next_i(text: &str, text_i: usize) -> usize {
@@ -32,6 +35,95 @@ fn prev_i(text: &str, text_i: usize) -> usize {
     slice.char_indices().rev().next().unwrap_or((0, '\0')).0
 }
 
+/// Check if a segment between two word boundaries is a word
+fn is_word(segment: &str) -> bool {
+    segment.chars().any(|c| c.is_alphanumeric())
+}
+
+/// Find the end index of the next word
+fn next_word_i(text: &str, text_i: usize) -> usize {
+    text.split_word_bound_indices()
+        .map(|(i, segment)| (i + segment.len(), segment))
+        .find(|&(end, segment)| end > text_i && is_word(segment))
+        .map(|(end, _)| end)
+        .unwrap_or(text.len())
+}
+
+/// Find the start index of the last word
+fn prev_word_i(text: &str, text_i: usize) -> usize {
+    text.split_word_bound_indices()
+        .rev()
+        .find(|&(i, segment)| i < text_i && is_word(segment))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Find the character index in the line above
+fn up_i(text: &str, mut text_i: usize) -> usize {
+    // Count back to last newline
+    let mut offset = 0;
+    while text_i > 0 {
+        let c = text[..text_i].chars().rev().next();
+        text_i = prev_i(text, text_i);
+        if c == Some('\n') {
+            break;
+        }
+        offset += 1;
+    }
+
+    // Go to newline before last newline
+    while text_i > 0 {
+        if text[..text_i].chars().rev().next() == Some('\n') {
+            break;
+        }
+        text_i = prev_i(text, text_i);
+    }
+
+    // Add back offset
+    while offset > 0 && text_i < text.len() {
+        if text[text_i..].chars().next() == Some('\n') {
+            break;
+        }
+        text_i = next_i(text, text_i);
+        offset -= 1;
+    }
+
+    text_i
+}
+
+/// Find the character index in the line below
+fn down_i(text: &str, mut text_i: usize) -> usize {
+    // Count back to last newline
+    let mut offset = 0;
+    while text_i > 0 {
+        if text[..text_i].chars().rev().next() == Some('\n') {
+            break;
+        }
+        text_i = prev_i(text, text_i);
+        offset += 1;
+    }
+
+    // Go to next newline
+    while text_i < text.len() {
+        let c = text[text_i..].chars().next();
+        text_i = next_i(text, text_i);
+        if c == Some('\n') {
+            break;
+        }
+    }
+
+    // Add back offset
+    while offset > 0 && text_i < text.len() {
+        if text[text_i..].chars().next() == Some('\n') {
+            break;
+        }
+        text_i = next_i(text, text_i);
+        offset -= 1;
+    }
+
+    text_i
+}
+
 pub struct TextBox {
     pub rect: Cell<Rect>,
     children: RefCell<Vec<Arc<dyn Widget>>>,
@@ -42,12 +134,21 @@ pub struct TextBox {
     pub selector: CloneCell<Selector>,
     pub text: CloneCell<String>,
     pub text_i: Cell<usize>,
+    /// The other end of the selection, the text between it and the caret is selected.
+    pub selection_anchor: Cell<Option<usize>>,
     pub text_offset: Cell<Point>,
     pub scroll_offset: Cell<(i32, i32)>,
     pub mask_char: Cell<Option<char>>,
+    /// Text that is shown while the text box is empty and not focused.
+    pub placeholder: CloneCell<String>,
+    /// A read only text box allows caret movement but rejects edits.
+    pub read_only: Cell<bool>,
     pub grab_focus: Cell<bool>,
     pub click_callback: RefCell<Option<Arc<dyn Fn(&TextBox, Point)>>>,
     pub enter_callback: RefCell<Option<Arc<dyn Fn(&TextBox)>>>,
+    pub change_callback: RefCell<Option<Arc<dyn Fn(&TextBox, String)>>>,
+    /// Describes the input the text box accepts. Invalid input sets the `invalid` pseudo-class.
+    pub validator: RefCell<Validator>,
     /// If event_filter is defined, all of the events will go trough it
     /// Instead of the default behavior. This allows defining fields that
     /// ex. will only accept numbers and ignore all else, or add some
@@ -58,12 +159,14 @@ pub struct TextBox {
     /// handler deal with it.
     pub event_filter:
         RefCell<Option<Arc<dyn Fn(&TextBox, Event, &mut bool, &mut bool) -> Option<Event>>>>,
+    /// The menu with Cut, Copy, Paste and Select All that is shown on a right click.
+    pub context_menu: RefCell<Option<Arc<Menu>>>,
     pressed: Cell<bool>,
 }
 
 impl TextBox {
     pub fn new() -> Arc<Self> {
-        Arc::new(TextBox {
+        let text_box = Arc::new(TextBox {
             rect: Cell::new(Rect::default()),
             local_position: Cell::new(Point::new(0, 0)),
             vertical_placement: Cell::new(VerticalPlacement::Absolute),
@@ -73,15 +176,57 @@ impl TextBox {
             selector: CloneCell::new(Selector::new(Some("text-box"))),
             text: CloneCell::new(String::new()),
             text_i: Cell::new(0),
+            selection_anchor: Cell::new(None),
             text_offset: Cell::new(Point::default()),
             scroll_offset: Cell::new((0, 0)),
             mask_char: Cell::new(None),
+            placeholder: CloneCell::new(String::new()),
+            read_only: Cell::new(false),
             grab_focus: Cell::new(false),
             click_callback: RefCell::new(None),
             enter_callback: RefCell::new(None),
+            change_callback: RefCell::new(None),
+            validator: RefCell::new(Validator::new()),
             event_filter: RefCell::new(None),
+            context_menu: RefCell::new(None),
             pressed: Cell::new(false),
-        })
+        });
+
+        *text_box.context_menu.borrow_mut() = Some(TextBox::edit_menu(&text_box));
+
+        text_box
+    }
+
+    /// Create the default context menu.
+    fn edit_menu(text_box: &Arc<TextBox>) -> Arc<Menu> {
+        let menu = Menu::new("");
+
+        let entries: [(&str, &str, fn(&TextBox)); 4] = [
+            ("Cu&t", "Ctrl+X", TextBox::cut),
+            ("&Copy", "Ctrl+C", TextBox::copy),
+            ("&Paste", "Ctrl+V", TextBox::paste),
+            ("Select &All", "Ctrl+A", TextBox::select_all),
+        ];
+
+        for (i, &(text, shortcut, func)) in entries.iter().enumerate() {
+            if i == 3 {
+                menu.add(&Separator::new());
+            }
+
+            let action = Action::new(text);
+            action.shortcut(shortcut.parse().ok());
+
+            let weak_text_box = Arc::downgrade(text_box);
+            action.on_click(move |_action: &Action, _point: Point| {
+                if let Some(text_box) = weak_text_box.upgrade() {
+                    func(&text_box);
+                }
+            });
+
+            menu.add(&action);
+        }
+
+        menu
     }
 
     pub fn grab_focus(&self, grab_focus: bool) -> &Self {
@@ -93,6 +238,197 @@ impl TextBox {
         self.mask_char.set(mask_char);
         self
     }
+
+    pub fn placeholder<S: Into<String>>(&self, placeholder: S) -> &Self {
+        self.placeholder.set(placeholder.into());
+        self
+    }
+
+    pub fn read_only(&self, read_only: bool) -> &Self {
+        self.read_only.set(read_only);
+        self
+    }
+
+    pub fn validator(&self, validator: Validator) -> &Self {
+        *self.validator.borrow_mut() = validator;
+        self
+    }
+
+    /// Check if the current text is accepted by the validator.
+    pub fn is_valid(&self) -> bool {
+        self.validator.borrow().is_valid(&self.text.borrow())
+    }
+
+    /// Get the start and the end of the selection, None if nothing is selected.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.selection_anchor.get()?;
+        let text_i = self.text_i.get();
+        if anchor == text_i {
+            None
+        } else {
+            Some((min(anchor, text_i), max(anchor, text_i)))
+        }
+    }
+
+    pub fn selected_text(&self) -> String {
+        match self.selection() {
+            Some((start, end)) => self.text.borrow()[start..end].to_owned(),
+            None => String::new(),
+        }
+    }
+
+    pub fn select_all(&self) {
+        self.selection_anchor.set(Some(0));
+        self.text_i.set(self.text.borrow().len());
+        self.scroll_to_caret();
+    }
+
+    /// Copy the selected text to the clipboard. Masked text is never copied.
+    pub fn copy(&self) {
+        if self.mask_char.get().is_none() && self.selection().is_some() {
+            clipboard::set_text(self.selected_text());
+        }
+    }
+
+    /// Move the selected text to the clipboard.
+    pub fn cut(&self) {
+        if self.read_only.get() || self.mask_char.get().is_some() {
+            return;
+        }
+
+        self.copy();
+        let text_i = self.delete_selection(&mut self.text.borrow_mut());
+        if let Some(text_i) = text_i {
+            self.text_i.set(text_i);
+            self.scroll_to_caret();
+            self.emit_change(self.text.get());
+        }
+    }
+
+    /// Replace the selected text with the text of the clipboard, characters that are
+    /// rejected by the validator are skipped.
+    pub fn paste(&self) {
+        if self.read_only.get() {
+            return;
+        }
+
+        let pasted = clipboard::text();
+        let text_i = {
+            let mut text = self.text.borrow_mut();
+            let deleted = self.delete_selection(&mut text);
+
+            let mut text_i = deleted.unwrap_or(self.text_i.get());
+            let mut changed = deleted.is_some();
+            for c in pasted.chars() {
+                if let Some(next_i) = self.validator.borrow().insert(&mut text, text_i, c) {
+                    text_i = next_i;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                return;
+            }
+            text_i
+        };
+
+        self.text_i.set(text_i);
+        self.scroll_to_caret();
+        self.emit_change(self.text.get());
+    }
+
+    /// Remove the selected text. Returns the start of the removed text if something was selected
+    /// and the validator accepts the text without it.
+    fn delete_selection(&self, text: &mut String) -> Option<usize> {
+        let (start, end) = self.selection()?;
+        if !self.delete_range(text, start, end) {
+            return None;
+        }
+
+        self.selection_anchor.set(None);
+        Some(start)
+    }
+
+    /// Remove the text between the byte indices if the validator accepts the rest,
+    /// e.g. a deleted literal of a mask would move the following characters out of place.
+    fn delete_range(&self, text: &mut String, start: usize, end: usize) -> bool {
+        let mut new_text = text.clone();
+        new_text.drain(start..end);
+        if !self.validator.borrow().accepts(&new_text) {
+            return false;
+        }
+
+        *text = new_text;
+        true
+    }
+
+    /// Scroll the text so that the caret is visible.
+    fn scroll_to_caret(&self) {
+        let text_i = self.text_i.get();
+        let text = self.text.borrow();
+
+        let text_offset = self.text_offset.get();
+        let mut scroll_offset = self.scroll_offset.get();
+
+        let mut col = 0;
+        let mut row = 0;
+        for (i, c) in text.char_indices() {
+            if c == '\n' {
+                if i == text_i {
+                    break;
+                }
+
+                col = 0;
+                row += 1;
+            } else if c == '\t' {
+                if i == text_i {
+                    break;
+                }
+
+                col += 4;
+            } else {
+                if i == text_i {
+                    break;
+                }
+
+                col += 1;
+            }
+        }
+
+        let rect = self.rect.get();
+        let cols = (rect.width as i32 - text_offset.x) / 8;
+        let rows = (rect.height as i32 - text_offset.y) / 16;
+
+        if col < scroll_offset.0 {
+            scroll_offset.0 = col;
+        }
+        if col >= scroll_offset.0 + cols {
+            scroll_offset.0 = col + 1 - cols;
+        }
+        if row < scroll_offset.1 {
+            scroll_offset.1 = row;
+        }
+        if row >= scroll_offset.1 + rows {
+            scroll_offset.1 = row + 1 - rows;
+        }
+
+        self.scroll_offset.set(scroll_offset);
+    }
+}
+
+impl Change for TextBox {
+    type Value = String;
+
+    fn emit_change(&self, text: String) {
+        if let Some(ref change_callback) = *self.change_callback.borrow() {
+            change_callback(self, text);
+        }
+    }
+
+    fn on_change<T: Fn(&Self, String) + 'static>(&self, func: T) -> &Self {
+        *self.change_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
 }
 
 impl Click for TextBox {
@@ -145,6 +481,7 @@ impl Text for TextBox {
     fn text<S: Into<String>>(&self, text: S) -> &Self {
         let text = text.into();
         self.text_i.set(text.len());
+        self.selection_anchor.set(None);
         self.text.set(text);
         self
     }
@@ -186,6 +523,20 @@ impl Widget for TextBox {
         &self.margin
     }
 
+    fn update(&self) {
+        if self.is_valid() {
+            self.without_pseudo_class("invalid");
+        } else {
+            self.with_pseudo_class("invalid");
+        }
+
+        if self.read_only.get() {
+            self.with_pseudo_class("read-only");
+        } else {
+            self.without_pseudo_class("read-only");
+        }
+    }
+
     fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
         let rect = self.rect.get();
 
@@ -203,6 +554,27 @@ impl Widget for TextBox {
         let text_offset = self.text_offset.get();
         let scroll_offset = self.scroll_offset.get();
 
+        if text.is_empty() && !focused {
+            //TODO: set this selector as the child of self.selector
+            let placeholder_selector = selector.clone().with_pseudo_class("placeholder");
+            let mut x = text_offset.x;
+            for c in self.placeholder.borrow().chars() {
+                let c_r = Rect::new(x + rect.x, text_offset.y + rect.y, 8, 16);
+                if rect.contains_rect(&c_r) {
+                    renderer.char(c_r.x, c_r.y, c, theme.color("color", &placeholder_selector));
+                }
+                x += 8;
+            }
+        }
+
+        //TODO: set this selector as the child of self.selector
+        let selection_selector = Selector::new(Some("text-selection"));
+        let selection = self.selection();
+        let selected = |i: usize| match selection {
+            Some((start, end)) => i >= start && i < end,
+            None => false,
+        };
+
         let mut x = text_offset.x - scroll_offset.0 * 8;
         let mut y = text_offset.y - scroll_offset.1 * 16;
         let start_x = x;
@@ -224,6 +596,10 @@ impl Widget for TextBox {
             } else if c == '\t' {
                 c_r.width = 8 * 4;
 
+                if selected(i) && rect.contains_rect(&c_r) {
+                    draw_box(renderer, c_r, theme, &selection_selector);
+                }
+
                 if focused && i == text_i && rect.contains_rect(&c_r) {
                     //TODO: set this selector as the child of self.selector
                     draw_box(
@@ -237,6 +613,10 @@ impl Widget for TextBox {
                 x += c_r.width as i32;
             } else {
                 if rect.contains_rect(&c_r) {
+                    if selected(i) {
+                        draw_box(renderer, c_r, theme, &selection_selector);
+                    }
+
                     if i == text_i && focused {
                         //TODO: set this selector as the child of self.selector
                         draw_box(
@@ -278,6 +658,8 @@ impl Widget for TextBox {
         // If the event wasn't handled by the custom handler.
         if let Some(event) = self.handle_event(event, &mut focused, redraw) {
             let mut new_text_i = None;
+            let mut selecting = false;
+            let mut changed = false;
             match event {
                 Event::Mouse {
                     point, left_button, ..
@@ -372,42 +754,91 @@ impl Widget for TextBox {
                     *redraw = true;
                 }
                 Event::KeyPressed(key_event) => match key_event.scancode {
+                    orbclient::K_A if key_event.modifiers.ctrl => if focused {
+                        self.select_all();
+                        *redraw = true;
+                    },
+                    orbclient::K_C if key_event.modifiers.ctrl => if focused {
+                        self.copy();
+                    },
+                    orbclient::K_X if key_event.modifiers.ctrl => if focused {
+                        self.cut();
+                        *redraw = true;
+                    },
+                    orbclient::K_V if key_event.modifiers.ctrl => if focused {
+                        self.paste();
+                        *redraw = true;
+                    },
                     orbclient::K_ENTER => if focused {
                         if self.enter_callback.borrow().is_some() {
                             self.emit_enter();
                             *redraw = true;
-                        } else {
+                        } else if !self.read_only.get() {
                             let mut text = self.text.borrow_mut();
-                            let text_i = self.text_i.get();
-                            text.insert(text_i, '\n');
-                            new_text_i = Some(next_i(text.deref(), text_i));
+                            let deleted = self.delete_selection(&mut text);
+                            let text_i = deleted.unwrap_or(self.text_i.get());
+                            new_text_i = self.validator.borrow().insert(&mut text, text_i, '\n').or(deleted);
+                            changed = new_text_i.is_some();
                         }
                     },
-                    orbclient::K_BKSP => if focused {
+                    orbclient::K_BKSP => if focused && !self.read_only.get() {
                         let mut text = self.text.borrow_mut();
-                        let mut text_i = self.text_i.get();
+                        let text_i = self.text_i.get();
 
-                        if text_i > 0 {
-                            text_i = prev_i(text.deref(), text_i);
+                        if self.selection().is_some() {
+                            if let Some(start) = self.delete_selection(&mut text) {
+                                new_text_i = Some(start);
+                                changed = true;
+                            }
+                        } else if key_event.modifiers.ctrl {
+                            let word_i = prev_word_i(text.deref(), text_i);
+                            if word_i < text_i && self.delete_range(&mut text, word_i, text_i) {
+                                new_text_i = Some(word_i);
+                                changed = true;
+                            }
+                        } else if text_i > 0 {
+                            let text_i = prev_i(text.deref(), text_i);
                             if text_i < text.len() {
-                                text.remove(text_i);
-                                new_text_i = Some(min(text_i, text.len()));
+                                let end = next_i(text.deref(), text_i);
+                                if self.delete_range(&mut text, text_i, end) {
+                                    new_text_i = Some(min(text_i, text.len()));
+                                    changed = true;
+                                }
                             }
                         }
                     },
-                    orbclient::K_DEL => if focused {
+                    orbclient::K_DEL => if focused && !self.read_only.get() {
                         let mut text = self.text.borrow_mut();
                         let text_i = self.text_i.get();
 
-                        if text_i < text.len() {
-                            text.remove(text_i);
-                            new_text_i = Some(min(text_i, text.len()));
+                        if self.selection().is_some() {
+                            if let Some(start) = self.delete_selection(&mut text) {
+                                new_text_i = Some(start);
+                                changed = true;
+                            }
+                        } else if key_event.modifiers.ctrl {
+                            let word_i = next_word_i(text.deref(), text_i);
+                            if text_i < word_i && self.delete_range(&mut text, text_i, word_i) {
+                                new_text_i = Some(text_i);
+                                changed = true;
+                            }
+                        } else if text_i < text.len() {
+                            let end = next_i(text.deref(), text_i);
+                            if self.delete_range(&mut text, text_i, end) {
+                                new_text_i = Some(min(text_i, text.len()));
+                                changed = true;
+                            }
                         }
                     },
                     orbclient::K_HOME => if focused {
+                        selecting = key_event.modifiers.shift;
                         let text = self.text.borrow();
                         let mut text_i = self.text_i.get();
 
+                        if key_event.modifiers.ctrl {
+                            text_i = 0;
+                        }
+
                         while text_i > 0 {
                             if text[..text_i].chars().rev().next() == Some('\n') {
                                 break;
@@ -418,9 +849,14 @@ impl Widget for TextBox {
                         new_text_i = Some(text_i);
                     },
                     orbclient::K_END => if focused {
+                        selecting = key_event.modifiers.shift;
                         let text = self.text.borrow();
                         let mut text_i = self.text_i.get();
 
+                        if key_event.modifiers.ctrl {
+                            text_i = text.len();
+                        }
+
                         while text_i < text.len() {
                             if text[text_i..].chars().next() == Some('\n') {
                                 break;
@@ -430,99 +866,67 @@ impl Widget for TextBox {
 
                         new_text_i = Some(text_i);
                     },
-                    orbclient::K_UP => {
-                        if focused {
-                            let text = self.text.borrow();
-                            let mut text_i = self.text_i.get();
-
-                            // Count back to last newline
-                            let mut offset = 0;
-                            while text_i > 0 {
-                                let c = text[..text_i].chars().rev().next();
-                                text_i = prev_i(text.deref(), text_i);
-                                if c == Some('\n') {
-                                    break;
-                                }
-                                offset += 1;
-                            }
-
-                            // Go to newline before last newline
-                            while text_i > 0 {
-                                if text[..text_i].chars().rev().next() == Some('\n') {
-                                    break;
-                                }
-                                text_i = prev_i(text.deref(), text_i);
-                            }
-
-                            // Add back offset
-                            while offset > 0 && text_i < text.len() {
-                                if text[text_i..].chars().next() == Some('\n') {
-                                    break;
-                                }
-                                text_i = next_i(text.deref(), text_i);
-                                offset -= 1;
-                            }
+                    orbclient::K_UP => if focused {
+                        selecting = key_event.modifiers.shift;
+                        let text = self.text.borrow();
+                        new_text_i = Some(up_i(text.deref(), self.text_i.get()));
+                    },
+                    orbclient::K_DOWN => if focused {
+                        selecting = key_event.modifiers.shift;
+                        let text = self.text.borrow();
+                        new_text_i = Some(down_i(text.deref(), self.text_i.get()));
+                    },
+                    orbclient::K_PGUP => if focused {
+                        selecting = key_event.modifiers.shift;
+                        let text = self.text.borrow();
+                        let mut text_i = self.text_i.get();
 
-                            new_text_i = Some(text_i);
+                        for _ in 0..max(1, self.rect.get().height / 16) {
+                            text_i = up_i(text.deref(), text_i);
                         }
-                    }
-                    orbclient::K_DOWN => {
-                        if focused {
-                            let text = self.text.borrow();
-                            let mut text_i = self.text_i.get();
 
-                            // Count back to last newline
-                            let mut offset = 0;
-                            while text_i > 0 {
-                                if text[..text_i].chars().rev().next() == Some('\n') {
-                                    break;
-                                }
-                                text_i = prev_i(text.deref(), text_i);
-                                offset += 1;
-                            }
-
-                            // Go to next newline
-                            while text_i < text.len() {
-                                let c = text[text_i..].chars().next();
-                                text_i = next_i(text.deref(), text_i);
-                                if c == Some('\n') {
-                                    break;
-                                }
-                            }
-
-                            // Add back offset
-                            while offset > 0 && text_i < text.len() {
-                                if text[text_i..].chars().next() == Some('\n') {
-                                    break;
-                                }
-                                text_i = next_i(text.deref(), text_i);
-                                offset -= 1;
-                            }
+                        new_text_i = Some(text_i);
+                    },
+                    orbclient::K_PGDN => if focused {
+                        selecting = key_event.modifiers.shift;
+                        let text = self.text.borrow();
+                        let mut text_i = self.text_i.get();
 
-                            new_text_i = Some(text_i);
+                        for _ in 0..max(1, self.rect.get().height / 16) {
+                            text_i = down_i(text.deref(), text_i);
                         }
-                    }
+
+                        new_text_i = Some(text_i);
+                    },
                     orbclient::K_LEFT => if focused {
+                        selecting = key_event.modifiers.shift;
                         let text = self.text.borrow();
                         let text_i = self.text_i.get();
 
-                        if text_i > 0 {
+                        if key_event.modifiers.ctrl {
+                            new_text_i = Some(prev_word_i(text.deref(), text_i));
+                        } else if text_i > 0 {
                             new_text_i = Some(prev_i(text.deref(), text_i));
                         }
                     },
                     orbclient::K_RIGHT => if focused {
+                        selecting = key_event.modifiers.shift;
                         let text = self.text.borrow();
                         let text_i = self.text_i.get();
-                        if text_i < text.len() {
+
+                        if key_event.modifiers.ctrl {
+                            new_text_i = Some(next_word_i(text.deref(), text_i));
+                        } else if text_i < text.len() {
                             new_text_i = Some(next_i(text.deref(), text_i));
                         }
                     },
-                    _ => if focused {
+                    _ => if focused && !self.read_only.get() {
                         if let Some(character) = key_event.character {
                             let mut text = self.text.borrow_mut();
-                            let text_i = self.text_i.get();
-                            text.insert(text_i, character);
-                            new_text_i = Some(next_i(text.deref(), text_i));
+                            let deleted = self.delete_selection(&mut text);
+                            let text_i = deleted.unwrap_or(self.text_i.get());
+                            new_text_i = self.validator.borrow().insert(&mut text, text_i, character).or(deleted);
+                            changed = new_text_i.is_some();
                         }
                     },
                 },
@@ -530,57 +934,22 @@ impl Widget for TextBox {
             }
 
             if let Some(text_i) = new_text_i {
-                self.text_i.set(text_i);
-                *redraw = true;
-
-                let text = self.text.borrow();
-
-                let text_offset = self.text_offset.get();
-                let mut scroll_offset = self.scroll_offset.get();
-
-                let mut col = 0;
-                let mut row = 0;
-                for (i, c) in text.char_indices() {
-                    if c == '\n' {
-                        if i == text_i {
-                            break;
-                        }
-
-                        col = 0;
-                        row += 1;
-                    } else if c == '\t' {
-                        if i == text_i {
-                            break;
-                        }
-
-                        col += 4;
-                    } else {
-                        if i == text_i {
-                            break;
-                        }
-
-                        col += 1;
+                // moving the caret with Shift extends the selection, other moves clear it
+                if selecting {
+                    if self.selection_anchor.get().is_none() {
+                        self.selection_anchor.set(Some(self.text_i.get()));
                     }
+                } else {
+                    self.selection_anchor.set(None);
                 }
 
-                let rect = self.rect.get();
-                let cols = (rect.width as i32 - text_offset.x) / 8;
-                let rows = (rect.height as i32 - text_offset.y) / 16;
-
-                if col < scroll_offset.0 {
-                    scroll_offset.0 = col;
-                }
-                if col >= scroll_offset.0 + cols {
-                    scroll_offset.0 = col + 1 - cols;
-                }
-                if row < scroll_offset.1 {
-                    scroll_offset.1 = row;
-                }
-                if row >= scroll_offset.1 + rows {
-                    scroll_offset.1 = row + 1 - rows;
-                }
+                self.text_i.set(text_i);
+                self.scroll_to_caret();
+                *redraw = true;
+            }
 
-                self.scroll_offset.set(scroll_offset);
+            if changed {
+                self.emit_change(self.text.get());
             }
 
             if self.grab_focus.check_set(false) {
@@ -591,6 +960,10 @@ impl Widget for TextBox {
         focused
     }
 
+    fn context_menu(&self) -> Option<Arc<Menu>> {
+        self.context_menu.borrow().clone()
+    }
+
     fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
         &self.children
     }
@@ -0,0 +1,483 @@
+use orbclient::{Color, Renderer};
+use std::cell::{Cell, RefCell};
+use std::cmp::{max, min};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use orbclient;
+
+use cell::{CheckSet, CloneCell};
+use draw::{draw_box, ClipRenderer};
+use event::Event;
+use point::Point;
+use rect::Rect;
+use thickness::Thickness;
+use theme::{Selector, Theme};
+use traits::{Change, Place, Style};
+use widgets::{HorizontalPlacement, VerticalPlacement, Widget};
+
+const ROW_HEIGHT: u32 = 20;
+const INDENT: i32 = 16;
+const DOUBLE_CLICK_TIME: u64 = 500;
+
+/// A node of a tree view. Its children could be added directly or be loaded lazily
+/// when the node is expanded for the first time.
+pub struct TreeNode {
+    pub text: CloneCell<String>,
+    pub expanded: Cell<bool>,
+    children: RefCell<Vec<Arc<TreeNode>>>,
+    loader: RefCell<Option<Arc<dyn Fn(&TreeNode)>>>,
+}
+
+impl TreeNode {
+    pub fn new<S: Into<String>>(text: S) -> Arc<Self> {
+        Arc::new(TreeNode {
+            text: CloneCell::new(text.into()),
+            expanded: Cell::new(false),
+            children: RefCell::new(vec![]),
+            loader: RefCell::new(None),
+        })
+    }
+
+    pub fn add(&self, child: &Arc<TreeNode>) -> &Self {
+        self.children.borrow_mut().push(child.clone());
+        self
+    }
+
+    pub fn clear(&self) {
+        self.children.borrow_mut().clear();
+    }
+
+    /// Set a function that adds the children of the node. It is called once, when
+    /// the node is expanded for the first time.
+    pub fn lazy<T: Fn(&TreeNode) + 'static>(&self, func: T) -> &Self {
+        *self.loader.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+
+    pub fn children(&self) -> Vec<Arc<TreeNode>> {
+        self.children.borrow().clone()
+    }
+
+    /// Check if the node has or could load children.
+    pub fn is_expandable(&self) -> bool {
+        !self.children.borrow().is_empty() || self.loader.borrow().is_some()
+    }
+
+    pub fn expand(&self) {
+        let loader = self.loader.borrow_mut().take();
+        if let Some(loader) = loader {
+            loader(self);
+        }
+
+        self.expanded.set(true);
+    }
+
+    pub fn collapse(&self) {
+        self.expanded.set(false);
+    }
+
+    /// Check if the node is a child or a descendant of a child of this node.
+    fn is_ancestor_of(&self, node: &Arc<TreeNode>) -> bool {
+        self.children
+            .borrow()
+            .iter()
+            .any(|child| Arc::ptr_eq(child, node) || child.is_ancestor_of(node))
+    }
+}
+
+/// A visible row of the tree.
+struct Row {
+    node: Arc<TreeNode>,
+    depth: usize,
+}
+
+/// Shows hierarchical data as a tree of expandable and collapsible nodes.
+///
+/// Up and Down move the selection, Right expands a node or moves to its first child,
+/// Left collapses a node or moves to its parent. Enter or a double click activates
+/// the selected node.
+pub struct TreeView {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub selector: CloneCell<Selector>,
+    roots: RefCell<Vec<Arc<TreeNode>>>,
+    selected: RefCell<Option<Arc<TreeNode>>>,
+    v_scroll: Cell<i32>,
+    change_callback: RefCell<Option<Arc<dyn Fn(&TreeView, Arc<TreeNode>)>>>,
+    activate_callback: RefCell<Option<Arc<dyn Fn(&TreeView, Arc<TreeNode>)>>>,
+    hover: Cell<Option<usize>>,
+    pressed: Cell<bool>,
+    last_click: Cell<Option<(usize, Instant)>>,
+}
+
+impl TreeView {
+    pub fn new() -> Arc<Self> {
+        Arc::new(TreeView {
+            rect: Cell::new(Rect::default()),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector: CloneCell::new(Selector::new(Some("tree-view"))),
+            roots: RefCell::new(vec![]),
+            selected: RefCell::new(None),
+            v_scroll: Cell::new(0),
+            change_callback: RefCell::new(None),
+            activate_callback: RefCell::new(None),
+            hover: Cell::new(None),
+            pressed: Cell::new(false),
+            last_click: Cell::new(None),
+        })
+    }
+
+    /// Add a top level node.
+    pub fn add(&self, node: &Arc<TreeNode>) -> &Self {
+        self.roots.borrow_mut().push(node.clone());
+        self
+    }
+
+    pub fn clear(&self) {
+        self.roots.borrow_mut().clear();
+        *self.selected.borrow_mut() = None;
+        self.v_scroll.set(0);
+    }
+
+    pub fn selected(&self) -> Option<Arc<TreeNode>> {
+        self.selected.borrow().clone()
+    }
+
+    /// Called with the node that is activated by Enter or a double click.
+    pub fn on_activate<T: Fn(&Self, Arc<TreeNode>) + 'static>(&self, func: T) -> &Self {
+        *self.activate_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+
+    fn emit_activate(&self, node: Arc<TreeNode>) {
+        if let Some(ref activate_callback) = *self.activate_callback.borrow() {
+            activate_callback(self, node);
+        }
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        fn push_rows(rows: &mut Vec<Row>, node: &Arc<TreeNode>, depth: usize) {
+            rows.push(Row { node: node.clone(), depth });
+            if node.expanded.get() {
+                for child in node.children.borrow().iter() {
+                    push_rows(rows, child, depth + 1);
+                }
+            }
+        }
+
+        let mut rows = vec![];
+        for root in self.roots.borrow().iter() {
+            push_rows(&mut rows, root, 0);
+        }
+        rows
+    }
+
+    fn selected_index(&self, rows: &[Row]) -> Option<usize> {
+        let selected = self.selected.borrow();
+        selected
+            .as_ref()
+            .and_then(|selected| rows.iter().position(|row| Arc::ptr_eq(&row.node, selected)))
+    }
+
+    fn row_index(&self, point: Point, rows: &[Row]) -> Option<usize> {
+        let rect = self.rect.get();
+        if !rect.contains(point) {
+            return None;
+        }
+
+        let index = ((point.y - rect.y + self.v_scroll.get()) / ROW_HEIGHT as i32) as usize;
+        if index < rows.len() {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    fn select_row(&self, rows: &[Row], index: usize) {
+        let node = rows[index].node.clone();
+        let changed = match *self.selected.borrow() {
+            Some(ref selected) => !Arc::ptr_eq(selected, &node),
+            None => true,
+        };
+
+        if changed {
+            *self.selected.borrow_mut() = Some(node.clone());
+            self.scroll_to_row(index);
+            self.emit_change(node);
+        }
+    }
+
+    fn scroll_to_row(&self, index: usize) {
+        let height = self.rect.get().height as i32;
+        let y = index as i32 * ROW_HEIGHT as i32;
+        let v_scroll = self.v_scroll.get();
+
+        if y < v_scroll {
+            self.v_scroll.set(y);
+        } else if y + ROW_HEIGHT as i32 > v_scroll + height {
+            self.v_scroll.set(y + ROW_HEIGHT as i32 - height);
+        }
+    }
+
+    fn scroll(&self, y: i32) {
+        let content_height = self.rows().len() as i32 * ROW_HEIGHT as i32;
+        let max_scroll = max(0, content_height - self.rect.get().height as i32);
+        self.v_scroll.set(max(0, min(max_scroll, self.v_scroll.get() + y)));
+    }
+
+    /// Collapsing rows could leave empty space at the bottom
+    fn clamp_scroll(&self) {
+        self.scroll(0);
+    }
+
+    fn toggle(&self, node: &Arc<TreeNode>) {
+        if node.expanded.get() {
+            node.collapse();
+
+            // a selected descendant is hidden now, the collapsed node takes over the selection
+            let hidden = match *self.selected.borrow() {
+                Some(ref selected) => node.is_ancestor_of(selected),
+                None => false,
+            };
+            if hidden {
+                let rows = self.rows();
+                if let Some(index) = rows.iter().position(|row| Arc::ptr_eq(&row.node, node)) {
+                    self.select_row(&rows, index);
+                }
+            }
+        } else {
+            node.expand();
+        }
+        self.clamp_scroll();
+    }
+
+    fn draw_expander(&self, renderer: &mut dyn Renderer, x: i32, y: i32, expanded: bool, color: Color) {
+        // a small triangle pointing right or down
+        for i in 0..5 {
+            if expanded {
+                renderer.rect(x + i, y - 2 + i, (9 - 2 * i) as u32, 1, color);
+            } else {
+                renderer.rect(x + 2 + i, y - 4 + i, 1, (9 - 2 * i) as u32, color);
+            }
+        }
+    }
+}
+
+impl Change for TreeView {
+    type Value = Arc<TreeNode>;
+
+    fn emit_change(&self, node: Arc<TreeNode>) {
+        if let Some(ref change_callback) = *self.change_callback.borrow() {
+            change_callback(self, node);
+        }
+    }
+
+    fn on_change<T: Fn(&Self, Arc<TreeNode>) + 'static>(&self, func: T) -> &Self {
+        *self.change_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+}
+
+impl Place for TreeView {}
+
+impl Style for TreeView {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.selector
+    }
+}
+
+impl Widget for TreeView {
+    fn name(&self) -> &str {
+        "TreeView"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+        let rect = self.rect.get();
+        let mut selector = self.selector.get();
+        if focused {
+            selector = selector.with_pseudo_class("focus");
+        }
+
+        draw_box(renderer, rect, theme, &selector);
+
+        let rows = self.rows();
+        let selected = self.selected_index(&rows);
+        let hover = self.hover.get();
+        let mut renderer = ClipRenderer::new(renderer, rect);
+
+        let mut y = rect.y - self.v_scroll.get();
+        for (i, row) in rows.iter().enumerate() {
+            if y + ROW_HEIGHT as i32 > rect.y && y < rect.y + rect.height as i32 {
+                //TODO: set this selector as the child of self.selector
+                let mut row_selector = Selector::new(Some("tree-view-row"));
+                if selected == Some(i) {
+                    row_selector = row_selector.with_pseudo_class("active");
+                } else if hover == Some(i) {
+                    row_selector = row_selector.with_pseudo_class("hover");
+                }
+
+                let row_rect = Rect::new(rect.x, y, rect.width, ROW_HEIGHT);
+                draw_box(&mut renderer, row_rect, theme, &row_selector);
+
+                let color = theme.color("color", &row_selector);
+                let x = rect.x + 4 + row.depth as i32 * INDENT;
+                if row.node.is_expandable() {
+                    self.draw_expander(&mut renderer, x + 2, y + ROW_HEIGHT as i32 / 2, row.node.expanded.get(), color);
+                }
+
+                let mut text_x = x + INDENT;
+                for c in row.node.text.borrow().chars() {
+                    if text_x + 8 <= rect.x + rect.width as i32 {
+                        renderer.char(text_x, y + (ROW_HEIGHT as i32 - 16) / 2, c, color);
+                    }
+                    text_x += 8;
+                }
+            }
+
+            y += ROW_HEIGHT as i32;
+        }
+    }
+
+    fn event(&self, event: Event, mut focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                let rows = self.rows();
+                let index = self.row_index(point, &rows);
+
+                if self.hover.check_set(index) {
+                    *redraw = true;
+                }
+
+                if self.rect.get().contains(point) {
+                    let mut click = false;
+                    if left_button {
+                        self.pressed.set(true);
+                    } else if self.pressed.check_set(false) {
+                        click = true;
+                    }
+
+                    if click {
+                        focused = true;
+                        *redraw = true;
+
+                        if let Some(index) = index {
+                            let row = &rows[index];
+                            let expander_x = self.rect.get().x + 4 + row.depth as i32 * INDENT;
+
+                            if row.node.is_expandable() && point.x >= expander_x && point.x < expander_x + INDENT {
+                                self.toggle(&row.node);
+                            } else {
+                                self.select_row(&rows, index);
+
+                                let now = Instant::now();
+                                let double_click = match self.last_click.get() {
+                                    Some((last_index, time)) => {
+                                        last_index == index
+                                            && now.duration_since(time) < Duration::from_millis(DOUBLE_CLICK_TIME)
+                                    }
+                                    None => false,
+                                };
+
+                                if double_click {
+                                    self.last_click.set(None);
+                                    self.emit_activate(row.node.clone());
+                                } else {
+                                    self.last_click.set(Some((index, now)));
+                                }
+                            }
+                        }
+                    }
+
+                    *caught = true;
+                } else if !left_button {
+                    self.pressed.set(false);
+                }
+            }
+            Event::Scroll { y, .. } => {
+                if self.hover.get().is_some() {
+                    self.scroll(-y * ROW_HEIGHT as i32 * 3);
+                    *redraw = true;
+                }
+            }
+            Event::KeyPressed(key_event) if focused => {
+                let rows = self.rows();
+                if rows.is_empty() {
+                    return focused;
+                }
+
+                let selected = self.selected_index(&rows);
+                let last = rows.len() - 1;
+
+                match key_event.scancode {
+                    orbclient::K_UP => {
+                        self.select_row(&rows, selected.map(|i| i.saturating_sub(1)).unwrap_or(0));
+                    }
+                    orbclient::K_DOWN => {
+                        self.select_row(&rows, selected.map(|i| min(last, i + 1)).unwrap_or(0));
+                    }
+                    orbclient::K_HOME => self.select_row(&rows, 0),
+                    orbclient::K_END => self.select_row(&rows, last),
+                    orbclient::K_RIGHT => if let Some(i) = selected {
+                        let node = &rows[i].node;
+                        if !node.expanded.get() {
+                            if node.is_expandable() {
+                                self.toggle(node);
+                            }
+                        } else if i < last && rows[i + 1].depth > rows[i].depth {
+                            self.select_row(&rows, i + 1);
+                        }
+                    },
+                    orbclient::K_LEFT => if let Some(i) = selected {
+                        let node = &rows[i].node;
+                        if node.expanded.get() {
+                            self.toggle(node);
+                        } else if let Some(parent) = rows[..i].iter().rposition(|row| row.depth < rows[i].depth) {
+                            self.select_row(&rows, parent);
+                        }
+                    },
+                    orbclient::K_ENTER => if let Some(i) = selected {
+                        self.emit_activate(rows[i].node.clone());
+                    },
+                    _ => return focused,
+                }
+
+                *redraw = true;
+            }
+            _ => (),
+        }
+
+        focused
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
@@ -0,0 +1,379 @@
+use orbclient::Renderer;
+use std::cell::{Cell, RefCell};
+use std::cmp::{max, min};
+use std::sync::Arc;
+use orbclient;
+
+use cell::{CheckSet, CloneCell};
+use draw::{draw_box, ClipRenderer};
+use event::Event;
+use point::Point;
+use rect::Rect;
+use thickness::Thickness;
+use theme::{Selector, Theme};
+use traits::{Change, Place, Style};
+use widgets::{HorizontalPlacement, VerticalPlacement, Widget};
+
+/// A list that shows a large count of items. Row widgets are only created for the
+/// visible rows and are recycled while scrolling: the `create` function of the source
+/// builds a new row widget and `bind` fills a row widget with the item of an index.
+/// The row widget of the selected item gets the `active` pseudo-class.
+pub struct VirtualList<T: Widget + Style> {
+    pub rect: Cell<Rect>,
+    local_position: Cell<Point>,
+    vertical_placement: Cell<VerticalPlacement>,
+    horizontal_placement: Cell<HorizontalPlacement>,
+    margin: Cell<Thickness>,
+    children: RefCell<Vec<Arc<dyn Widget>>>,
+    pub selector: CloneCell<Selector>,
+    pub row_height: Cell<u32>,
+    item_count: Cell<usize>,
+    create: RefCell<Option<Arc<dyn Fn() -> Arc<T>>>>,
+    bind: RefCell<Option<Arc<dyn Fn(&Arc<T>, usize)>>>,
+    /// The recycled row widgets with the index they are bound to. The row of an
+    /// index is always shown by the widget at `index % rows.len()`.
+    rows: RefCell<Vec<(Option<usize>, Arc<T>)>>,
+    v_scroll: Cell<i32>,
+    selected: Cell<Option<usize>>,
+    change_callback: RefCell<Option<Arc<dyn Fn(&VirtualList<T>, usize)>>>,
+    pressed: Cell<bool>,
+    hover: Cell<bool>,
+}
+
+impl<T: Widget + Style> VirtualList<T> {
+    pub fn new() -> Arc<Self> {
+        Arc::new(VirtualList {
+            rect: Cell::new(Rect::default()),
+            local_position: Cell::new(Point::new(0, 0)),
+            vertical_placement: Cell::new(VerticalPlacement::Absolute),
+            horizontal_placement: Cell::new(HorizontalPlacement::Absolute),
+            margin: Cell::new(Thickness::default()),
+            children: RefCell::new(vec![]),
+            selector: CloneCell::new(Selector::new(Some("list"))),
+            row_height: Cell::new(20),
+            item_count: Cell::new(0),
+            create: RefCell::new(None),
+            bind: RefCell::new(None),
+            rows: RefCell::new(vec![]),
+            v_scroll: Cell::new(0),
+            selected: Cell::new(None),
+            change_callback: RefCell::new(None),
+            pressed: Cell::new(false),
+            hover: Cell::new(false),
+        })
+    }
+
+    /// Set the data source of the list.
+    pub fn source<C, B>(&self, item_count: usize, create: C, bind: B) -> &Self
+    where
+        C: Fn() -> Arc<T> + 'static,
+        B: Fn(&Arc<T>, usize) + 'static,
+    {
+        *self.create.borrow_mut() = Some(Arc::new(create));
+        *self.bind.borrow_mut() = Some(Arc::new(bind));
+        self.rows.borrow_mut().clear();
+        self.item_count(item_count);
+        self
+    }
+
+    /// Set the count of items, e.g. after items were appended to the data.
+    pub fn item_count(&self, item_count: usize) -> &Self {
+        self.item_count.set(item_count);
+        if let Some(selected) = self.selected.get() {
+            if selected >= item_count {
+                self.selected.set(None);
+            }
+        }
+        self.scroll(0);
+        self.refresh();
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.item_count.get()
+    }
+
+    pub fn row_height(&self, row_height: u32) -> &Self {
+        self.row_height.set(max(1, row_height));
+        self.arrange();
+        self
+    }
+
+    /// Bind the visible rows again, e.g. after the data of some items changed.
+    pub fn refresh(&self) {
+        for row in self.rows.borrow_mut().iter_mut() {
+            row.0 = None;
+        }
+        self.arrange();
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected.get()
+    }
+
+    /// Select the item with the given index without emitting a change.
+    pub fn select(&self, index: usize) {
+        if index < self.item_count.get() {
+            self.selected.set(Some(index));
+            self.scroll_into_view(index);
+        }
+    }
+
+    /// Scroll as little as possible to make the item with the given index visible.
+    pub fn scroll_into_view(&self, index: usize) {
+        let row_height = self.row_height.get() as i32;
+        let height = self.rect.get().height as i32;
+        let y = index as i32 * row_height;
+        let v_scroll = self.v_scroll.get();
+
+        if y < v_scroll {
+            self.scroll(y - v_scroll);
+        } else if y + row_height > v_scroll + height {
+            self.scroll(y + row_height - v_scroll - height);
+        }
+    }
+
+    /// Scroll the list by the given distance.
+    pub fn scroll(&self, y: i32) {
+        let content_height = self.item_count.get() as i32 * self.row_height.get() as i32;
+        let max_scroll = max(0, content_height - self.rect.get().height as i32);
+        self.v_scroll.set(max(0, min(max_scroll, self.v_scroll.get() + y)));
+        self.arrange();
+    }
+
+    /// Get the range of item indices that are visible.
+    fn visible_range(&self) -> (usize, usize) {
+        let row_height = self.row_height.get() as i32;
+        let first = (self.v_scroll.get() / row_height) as usize;
+        let count = self.rect.get().height as usize / row_height as usize + 2;
+        (min(first, self.item_count.get()), min(first + count, self.item_count.get()))
+    }
+
+    fn item_index(&self, point: Point) -> Option<usize> {
+        let rect = self.rect.get();
+        if !rect.contains(point) {
+            return None;
+        }
+
+        let index = ((point.y - rect.y + self.v_scroll.get()) / self.row_height.get() as i32) as usize;
+        if index < self.item_count.get() {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    fn change_selection(&self, index: usize) {
+        if self.selected.check_set(Some(index)) {
+            self.scroll_into_view(index);
+            self.emit_change(index);
+        }
+    }
+}
+
+impl<T: Widget + Style> Change for VirtualList<T> {
+    type Value = usize;
+
+    fn emit_change(&self, index: usize) {
+        if let Some(ref change_callback) = *self.change_callback.borrow() {
+            change_callback(self, index);
+        }
+    }
+
+    fn on_change<F: Fn(&Self, usize) + 'static>(&self, func: F) -> &Self {
+        *self.change_callback.borrow_mut() = Some(Arc::new(func));
+        self
+    }
+}
+
+impl<T: Widget + Style> Place for VirtualList<T> {}
+
+impl<T: Widget + Style> Style for VirtualList<T> {
+    fn selector(&self) -> &CloneCell<Selector> {
+        &self.selector
+    }
+}
+
+impl<T: Widget + Style> Widget for VirtualList<T> {
+    fn name(&self) -> &str {
+        "VirtualList"
+    }
+
+    fn rect(&self) -> &Cell<Rect> {
+        &self.rect
+    }
+
+    fn local_position(&self) -> &Cell<Point> {
+        &self.local_position
+    }
+
+    fn vertical_placement(&self) -> &Cell<VerticalPlacement> {
+        &self.vertical_placement
+    }
+
+    fn horizontal_placement(&self) -> &Cell<HorizontalPlacement> {
+        &self.horizontal_placement
+    }
+
+    fn margin(&self) -> &Cell<Thickness> {
+        &self.margin
+    }
+
+    /// Create missing row widgets, bind the rows that became visible and move them into place.
+    fn arrange(&self) {
+        let create = match *self.create.borrow() {
+            Some(ref create) => create.clone(),
+            None => return,
+        };
+        let bind = match *self.bind.borrow() {
+            Some(ref bind) => bind.clone(),
+            None => return,
+        };
+
+        let rect = self.rect.get();
+        let row_height = self.row_height.get();
+        let capacity = rect.height as usize / row_height as usize + 2;
+
+        let mut rows = self.rows.borrow_mut();
+        if rows.len() < capacity {
+            // the rows of the indices change with the count of widgets
+            for row in rows.iter_mut() {
+                row.0 = None;
+            }
+            while rows.len() < capacity {
+                rows.push((None, create()));
+            }
+        }
+
+        let (first, last) = self.visible_range();
+        let len = rows.len();
+        for index in first..last {
+            let row = &mut rows[index % len];
+            if row.0 != Some(index) {
+                bind(&row.1, index);
+                row.0 = Some(index);
+            }
+
+            row.1.rect().set(Rect::new(
+                rect.x,
+                rect.y + index as i32 * row_height as i32 - self.v_scroll.get(),
+                rect.width,
+                row_height,
+            ));
+            row.1.arrange();
+        }
+    }
+
+    fn update(&self) {
+        let mut selector = self.selector.get().without_pseudo_class("hover");
+        if self.hover.get() {
+            selector = selector.with_pseudo_class("hover");
+        }
+        self.selector.set(selector);
+    }
+
+    fn draw(&self, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+        fn draw_widget(widget: &Arc<dyn Widget>, renderer: &mut dyn Renderer, focused: bool, theme: &Theme) {
+            widget.update();
+            widget.draw(renderer, focused, theme);
+
+            for child in widget.children().borrow().iter() {
+                draw_widget(child, renderer, focused, theme);
+            }
+        }
+
+        // the rect could have changed without an arrange, e.g. by a stretch placement
+        self.arrange();
+
+        let rect = self.rect.get();
+        let mut selector = self.selector.get();
+        if focused {
+            selector = selector.with_pseudo_class("focus");
+        }
+        draw_box(renderer, rect, theme, &selector);
+
+        let rows = self.rows.borrow();
+        if rows.is_empty() {
+            return;
+        }
+
+        let mut renderer = ClipRenderer::new(renderer, rect);
+        let (first, last) = self.visible_range();
+        for index in first..last {
+            let row = &rows[index % rows.len()];
+            let row_rect = row.1.rect().get();
+
+            //TODO: set this selector as the child of self.selector
+            let mut row_selector = Selector::new(Some("entry"));
+            let mut widget_selector = row.1.selector().get().without_pseudo_class("active");
+            if self.selected.get() == Some(index) {
+                row_selector = row_selector.with_pseudo_class("active");
+                widget_selector = widget_selector.with_pseudo_class("active");
+            }
+            draw_box(&mut renderer, row_rect, theme, &row_selector);
+            row.1.selector().set(widget_selector);
+
+            let widget: Arc<dyn Widget> = row.1.clone();
+            draw_widget(&widget, &mut renderer, false, theme);
+        }
+    }
+
+    fn event(&self, event: Event, mut focused: bool, redraw: &mut bool, caught: &mut bool) -> bool {
+        match event {
+            Event::Mouse { point, left_button, .. } => {
+                let rect = self.rect.get();
+                if self.hover.check_set(rect.contains(point)) {
+                    *redraw = true;
+                }
+
+                if rect.contains(point) {
+                    if left_button {
+                        self.pressed.set(true);
+                    } else if self.pressed.check_set(false) {
+                        if let Some(index) = self.item_index(point) {
+                            self.change_selection(index);
+                        }
+                        focused = true;
+                        *redraw = true;
+                    }
+
+                    *caught = true;
+                } else if !left_button {
+                    self.pressed.set(false);
+                }
+            }
+            Event::Scroll { y, .. } => if self.hover.get() {
+                self.scroll(-y * self.row_height.get() as i32 * 3);
+                *redraw = true;
+            },
+            Event::KeyPressed(key_event) if focused => {
+                let count = self.item_count.get();
+                if count == 0 {
+                    return focused;
+                }
+
+                let page = max(1, self.rect.get().height as usize / self.row_height.get() as usize);
+                let selected = self.selected.get();
+                let index = match key_event.scancode {
+                    orbclient::K_UP => selected.map(|i| i.saturating_sub(1)).unwrap_or(0),
+                    orbclient::K_DOWN => selected.map(|i| min(count - 1, i + 1)).unwrap_or(0),
+                    orbclient::K_PGUP => selected.map(|i| i.saturating_sub(page)).unwrap_or(0),
+                    orbclient::K_PGDN => selected.map(|i| min(count - 1, i + page)).unwrap_or(0),
+                    orbclient::K_HOME => 0,
+                    orbclient::K_END => count - 1,
+                    _ => return focused,
+                };
+
+                self.change_selection(index);
+                *redraw = true;
+            }
+            _ => (),
+        }
+
+        focused
+    }
+
+    fn children(&self) -> &RefCell<Vec<Arc<dyn Widget>>> {
+        &self.children
+    }
+}
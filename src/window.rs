@@ -2,16 +2,74 @@ extern crate orbfont;
 
 use orbclient::{self, Renderer, Mode, WindowFlag};
 use orbclient::color::Color;
+use std::any::Any;
 use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::sync::Arc;
-
-use super::{Event, FocusManager, KeyEvent, Point, Rect, Widget};
+use std::time::{Duration, Instant};
+
+use super::{Dialog, DialogHost, Event, FocusManager, KeyEvent, Modifiers, Point, Rect, Widget};
+use widgets::{close_popups, collect_popups, tick_widgets, tree_needs_tick};
+use animation;
+use clipboard;
+use context_menu::ContextMenuManager;
+use dispatcher::{DispatchQueue, Dispatcher};
+use shortcut::ShortcutRegistry;
 use theme::Theme;
+use timer;
+use tooltip::TooltipManager;
 use traits::Resize;
 
 pub use orbclient::Window as InnerWindow;
 
+/// How often a window looks for input while it waits for the next deadline or a
+/// dispatched closure, in milliseconds.
+const INPUT_POLL: u64 = 10;
+/// The time between the frames of a running animation, in milliseconds.
+const FRAME_TIME: u64 = 16;
+
+/// The scancode of the menu key, which opens the context menu of the focused widget.
+const K_MENU: u8 = 0x5D;
+
+/// Add the async flag, the window polls its events to be able to run timers on time and
+/// to wake up for dispatched closures.
+fn async_flags(flags: &[WindowFlag]) -> Vec<WindowFlag> {
+    let mut flags = flags.to_vec();
+    if !flags.iter().any(|flag| match *flag {
+        WindowFlag::Async => true,
+        _ => false,
+    }) {
+        flags.push(WindowFlag::Async);
+    }
+    flags
+}
+
+/// Identifies a window. Timers and animations belong to the window whose event loop
+/// runs when they are started.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WindowId(u64);
+
+thread_local! {
+    static NEXT_WINDOW_ID: Cell<u64> = Cell::new(0);
+    /// The windows whose event loops run, a nested event loop, e.g. of a file dialog, is the last one
+    static RUNNING_WINDOWS: RefCell<Vec<WindowId>> = RefCell::new(vec![]);
+}
+
+impl WindowId {
+    fn next() -> Self {
+        NEXT_WINDOW_ID.with(|next_id| {
+            let id = next_id.get();
+            next_id.set(id + 1);
+            WindowId(id)
+        })
+    }
+}
+
+/// Get the window whose event loop runs, the innermost one if event loops are nested.
+pub fn current_window() -> Option<WindowId> {
+    RUNNING_WINDOWS.with(|windows| windows.borrow().last().cloned())
+}
+
 pub struct WindowRenderer<'a> {
     inner: &'a mut InnerWindow,
     font: &'a Option<orbfont::Font>,
@@ -79,9 +137,18 @@ pub struct Window {
     mouse_left: bool,
     mouse_middle: bool,
     mouse_right: bool,
+    modifiers: Modifiers,
     events: VecDeque<Event>,
     redraw: bool,
     focus_manager: FocusManager,
+    shortcuts: ShortcutRegistry,
+    tooltips: TooltipManager,
+    context_menus: ContextMenuManager,
+    dialogs: DialogHost,
+    dispatch_queue: DispatchQueue,
+    id: WindowId,
+    /// Set if a widget needs a tick every frame
+    ticking: bool,
 }
 
 impl Resize for Window {
@@ -104,13 +171,17 @@ impl Window {
 
     pub fn new_flags(rect: Rect, title: &str, flags: &[WindowFlag]) -> Self {
         Window::from_inner(
-            InnerWindow::new_flags(rect.x, rect.y, rect.width, rect.height, title, flags).unwrap(),
+            InnerWindow::new_flags(rect.x, rect.y, rect.width, rect.height, title, &async_flags(flags)).unwrap(),
         )
     }
 
+    /// Create a window from an inner window. The inner window should be created with
+    /// `WindowFlag::Async`, otherwise timers and dispatched closures wait for its next event.
     pub fn from_inner(inner: InnerWindow) -> Self {
         let mut events = VecDeque::new();
         events.push_back(Event::Init);
+        let dialogs = DialogHost::new();
+        dialogs.resize(inner.width(), inner.height());
         Window {
             inner: RefCell::new(inner),
             font: orbfont::Font::find(None, None, None).ok(),
@@ -122,12 +193,24 @@ impl Window {
             mouse_left: false,
             mouse_right: false,
             mouse_middle: false,
+            modifiers: Modifiers::default(),
             events: events,
             redraw: true,
             focus_manager: FocusManager::new(),
+            shortcuts: ShortcutRegistry::new(),
+            tooltips: TooltipManager::new(),
+            context_menus: ContextMenuManager::new(),
+            dialogs,
+            dispatch_queue: DispatchQueue::new(),
+            id: WindowId::next(),
+            ticking: false,
         }
     }
 
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
     pub fn into_inner(self) -> InnerWindow {
         self.inner.into_inner()
     }
@@ -188,6 +271,57 @@ impl Window {
         id
     }
 
+    /// Borrow the keyboard shortcut registry of the window.
+    pub fn shortcuts(&self) -> &ShortcutRegistry {
+        &self.shortcuts
+    }
+
+    /// Show a modal dialog centered in the window, see `DialogHost::show`.
+    pub fn show_dialog(&self, dialog: &Arc<Dialog>) {
+        self.dialogs.show(dialog);
+    }
+
+    /// Borrow the dialog host of the window. Clone it to show dialogs from callbacks.
+    pub fn dialogs(&self) -> &DialogHost {
+        &self.dialogs
+    }
+
+    /// Borrow the tooltip manager of the window.
+    pub fn tooltips(&self) -> &TooltipManager {
+        &self.tooltips
+    }
+
+    /// Borrow the context menu manager of the window.
+    pub fn context_menus(&self) -> &ContextMenuManager {
+        &self.context_menus
+    }
+
+    /// Get a dispatcher that queues closures and messages for the window from other threads.
+    pub fn dispatcher(&self) -> Dispatcher {
+        self.dispatch_queue.dispatcher()
+    }
+
+    /// Set the handler of the messages of a type that are sent with a dispatcher. The
+    /// handler runs on the thread of the window and could change the widgets.
+    pub fn on_message<T: Any + Send, F: Fn(&Window, T) + 'static>(&self, func: F) {
+        self.dispatch_queue.on_message(func);
+    }
+
+    fn bounds(&self) -> Rect {
+        Rect::new(0, 0, self.width(), self.height())
+    }
+
+    /// Execute the command with the given name as if its shortcut was pressed.
+    /// Returns false if there is no such command.
+    pub fn execute(&self, name: &str) -> bool {
+        if let Some(command) = self.shortcuts.command(name) {
+            command(self);
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn draw(&self) {
         let mut inner = self.inner.borrow_mut();
         inner.set(self.theme.color("background", &"window".into()));
@@ -196,6 +330,83 @@ impl Window {
         for widget in self.widgets.borrow().iter() {
             self.draw_widget(&mut renderer, self.focus_manager.focused(&widget), widget);
         }
+
+        for dialog in self.dialogs.open_dialogs() {
+            let overlay = self.theme.color("background", &"dialog-overlay".into());
+            renderer.rect(0, 0, renderer.width(), renderer.height(), overlay);
+
+            dialog.update();
+            dialog.draw(&mut renderer, true, &self.theme);
+        }
+
+        // popups and tooltips are drawn above everything else
+        for (_, popup) in self.popups() {
+            self.draw_widget(&mut renderer, true, &popup);
+        }
+
+        self.tooltips.draw(&mut renderer, &self.theme);
+    }
+
+    /// Get the open popups with the widgets they belong to, the topmost popup is the last one.
+    /// While a dialog is open only the popups of the topmost dialog are shown.
+    fn popups(&self) -> Vec<(Arc<dyn Widget>, Arc<dyn Widget>)> {
+        let mut popups = vec![];
+        match self.dialogs.open_dialogs().pop() {
+            Some(dialog) => {
+                let dialog: Arc<dyn Widget> = dialog;
+                collect_popups(&dialog, &mut popups);
+            }
+            None => for widget in self.widgets.borrow().iter() {
+                collect_popups(widget, &mut popups);
+            },
+        }
+
+        if let Some(menu) = self.context_menus.open_menu() {
+            let menu: Arc<dyn Widget> = menu;
+            collect_popups(&menu, &mut popups);
+        }
+
+        popups
+    }
+
+    /// Offer an event to the open popups. A click outside of a popup and its widget closes
+    /// it, Escape closes the topmost popup. Returns true if a popup caught the event.
+    fn popup_event(&mut self, event: Event) -> bool {
+        let popups = self.popups();
+        if popups.is_empty() {
+            return false;
+        }
+
+        match event {
+            Event::KeyPressed(key_event) if key_event.scancode == orbclient::K_ESC => {
+                if let Some(&(ref widget, _)) = popups.last() {
+                    widget.close_popup();
+                }
+                self.redraw = true;
+                return true;
+            }
+            Event::Mouse { point, left_button, middle_button, right_button, .. } => {
+                if left_button || middle_button || right_button {
+                    for &(ref widget, ref popup) in popups.iter() {
+                        if !popup.rect().get().contains(point) && !widget.rect().get().contains(point) {
+                            widget.close_popup();
+                            self.redraw = true;
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        let mut caught = false;
+        for &(_, ref popup) in popups.iter().rev() {
+            popup.event(event, true, &mut self.redraw, &mut caught);
+            if caught {
+                return true;
+            }
+        }
+
+        false
     }
 
     fn draw_widget(&self, renderer: &mut dyn Renderer, focused: bool, widget: &Arc<dyn Widget>) {
@@ -212,16 +423,157 @@ impl Window {
     }
 
     pub fn step(&mut self) {
+        RUNNING_WINDOWS.with(|windows| windows.borrow_mut().push(self.id));
         self.drain_orbital_events();
+        self.run_timers();
         self.drain_events();
+        self.run_dispatched();
+        if self.tooltips.update() {
+            self.redraw = true;
+        }
+        self.tick();
+        RUNNING_WINDOWS.with(|windows| windows.borrow_mut().pop());
+    }
+
+    /// Run the closures and messages that were queued by the dispatchers of the window.
+    /// The window is redrawn after something was run.
+    pub fn run_dispatched(&mut self) {
+        if self.dispatch_queue.run(self) {
+            self.redraw = true;
+        }
+    }
+
+    /// Run the timers that expired and queue their events. The window is redrawn after a
+    /// timer expired, its callback could have changed the widgets.
+    pub fn run_timers(&mut self) {
+        for id in timer::run(self.id, Instant::now()) {
+            self.events.push_back(Event::Timer(id));
+            self.redraw = true;
+        }
+    }
+
+    /// Advance the animations and the animated widgets, the open dialogs and the popups.
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        let mut redraw = animation::tick(self.id, now);
+        let mut ticking = false;
+
+        for widget in self.widgets.borrow().iter() {
+            redraw |= tick_widgets(widget, now);
+            ticking |= tree_needs_tick(widget);
+        }
+
+        for dialog in self.dialogs.open_dialogs() {
+            let dialog: Arc<dyn Widget> = dialog;
+            redraw |= tick_widgets(&dialog, now);
+            ticking |= tree_needs_tick(&dialog);
+        }
+
+        for (_, popup) in self.popups() {
+            redraw |= tick_widgets(&popup, now);
+            ticking |= tree_needs_tick(&popup);
+        }
+
+        self.ticking = ticking;
+
+        if redraw {
+            self.redraw = true;
+        }
     }
 
     pub fn drain_events(&mut self) {
         while let Some(event) = self.events.pop_front() {
+            if let Event::Resize { width, height } = event {
+                self.dialogs.resize(width, height);
+                self.emit_resize(width, height);
+            }
+
+            // timer events are not modal, every widget could have started the timer
+            if let Event::Timer(_) = event {
+                self.timer_event(event);
+                continue;
+            }
+
+            // the right button is tracked before a popup could catch the event
+            let right_released = match event {
+                Event::Mouse { right_button, .. } => self.context_menus.right_button(right_button),
+                _ => false,
+            };
+
+            if self.popup_event(event) {
+                continue;
+            }
+
+            // an open dialog is modal and gets the events instead of the widgets and shortcuts
+            let dialog = self.dialogs.open_dialogs().pop();
+            if let Some(dialog) = dialog {
+                if self.tooltips.hide() {
+                    self.redraw = true;
+                }
+                for widget in self.widgets.borrow().iter() {
+                    close_popups(widget);
+                }
+
+                let mut caught = false;
+                dialog.event(event, true, &mut self.redraw, &mut caught);
+
+                if self.dialogs.remove_closed() {
+                    self.redraw = true;
+                }
+                continue;
+            }
+
+            match event {
+                Event::Mouse { point, .. } if right_released => {
+                    if self.context_menus.open_at_point(point, &self.widgets.borrow(), self.bounds()) {
+                        self.tooltips.hide();
+                        self.redraw = true;
+                        continue;
+                    }
+                }
+                Event::KeyPressed(key_event) if key_event.scancode == K_MENU
+                    || (key_event.scancode == orbclient::K_F10 && key_event.modifiers.shift) => {
+                    let focused_widget = self.focus_manager.focused_widget().borrow().clone();
+                    if let Some(focused_widget) = focused_widget {
+                        if self.context_menus.open_for(&focused_widget, self.bounds()) {
+                            self.tooltips.hide();
+                            self.redraw = true;
+                            continue;
+                        }
+                    }
+                }
+                _ => (),
+            }
+
+            // shortcuts are processed before the event reaches any widget
+            if let Event::KeyPressed(key_event) = event {
+                let command = {
+                    let focused_widget = self.focus_manager.focused_widget().borrow();
+                    self.shortcuts.find(&key_event, focused_widget.as_ref())
+                };
+
+                if let Some(command) = command {
+                    command(self);
+                    self.redraw = true;
+                    continue;
+                }
+            }
+
             match event {
-                Event::Resize { width, height } => {
-                    self.emit_resize(width, height);
+                Event::Mouse { point, left_button, middle_button, right_button, .. } => {
+                    let hidden = if left_button || middle_button || right_button {
+                        self.tooltips.hide()
+                    } else {
+                        self.tooltips.mouse_moved(point, &self.widgets.borrow())
+                    };
+
+                    if hidden {
+                        self.redraw = true;
+                    }
                 }
+                Event::KeyPressed(_) | Event::Scroll { .. } => if self.tooltips.hide() {
+                    self.redraw = true;
+                },
                 _ => (),
             }
 
@@ -239,9 +591,65 @@ impl Window {
                 }
             }
         }
+
+        // store the text that was copied by a widget in the system clipboard
+        if let Some(text) = clipboard::take_changed() {
+            self.inner.borrow_mut().set_clipboard(&text);
+        }
+    }
+
+    fn timer_event(&mut self, event: Event) {
+        for widget in self.widgets.borrow().iter() {
+            let mut caught = false;
+            widget.event(event, self.focus_manager.focused(&widget), &mut self.redraw, &mut caught);
+        }
+
+        for dialog in self.dialogs.open_dialogs() {
+            let mut caught = false;
+            dialog.event(event, true, &mut self.redraw, &mut caught);
+        }
+
+        for (_, popup) in self.popups() {
+            let mut caught = false;
+            popup.event(event, false, &mut self.redraw, &mut caught);
+        }
+    }
+
+    /// Get the time something needs to be done, i.e. the next timer expires or the next
+    /// frame of an animation or a ticking widget is due. `None` if nothing is scheduled.
+    fn deadline(&self) -> Option<Instant> {
+        let frame = if animation::is_animating(self.id) || self.ticking {
+            Some(Instant::now() + Duration::from_millis(FRAME_TIME))
+        } else {
+            None
+        };
+
+        match (timer::next_deadline(self.id), frame) {
+            (Some(deadline), Some(frame)) => Some(deadline.min(frame)),
+            (deadline, frame) => deadline.or(frame),
+        }
+    }
+
+    /// Sleep until the next deadline or until a dispatcher queues something. The window
+    /// looks for input every `INPUT_POLL` milliseconds meanwhile.
+    fn wait(&self) {
+        if !self.events.is_empty() || !self.running.get() {
+            return;
+        }
+
+        let now = Instant::now();
+        let input_poll = Duration::from_millis(INPUT_POLL);
+        let timeout = match self.deadline() {
+            Some(deadline) if deadline <= now => return,
+            Some(deadline) => (deadline - now).min(input_poll),
+            None => input_poll,
+        };
+
+        self.dispatch_queue.wait(timeout);
     }
 
     pub fn drain_orbital_events(&mut self) {
+        let mut input = false;
         for orbital_event in self.inner.borrow_mut().events() {
             match orbital_event.to_option() {
                 orbclient::EventOption::Mouse(mouse_event) => {
@@ -253,9 +661,11 @@ impl Window {
                         left_button: self.mouse_left,
                         middle_button: self.mouse_middle,
                         right_button: self.mouse_right,
+                        modifiers: self.modifiers,
                     })
                 }
                 orbclient::EventOption::Button(button_event) => {
+                    input = true;
                     self.mouse_left = button_event.left;
                     self.mouse_middle = button_event.middle;
                     self.mouse_right = button_event.right;
@@ -265,6 +675,7 @@ impl Window {
                         left_button: self.mouse_left,
                         middle_button: self.mouse_middle,
                         right_button: self.mouse_right,
+                        modifiers: self.modifiers,
                     })
                 }
                 orbclient::EventOption::Scroll(scroll_event) => {
@@ -273,15 +684,19 @@ impl Window {
                         y: scroll_event.y,
                     })
                 }
-                orbclient::EventOption::Key(key_event) => if key_event.pressed {
-                    self.events.push_back(Event::KeyPressed(
-                        KeyEvent::from_orbital_key_event(key_event),
-                    ));
-                } else {
-                    self.events.push_back(Event::KeyReleased(
-                        KeyEvent::from_orbital_key_event(key_event),
-                    ));
-                },
+                orbclient::EventOption::Key(orbital_key_event) => {
+                    input = true;
+                    self.modifiers.update(&orbital_key_event);
+
+                    let mut key_event = KeyEvent::from_orbital_key_event(orbital_key_event);
+                    key_event.modifiers = self.modifiers;
+
+                    if orbital_key_event.pressed {
+                        self.events.push_back(Event::KeyPressed(key_event));
+                    } else {
+                        self.events.push_back(Event::KeyReleased(key_event));
+                    }
+                }
                 orbclient::EventOption::Resize(resize_event) => {
                     self.redraw = true;
                     self.events.push_back(Event::Resize {
@@ -295,14 +710,34 @@ impl Window {
                 _ => (),
             };
         }
+
+        // a key press or a click could paste the text of the system clipboard
+        if input {
+            let text = self.inner.borrow().clipboard();
+            clipboard::load(text);
+        }
     }
 
+    /// Run the event loop until the window is closed. The timers and animations of the
+    /// window are stopped when it returns.
     pub fn exec(&mut self) {
+        RUNNING_WINDOWS.with(|windows| windows.borrow_mut().push(self.id));
         'event: while self.running.get() {
+            self.run_timers();
             self.drain_events();
+            self.run_dispatched();
+            if self.tooltips.update() {
+                self.redraw = true;
+            }
+            self.tick();
             self.draw_if_needed();
             self.drain_orbital_events();
+            self.wait();
         }
+        RUNNING_WINDOWS.with(|windows| windows.borrow_mut().pop());
+
+        timer::stop_window(self.id);
+        animation::stop_window(self.id);
     }
 
     pub fn needs_redraw(&mut self) {
@@ -360,7 +795,7 @@ impl<'a> WindowBuilder<'a> {
         };
 
         let inner =
-            InnerWindow::new_flags(rect.x, rect.y, rect.width, rect.height, title, flags).unwrap();
+            InnerWindow::new_flags(rect.x, rect.y, rect.width, rect.height, title, &async_flags(flags)).unwrap();
 
         let theme = match self.theme {
             Some(theme) => theme,
@@ -369,6 +804,8 @@ impl<'a> WindowBuilder<'a> {
 
         let mut events = VecDeque::new();
         events.push_back(Event::Init);
+        let dialogs = DialogHost::new();
+        dialogs.resize(rect.width, rect.height);
 
         Window {
             inner: RefCell::new(inner),
@@ -381,9 +818,17 @@ impl<'a> WindowBuilder<'a> {
             mouse_left: false,
             mouse_right: false,
             mouse_middle: false,
+            modifiers: Modifiers::default(),
             events: events,
             redraw: true,
             focus_manager: FocusManager::new(),
+            shortcuts: ShortcutRegistry::new(),
+            tooltips: TooltipManager::new(),
+            context_menus: ContextMenuManager::new(),
+            dialogs,
+            dispatch_queue: DispatchQueue::new(),
+            id: WindowId::next(),
+            ticking: false,
         }
     }
 }